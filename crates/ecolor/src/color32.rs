@@ -257,6 +257,53 @@ impl Color32 {
         ]
     }
 
+    /// Lerp from `self` (at `t = 0`) to `other` (at `t = 1`) in gamma space.
+    ///
+    /// Same as [`Self::lerp_to_gamma`], but takes `self` by value.
+    #[inline]
+    pub fn lerp(self, other: Self, t: f32) -> Self {
+        self.lerp_to_gamma(other, t)
+    }
+
+    /// The same color with the given (unmultiplied) alpha.
+    ///
+    /// `Color32::RED.with_alpha(128)` is a half-transparent red.
+    #[inline]
+    pub fn with_alpha(self, alpha: u8) -> Self {
+        let [r, g, b, _] = self.to_srgba_unmultiplied();
+        Self::from_rgba_unmultiplied(r, g, b, alpha)
+    }
+
+    /// The perceived brightness of the color in `0..=1`, from its gamma-encoded channels.
+    #[inline]
+    pub fn luma(self) -> f32 {
+        (0.299 * self.r() as f32 + 0.587 * self.g() as f32 + 0.114 * self.b() as f32) / 255.0
+    }
+
+    /// This color moved toward `target` until its [`Self::luma`] changed by `delta`,
+    /// or all the way to `target`.
+    pub fn nudged_toward(self, target: Self, delta: f32) -> Self {
+        // Luma is linear in the gamma-encoded channels, and so is `Self::lerp`:
+        let distance = (target.luma() - self.luma()).abs();
+        if distance <= delta {
+            return target;
+        }
+        self.lerp(target, delta / distance)
+    }
+
+    /// This color made lighter if it is dark, or darker if it is light,
+    /// until its [`Self::luma`] changed by `delta`, so it stands out against the original.
+    ///
+    /// Colors with a luma of `0.5` or more count as light.
+    pub fn nudged_for_contrast(self, delta: f32) -> Self {
+        let target = if self.luma() < 0.5 {
+            Self::WHITE
+        } else {
+            Self::BLACK
+        };
+        self.nudged_toward(target, delta)
+    }
+
     /// Lerp this color towards `other` by `t` in gamma space.
     pub fn lerp_to_gamma(&self, other: Self, t: f32) -> Self {
         use emath::lerp;
@@ -284,3 +331,46 @@ impl std::ops::Mul for Color32 {
         ])
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lerp_endpoints() {
+        let from = Color32::from_rgba_premultiplied(10, 20, 30, 40);
+        let to = Color32::from_rgba_premultiplied(200, 150, 100, 250);
+        assert_eq!(from.lerp(to, 0.0), from);
+        assert_eq!(from.lerp(to, 1.0), to);
+        assert_eq!(
+            Color32::BLACK.lerp(Color32::WHITE, 0.5),
+            Color32::from_gray(128)
+        );
+    }
+
+    #[test]
+    fn with_alpha_overrides_alpha() {
+        assert_eq!(Color32::RED.with_alpha(255), Color32::RED);
+        assert_eq!(Color32::RED.with_alpha(0), Color32::TRANSPARENT);
+
+        let half = Color32::RED.with_alpha(128);
+        assert_eq!(half.a(), 128);
+        assert_eq!(half.to_srgba_unmultiplied(), [255, 0, 0, 128]);
+        assert_eq!(half.with_alpha(255), Color32::RED);
+    }
+
+    #[test]
+    fn nudged_toward_changes_luma_by_delta() {
+        assert_eq!(Color32::BLACK.luma(), 0.0);
+        assert_eq!(Color32::WHITE.luma(), 1.0);
+
+        let gray = Color32::from_gray(40);
+        let nudged = gray.nudged_toward(Color32::WHITE, 0.1);
+        assert!((nudged.luma() - gray.luma() - 0.1).abs() < 0.01);
+        assert_eq!(gray.nudged_toward(Color32::BLACK, 1.0), Color32::BLACK);
+
+        assert!(gray.nudged_for_contrast(0.1).luma() > gray.luma());
+        let light = Color32::from_gray(200);
+        assert!(light.nudged_for_contrast(0.1).luma() < light.luma());
+    }
+}
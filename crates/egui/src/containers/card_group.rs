@@ -0,0 +1,255 @@
+//! A grid of [`ExtFrame`] cards that can be navigated with the arrow keys.
+
+use crate::{
+    containers::frame_ext::ExtFrame, EventFilter, Id, Key, Response, Sense, Ui, UiBuilder, Vec2,
+};
+use emath::GuiRounding as _;
+use epaint::{Margin, Rect};
+
+/// A grid of equally sized cards, each painted with an [`ExtFrame`].
+///
+/// Every card can be clicked and focused. When a card has keyboard focus,
+/// the arrow keys move the focus to the neighboring card in the grid,
+/// and Space or Enter activates it.
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// use egui::containers::CardGroup;
+///
+/// let response = CardGroup::new("projects", 2).show(ui, 4, |ui, card| {
+///     ui.label(format!("Project {card}"));
+/// });
+/// if let Some(card) = response.activated {
+///     println!("Opening project {card}");
+/// }
+/// # });
+/// ```
+#[derive(Clone, Debug)]
+#[must_use = "You should call .show()"]
+pub struct CardGroup {
+    pub id: Id,
+    pub columns: usize,
+    pub card_size: Vec2,
+    pub spacing: Vec2,
+    pub frame: Option<ExtFrame>,
+}
+
+impl CardGroup {
+    /// A grid with this many columns. The id salt must be unique within the [`Ui`].
+    pub fn new(id_salt: impl std::hash::Hash, columns: usize) -> Self {
+        Self {
+            id: Id::new(id_salt),
+            columns: columns.max(1),
+            card_size: Vec2::new(160.0, 100.0),
+            spacing: Vec2::splat(8.0),
+            frame: None,
+        }
+    }
+
+    /// The size of each card, including the outer margin of the frame.
+    #[inline]
+    pub fn card_size(mut self, card_size: impl Into<Vec2>) -> Self {
+        self.card_size = card_size.into();
+        self
+    }
+
+    /// The space between the cards.
+    #[inline]
+    pub fn spacing(mut self, spacing: impl Into<Vec2>) -> Self {
+        self.spacing = spacing.into();
+        self
+    }
+
+    /// Paint each card with this frame.
+    ///
+    /// Default is a frame in the faint background color of the current style.
+    #[inline]
+    pub fn frame(mut self, frame: ExtFrame) -> Self {
+        self.frame = Some(frame);
+        self
+    }
+
+    /// The grid position of card `index` moved one step by an arrow key, if there is a card there.
+    fn neighbor(&self, index: usize, count: usize, key: Key) -> Option<usize> {
+        let column = index % self.columns;
+        let neighbor = match key {
+            Key::ArrowLeft if column > 0 => index - 1,
+            Key::ArrowRight if column + 1 < self.columns => index + 1,
+            Key::ArrowUp => index.checked_sub(self.columns)?,
+            Key::ArrowDown => index + self.columns,
+            _ => return None,
+        };
+        (neighbor < count).then_some(neighbor)
+    }
+
+    /// Show `count` cards, row by row, calling `add_card` with the index of each card.
+    pub fn show(
+        self,
+        ui: &mut Ui,
+        count: usize,
+        mut add_card: impl FnMut(&mut Ui, usize),
+    ) -> CardGroupResponse {
+        let origin = ui.available_rect_before_wrap().min;
+        let step = self.card_size + self.spacing;
+        let outer_rects: Vec<Rect> = (0..count)
+            .map(|index| {
+                let cell = Vec2::new((index % self.columns) as f32, (index / self.columns) as f32);
+                Rect::from_min_size(origin + cell * step, self.card_size).round_ui()
+            })
+            .collect();
+
+        let frame = self.frame.clone().unwrap_or_else(|| {
+            let visuals = ui.visuals();
+            ExtFrame::new()
+                .fill(visuals.faint_bg_color)
+                .rounding(visuals.widgets.noninteractive.rounding)
+                .inner_margin(Margin::same(8))
+        });
+
+        let group_id = ui.id().with(self.id);
+        let card_id = |index: usize| group_id.with(index);
+        let responses: Vec<Response> = outer_rects
+            .iter()
+            .enumerate()
+            .map(|(index, &outer_rect)| {
+                ui.interact(
+                    frame.widget_rect(outer_rect),
+                    card_id(index),
+                    Sense::click(),
+                )
+            })
+            .collect();
+
+        let mut focused = responses.iter().position(Response::has_focus);
+        if let Some(index) = focused {
+            let id = card_id(index);
+            let arrows = EventFilter {
+                horizontal_arrows: true,
+                vertical_arrows: true,
+                ..Default::default()
+            };
+            let key = [
+                Key::ArrowLeft,
+                Key::ArrowRight,
+                Key::ArrowUp,
+                Key::ArrowDown,
+            ]
+            .into_iter()
+            .find(|&key| ui.input(|i| i.key_pressed(key)));
+            ui.memory_mut(|mem| {
+                mem.set_focus_lock_filter(id, arrows);
+                if let Some(neighbor) = key.and_then(|key| self.neighbor(index, count, key)) {
+                    mem.request_focus(card_id(neighbor));
+                    focused = Some(neighbor);
+                }
+            });
+        }
+        let activated = responses.iter().position(Response::clicked);
+
+        for (index, &outer_rect) in outer_rects.iter().enumerate() {
+            frame.paint_at(ui, outer_rect);
+            let widget_rect = frame.widget_rect(outer_rect);
+            if focused == Some(index) {
+                ui.painter().rect_stroke(
+                    widget_rect.expand(2.0),
+                    frame.rounding,
+                    ui.visuals().selection.stroke,
+                );
+            }
+            let content_rect = frame.content_rect(widget_rect);
+            let mut card_ui = ui.new_child(
+                UiBuilder::new()
+                    .id_salt(card_id(index))
+                    .max_rect(content_rect),
+            );
+            card_ui.set_clip_rect(content_rect.intersect(ui.clip_rect()));
+            add_card(&mut card_ui, index);
+        }
+
+        let bounds = outer_rects
+            .iter()
+            .fold(Rect::from_min_max(origin, origin), |bounds, rect| {
+                bounds.union(*rect)
+            });
+        let response = ui.allocate_rect(bounds, Sense::hover());
+
+        CardGroupResponse {
+            response,
+            cards: responses,
+            focused,
+            activated,
+        }
+    }
+}
+
+/// What happened to a [`CardGroup`].
+pub struct CardGroupResponse {
+    /// The response of the whole grid.
+    pub response: Response,
+
+    /// The response of each card.
+    pub cards: Vec<Response>,
+
+    /// The card with keyboard focus, after any arrow key navigation this frame.
+    pub focused: Option<usize>,
+
+    /// The card that was clicked, or activated with Space or Enter while focused.
+    pub activated: Option<usize>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CentralPanel, Context, Event, Modifiers, RawInput};
+
+    fn press(key: Key) -> Vec<Event> {
+        vec![Event::Key {
+            key,
+            physical_key: None,
+            pressed: true,
+            repeat: false,
+            modifiers: Modifiers::NONE,
+        }]
+    }
+
+    #[test]
+    fn arrow_keys_move_focus_across_the_grid() {
+        let ctx = Context::default();
+        let run = |events: Vec<Event>| {
+            let mut response = None;
+            let _ = ctx.run(
+                RawInput {
+                    events,
+                    ..Default::default()
+                },
+                |ctx| {
+                    CentralPanel::default().show(ctx, |ui| {
+                        let group = CardGroup::new("cards", 2).show(ui, 4, |ui, card| {
+                            ui.label(card.to_string());
+                        });
+                        response = Some((group.focused, group.activated));
+                    });
+                },
+            );
+            response.unwrap()
+        };
+
+        assert_eq!(run(vec![]), (None, None));
+        assert_eq!(run(press(Key::Tab)), (Some(0), None));
+        assert_eq!(run(vec![]), (Some(0), None));
+
+        for (key, focused) in [
+            (Key::ArrowRight, 1),
+            (Key::ArrowRight, 1), // No card to the right of the second column
+            (Key::ArrowDown, 3),
+            (Key::ArrowLeft, 2),
+            (Key::ArrowDown, 2), // No card below the last row
+            (Key::ArrowUp, 0),
+        ] {
+            assert_eq!(run(press(key)), (Some(focused), None), "{key:?}");
+            assert_eq!(run(vec![]), (Some(focused), None), "after {key:?}");
+        }
+
+        assert_eq!(run(press(Key::Enter)), (Some(0), Some(0)));
+    }
+}
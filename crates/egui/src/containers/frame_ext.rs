@@ -1,499 +1,3341 @@
-//! Frame container
+//! Extended frame container
+
+use std::sync::Arc;
 
 use crate::{
-    epaint, layers::ShapeIdx, InnerResponse, Response, Sense, Style, Ui, UiBuilder, UiKind,
-    UiStackInfo,
+    cache::{ComputerMut, FrameCache},
+    epaint, Context, Id, InnerResponse, Painter, Response, Sense, Ui, Vec2, WidgetRect, WidgetText,
+};
+use emath::GuiRounding as _;
+use epaint::{
+    Color32, Margin, Marginf, Mesh, Pos2, Rect, RectShape, Rounding, Shape, Stroke, TextureId,
 };
-use epaint::{Color32, CornerRadius, Margin, Marginf, Rect, Shadow, Shape, Stroke};
 
-/// A frame around some content, including margin, colors, etc.
-///
-/// ## Definitions
-/// The total (outer) size of a frame is
-/// `content_size + inner_margin + 2 * stroke.width + outer_margin`.
-///
-/// Everything within the stroke is filled with the fill color (if any).
-///
-/// ```text
-/// +-----------------^-------------------------------------- -+
-/// |                 | outer_margin                           |
-/// |    +------------v----^------------------------------+    |
-/// |    |                 | stroke width                 |    |
-/// |    |    +------------v---^---------------------+    |    |
-/// |    |    |                | inner_margin        |    |    |
-/// |    |    |    +-----------v----------------+    |    |    |
-/// |    |    |    |             ^              |    |    |    |
-/// |    |    |    |             |              |    |    |    |
-/// |    |    |    |<------ content_size ------>|    |    |    |
-/// |    |    |    |             |              |    |    |    |
-/// |    |    |    |             v              |    |    |    |
-/// |    |    |    +------- content_rect -------+    |    |    |
-/// |    |    |                                      |    |    |
-/// |    |    +-------------fill_rect ---------------+    |    |
-/// |    |                                                |    |
-/// |    +----------------- widget_rect ------------------+    |
-/// |                                                          |
-/// +---------------------- outer_rect ------------------------+
-/// ```
-///
-/// The four rectangles, from inside to outside, are:
-/// * `content_rect`: the rectangle that is made available to the inner [`Ui`] or widget.
-/// * `fill_rect`: the rectangle that is filled with the fill color (inside the stroke, if any).
-/// * `widget_rect`: is the interactive part of the widget (what sense clicks etc).
-/// * `outer_rect`: what is allocated in the outer [`Ui`], and is what is returned by [`Response::rect`].
-///
-/// ## Usage
-///
-/// ```
-/// # egui::__run_test_ui(|ui| {
-/// egui::Frame::none()
-///     .fill(egui::Color32::RED)
-///     .show(ui, |ui| {
-///         ui.label("Label with red background");
-///     });
-/// # });
-/// ```
-///
-/// ## Dynamic color
-/// If you want to change the color of the frame based on the response of
-/// the widget, you need to break it up into multiple steps:
-///
-/// ```
-/// # egui::__run_test_ui(|ui| {
-/// let mut frame = egui::Frame::default().inner_margin(4.0).begin(ui);
-/// {
-///     let response = frame.content_ui.label("Inside the frame");
-///     if response.hovered() {
-///         frame.frame.fill = egui::Color32::RED;
-///     }
-/// }
-/// frame.end(ui); // Will "close" the frame.
-/// # });
-/// ```
-///
-/// You can also respond to the hovering of the frame itself:
-///
-/// ```
-/// # egui::__run_test_ui(|ui| {
-/// let mut frame = egui::Frame::default().inner_margin(4.0).begin(ui);
-/// {
-///     frame.content_ui.label("Inside the frame");
-///     frame.content_ui.label("This too");
-/// }
-/// let response = frame.allocate_space(ui);
-/// if response.hovered() {
-///     frame.frame.fill = egui::Color32::RED;
-/// }
-/// frame.paint(ui);
-/// # });
-/// ```
-///
-/// Note that you cannot change the margins after calling `begin`.
-#[doc(alias = "border")]
-#[derive(Clone, Copy, Debug, Default, PartialEq)]
+/// How the border of an [`ExtFrame`] is drawn.
+#[derive(Clone, Debug, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
-#[must_use = "You should call .show()"]
-pub struct Frame {
-    // Fields are ordered inside-out.
-    // TODO(emilk): add `min_content_size: Vec2`
-    //
-    /// Margin within the painted frame.
-    ///
-    /// Known as `padding` in CSS.
-    #[doc(alias = "padding")]
-    pub inner_margin: Margin,
-
-    /// The background fill color of the frame, within the [`Self::stroke`].
-    ///
-    /// Known as `background` in CSS.
-    #[doc(alias = "background")]
-    pub fill: Color32,
+pub enum StrokeStyle {
+    /// A continuous line.
+    #[default]
+    Solid,
 
-    /// The width and color of the outline around the frame.
-    ///
-    /// The width of the stroke is part of the total margin/padding of the frame.
-    #[doc(alias = "border")]
-    pub stroke: Stroke,
+    /// A line broken up into dashes.
+    Dashed {
+        /// Length of each dash.
+        dash_length: f32,
 
-    /// The rounding of the _outer_ corner of the [`Self::stroke`]
-    /// (or, if there is no stroke, the outer corner of [`Self::fill`]).
-    ///
-    /// In other words, this is the corner radius of the _widget rect_.
-    pub corner_radius: CornerRadius,
+        /// Length of the gap between two dashes.
+        gap_length: f32,
+    },
 
-    /// Margin outside the painted frame.
+    /// A line broken up by a repeating pattern, like `stroke-dasharray` in SVG,
+    /// e.g. `vec![8.0, 3.0, 1.0, 3.0]` for dot-dash.
     ///
-    /// Similar to what is called `margin` in CSS.
-    /// However, egui does NOT do "Margin Collapse" like in CSS,
-    /// i.e. when placing two frames next to each other,
-    /// the distance between their borders is the SUM
-    /// of their other margins.
-    /// In CSS the distance would be the MAX of their outer margins.
-    /// Supporting margin collapse is difficult, and would
-    /// requires complicating the already complicated egui layout code.
-    ///
-    /// Consider using [`crate::Spacing::item_spacing`]
-    /// for adding space between widgets.
-    pub outer_margin: Margin,
+    /// The lengths alternate between dashes and gaps, starting with a dash.
+    /// An odd number of lengths is repeated to make it even, so `[4.0]` is the same as `[4.0, 4.0]`.
+    /// An empty pattern, or one without any positive length, is drawn solid.
+    DashPattern(Vec<f32>),
+}
 
-    /// Optional drop-shadow behind the frame.
-    pub shadow: Shadow,
+impl StrokeStyle {
+    /// The length after which the dashes repeat, or `None` for a solid line.
+    pub fn period(&self) -> Option<f32> {
+        let period = match self {
+            Self::Solid => return None,
+            Self::Dashed {
+                dash_length,
+                gap_length,
+            } => dash_length + gap_length,
+            Self::DashPattern(pattern) => {
+                let sum: f32 = pattern.iter().sum();
+                if pattern.len() % 2 == 1 {
+                    2.0 * sum
+                } else {
+                    sum
+                }
+            }
+        };
+        (period > 0.0).then_some(period)
+    }
 }
 
-#[test]
-fn frame_size() {
-    assert_eq!(
-        std::mem::size_of::<Frame>(), 32,
-        "Frame changed size! If it shrank - good! Update this test. If it grew - bad! Try to find a way to avoid it."
-    );
-    assert!(
-        std::mem::size_of::<Frame>() <= 64,
-        "Frame is getting way too big!"
-    );
+/// A margin of an [`ExtFrame`], either in points or relative to the width of the frame.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum MarginSpec {
+    /// The same margin whatever the size of the frame.
+    Fixed(Margin),
+
+    /// This many percent of the width of the frame (the `widget_rect`) on every side,
+    /// like percentage padding in CSS. `Percent(5.0)` is 5%.
+    Percent(f32),
 }
 
-/// ## Constructors
-impl Frame {
-    /// No colors, no margins, no border.
-    ///
-    /// This is also the default.
-    pub const NONE: Self = Self {
-        inner_margin: Margin::ZERO,
-        stroke: Stroke::NONE,
-        fill: Color32::TRANSPARENT,
-        corner_radius: CornerRadius::ZERO,
-        outer_margin: Margin::ZERO,
-        shadow: Shadow::NONE,
-    };
+impl MarginSpec {
+    pub const ZERO: Self = Self::Fixed(Margin::ZERO);
 
-    /// No colors, no margins, no border.
-    ///
-    /// Same as [`Frame::NONE`].
-    pub const fn new() -> Self {
-        Self::NONE
+    /// The margin in points, for a frame of the given width.
+    pub fn resolve(&self, frame_width: f32) -> Marginf {
+        match *self {
+            Self::Fixed(margin) => margin.into(),
+            Self::Percent(percent) => Marginf::same(percent * frame_width / 100.0),
+        }
     }
+}
 
-    #[deprecated = "Use `Frame::NONE` or `Frame::new()` instead."]
-    pub const fn none() -> Self {
-        Self::NONE
+impl Default for MarginSpec {
+    #[inline]
+    fn default() -> Self {
+        Self::ZERO
     }
+}
 
-    /// For when you want to group a few widgets together within a frame.
-    pub fn group(style: &Style) -> Self {
-        Self::new()
-            .inner_margin(6)
-            .corner_radius(style.visuals.widgets.noninteractive.corner_radius)
-            .stroke(style.visuals.widgets.noninteractive.bg_stroke)
+impl From<Margin> for MarginSpec {
+    #[inline]
+    fn from(margin: Margin) -> Self {
+        Self::Fixed(margin)
     }
+}
 
-    pub fn side_top_panel(style: &Style) -> Self {
-        Self::new()
-            .inner_margin(Margin::symmetric(8, 2))
-            .fill(style.visuals.panel_fill)
+impl From<i8> for MarginSpec {
+    #[inline]
+    fn from(margin: i8) -> Self {
+        Self::Fixed(margin.into())
     }
+}
 
-    pub fn central_panel(style: &Style) -> Self {
-        Self::new().inner_margin(8).fill(style.visuals.panel_fill)
+impl From<f32> for MarginSpec {
+    #[inline]
+    fn from(margin: f32) -> Self {
+        Self::Fixed(margin.into())
     }
+}
 
-    pub fn window(style: &Style) -> Self {
-        Self::new()
-            .inner_margin(style.spacing.window_margin)
-            .corner_radius(style.visuals.window_corner_radius)
-            .shadow(style.visuals.window_shadow)
-            .fill(style.visuals.window_fill())
-            .stroke(style.visuals.window_stroke())
-    }
+/// Where the border of an [`ExtFrame`] is drawn relative to the frame edge.
+///
+/// This is the same idea as [`epaint::StrokeKind`], but for the whole border of a frame.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum StrokeAlignment {
+    /// The border grows inwards, eating into the fill.
+    #[default]
+    Inside,
 
-    pub fn menu(style: &Style) -> Self {
-        Self::new()
-            .inner_margin(style.spacing.menu_margin)
-            .corner_radius(style.visuals.menu_corner_radius)
-            .shadow(style.visuals.popup_shadow)
-            .fill(style.visuals.window_fill())
-            .stroke(style.visuals.window_stroke())
-    }
+    /// The border is centered on the frame edge.
+    Center,
 
-    pub fn popup(style: &Style) -> Self {
-        Self::new()
-            .inner_margin(style.spacing.menu_margin)
-            .corner_radius(style.visuals.menu_corner_radius)
-            .shadow(style.visuals.popup_shadow)
-            .fill(style.visuals.window_fill())
-            .stroke(style.visuals.window_stroke())
-    }
+    /// The border grows outwards, leaving the fill untouched.
+    Outside,
+}
+
+/// The border of an [`ExtFrame`].
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct ExtStroke {
+    pub width: f32,
+    pub color: Color32,
+    pub style: StrokeStyle,
+
+    /// Whether the border grows inwards, outwards, or both from the frame edge.
+    pub stroke_alignment: StrokeAlignment,
 
-    /// A canvas to draw on.
+    /// How fast the dashes of a dashed border move along it, in points per second.
     ///
-    /// In bright mode this will be very bright,
-    /// and in dark mode this will be very dark.
-    pub fn canvas(style: &Style) -> Self {
-        Self::new()
-            .inner_margin(2)
-            .corner_radius(style.visuals.widgets.noninteractive.corner_radius)
-            .fill(style.visuals.extreme_bg_color)
-            .stroke(style.visuals.window_stroke())
-    }
+    /// Zero (the default) keeps them still. Negative values move them the other way.
+    pub dash_speed: f32,
+}
+
+impl ExtStroke {
+    /// No border.
+    pub const NONE: Self = Self {
+        width: 0.0,
+        color: Color32::TRANSPARENT,
+        style: StrokeStyle::Solid,
+        stroke_alignment: StrokeAlignment::Inside,
+        dash_speed: 0.0,
+    };
+
+    /// How much darker or lighter than the fill [`Self::auto_contrast`] makes the border,
+    /// as a difference in luma (`0..=1`).
+    pub const AUTO_CONTRAST_LUMA_DELTA: f32 = 0.3;
 
-    /// A dark canvas to draw on.
-    pub fn dark_canvas(style: &Style) -> Self {
-        Self::canvas(style).fill(Color32::from_black_alpha(250))
+    /// A solid border, drawn inside the frame edge.
+    #[inline]
+    pub fn new(width: impl Into<f32>, color: impl Into<Color32>) -> Self {
+        Self {
+            width: width.into(),
+            color: color.into(),
+            ..Self::NONE
+        }
     }
-}
 
-/// ## Builders
-impl Frame {
-    /// Margin within the painted frame.
-    ///
-    /// Known as `padding` in CSS.
-    #[doc(alias = "padding")]
     #[inline]
-    pub fn inner_margin(mut self, inner_margin: impl Into<Margin>) -> Self {
-        self.inner_margin = inner_margin.into();
+    pub fn style(mut self, style: StrokeStyle) -> Self {
+        self.style = style;
         self
     }
 
-    /// The background fill color of the frame, within the [`Self::stroke`].
-    ///
-    /// Known as `background` in CSS.
-    #[doc(alias = "background")]
     #[inline]
-    pub fn fill(mut self, fill: Color32) -> Self {
-        self.fill = fill;
+    pub fn stroke_alignment(mut self, stroke_alignment: StrokeAlignment) -> Self {
+        self.stroke_alignment = stroke_alignment;
         self
     }
 
-    /// The width and color of the outline around the frame.
+    /// Make the dashes of a dashed border move ("marching ants"),
+    /// e.g. to show that a card is selected.
     ///
-    /// The width of the stroke is part of the total margin/padding of the frame.
+    /// `dash_speed` is in points per second. The frame repaints continuously while visible.
     #[inline]
-    pub fn stroke(mut self, stroke: impl Into<Stroke>) -> Self {
-        self.stroke = stroke.into();
+    pub fn marching_ants(mut self, dash_speed: f32) -> Self {
+        self.dash_speed = dash_speed;
         self
     }
 
-    /// The rounding of the _outer_ corner of the [`Self::stroke`]
-    /// (or, if there is no stroke, the outer corner of [`Self::fill`]).
+    /// Color the border to stand out against the `fill` of the frame:
+    /// darker on a light fill, lighter on a dark one, by [`Self::AUTO_CONTRAST_LUMA_DELTA`].
     ///
-    /// In other words, this is the corner radius of the _widget rect_.
+    /// Use this for presets that should look right on both light and dark fills.
+    /// Fills with a luma of `0.5` or more count as light.
     #[inline]
-    pub fn corner_radius(mut self, corner_radius: impl Into<CornerRadius>) -> Self {
-        self.corner_radius = corner_radius.into();
+    pub fn auto_contrast(mut self, fill: Color32) -> Self {
+        self.color = fill
+            .to_opaque()
+            .nudged_for_contrast(Self::AUTO_CONTRAST_LUMA_DELTA);
         self
     }
 
-    /// The rounding of the _outer_ corner of the [`Self::stroke`]
-    /// (or, if there is no stroke, the outer corner of [`Self::fill`]).
-    ///
-    /// In other words, this is the corner radius of the _widget rect_.
+    /// True if the dashes move, see [`Self::marching_ants`].
     #[inline]
-    #[deprecated = "Renamed to `corner_radius`"]
-    pub fn rounding(self, corner_radius: impl Into<CornerRadius>) -> Self {
-        self.corner_radius(corner_radius)
+    pub fn is_animated(&self) -> bool {
+        self.style.period().is_some() && self.dash_speed != 0.0 && !self.is_empty()
     }
 
-    /// Margin outside the painted frame.
+    /// How far along the border the dash pattern has moved at the given time,
+    /// within one [`StrokeStyle::period`].
     ///
-    /// Similar to what is called `margin` in CSS.
-    /// However, egui does NOT do "Margin Collapse" like in CSS,
-    /// i.e. when placing two frames next to each other,
-    /// the distance between their borders is the SUM
-    /// of their other margins.
-    /// In CSS the distance would be the MAX of their outer margins.
-    /// Supporting margin collapse is difficult, and would
-    /// requires complicating the already complicated egui layout code.
-    ///
-    /// Consider using [`crate::Spacing::item_spacing`]
-    /// for adding space between widgets.
+    /// Always zero for a solid or still border.
+    pub fn dash_offset(&self, time: f64) -> f32 {
+        match self.style.period() {
+            Some(period) if self.dash_speed != 0.0 => {
+                (time * self.dash_speed as f64).rem_euclid(period as f64) as f32
+            }
+            _ => 0.0,
+        }
+    }
+
+    /// True if the border is invisible.
     #[inline]
-    pub fn outer_margin(mut self, outer_margin: impl Into<Margin>) -> Self {
-        self.outer_margin = outer_margin.into();
-        self
+    pub fn is_empty(&self) -> bool {
+        self.width <= 0.0 || self.color == Color32::TRANSPARENT
     }
 
-    /// Optional drop-shadow behind the frame.
+    /// How far inside the frame edge the inner side of the border lies.
     #[inline]
-    pub fn shadow(mut self, shadow: Shadow) -> Self {
-        self.shadow = shadow;
-        self
+    pub fn inset(&self) -> f32 {
+        match self.stroke_alignment {
+            StrokeAlignment::Inside => self.width,
+            StrokeAlignment::Center => 0.5 * self.width,
+            StrokeAlignment::Outside => 0.0,
+        }
     }
 
-    /// Opacity multiplier in gamma space.
-    ///
-    /// For instance, multiplying with `0.5`
-    /// will make the frame half transparent.
+    /// The plain [`Stroke`] with the same width and color.
     #[inline]
-    pub fn multiply_with_opacity(mut self, opacity: f32) -> Self {
-        self.fill = self.fill.gamma_multiply(opacity);
-        self.stroke.color = self.stroke.color.gamma_multiply(opacity);
-        self.shadow.color = self.shadow.color.gamma_multiply(opacity);
-        self
+    pub fn as_stroke(&self) -> Stroke {
+        Stroke::new(self.width, self.color)
     }
 }
 
-/// ## Inspectors
-impl Frame {
-    /// How much extra space the frame uses up compared to the content.
-    ///
-    /// [`Self::inner_margin`] + [`Self.stroke`]`.width` + [`Self::outer_margin`].
+impl From<Stroke> for ExtStroke {
+    #[inline]
+    fn from(stroke: Stroke) -> Self {
+        Self::new(stroke.width, stroke.color)
+    }
+}
+
+/// Whether an [`ExtShadow`] is cast outside or inside the frame.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum ShadowType {
+    /// A drop-shadow behind the frame.
+    #[default]
+    Outer,
+
+    /// A shadow cast onto the fill, as if the frame was sunk into the background.
+    Inner,
+}
+
+/// Which sides of an [`ExtFrame`] an outer [`ExtShadow`] is cast on.
+///
+/// Combine them with `|`, e.g. `ShadowSides::LEFT | ShadowSides::BOTTOM`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct ShadowSides {
+    pub left: bool,
+    pub right: bool,
+    pub top: bool,
+    pub bottom: bool,
+}
+
+impl ShadowSides {
+    pub const NONE: Self = Self {
+        left: false,
+        right: false,
+        top: false,
+        bottom: false,
+    };
+    pub const ALL: Self = Self {
+        left: true,
+        right: true,
+        top: true,
+        bottom: true,
+    };
+    pub const LEFT: Self = Self {
+        left: true,
+        ..Self::NONE
+    };
+    pub const RIGHT: Self = Self {
+        right: true,
+        ..Self::NONE
+    };
+    pub const TOP: Self = Self {
+        top: true,
+        ..Self::NONE
+    };
+    pub const BOTTOM: Self = Self {
+        bottom: true,
+        ..Self::NONE
+    };
+}
+
+impl Default for ShadowSides {
+    #[inline]
+    fn default() -> Self {
+        Self::ALL
+    }
+}
+
+impl std::ops::BitOr for ShadowSides {
+    type Output = Self;
+
     #[inline]
-    pub fn total_margin(&self) -> Marginf {
-        Marginf::from(self.inner_margin)
-            + Marginf::from(self.stroke.width)
-            + Marginf::from(self.outer_margin)
+    fn bitor(self, rhs: Self) -> Self {
+        Self {
+            left: self.left || rhs.left,
+            right: self.right || rhs.right,
+            top: self.top || rhs.top,
+            bottom: self.bottom || rhs.bottom,
+        }
     }
+}
+
+/// A soft shadow of an [`ExtFrame`].
+///
+/// Very similar to a box-shadow in CSS, except an [`ExtFrame`] can have any number of them.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct ExtShadow {
+    /// Move the shadow by this much.
+    pub offset: Vec2,
+
+    /// The width of the blur, i.e. the width of the fuzzy penumbra.
+    pub blur_radius: f32,
+
+    /// Expand (outer) or shrink (inner) the shadow in all directions by this much.
+    pub spread: f32,
 
-    /// Calculate the `fill_rect` from the `content_rect`.
+    /// Color of the opaque center of the shadow.
+    pub color: Color32,
+
+    pub shadow_type: ShadowType,
+
+    /// The sides the shadow is cast on. All of them by default.
+    ///
+    /// For example, [`ShadowSides::BOTTOM`] gives a "lift" shadow below the frame.
+    /// Only used by [`ShadowType::Outer`] shadows.
+    pub sides: ShadowSides,
+}
+
+impl ExtShadow {
+    /// An outer shadow cast by a light shining in the given direction, e.g. `vec2(0.0, 4.0)` from above.
     ///
-    /// This is the rectangle that is filled with the fill color (inside the stroke, if any).
-    pub fn fill_rect(&self, content_rect: Rect) -> Rect {
-        content_rect + self.inner_margin
+    /// The length of `light_direction` is how far the shadow falls from the frame, in points.
+    /// The further it falls, the blurrier and fainter it gets.
+    /// Give all frames the same direction to make them look lit by the same light.
+    pub fn from_light_direction(light_direction: Vec2) -> Self {
+        let distance = light_direction.length();
+        let alpha = 96.0 / (1.0 + distance / 16.0);
+        Self {
+            offset: light_direction,
+            blur_radius: 2.0 * distance,
+            color: Color32::from_black_alpha(alpha.round() as u8),
+            ..Default::default()
+        }
     }
 
-    /// Calculate the `widget_rect` from the `content_rect`.
+    /// The shape of the shadow for a frame covering `rect` with the given `rounding`.
     ///
-    /// This is the visible and interactive rectangle.
-    pub fn widget_rect(&self, content_rect: Rect) -> Rect {
-        content_rect + self.inner_margin + Marginf::from(self.stroke.width)
+    /// Inner shadows must be clipped to `rect` by the caller.
+    pub fn as_shape(&self, rect: Rect, rounding: Rounding) -> RectShape {
+        let Self {
+            offset,
+            blur_radius,
+            spread,
+            color,
+            shadow_type,
+            sides: _,
+        } = *self;
+
+        match shadow_type {
+            ShadowType::Outer => {
+                let rect = rect.translate(offset).expand(spread);
+                let rounding = rounding + Rounding::from(spread);
+                RectShape::filled(rect, rounding, color).with_blur_width(blur_radius)
+            }
+            ShadowType::Inner => {
+                // A thick ring around the (shrunken) hole, blurred towards the inside.
+                let hole = rect.translate(offset).shrink(spread);
+                let thickness = spread + blur_radius + offset.abs().max_elem();
+                RectShape::stroke(hole, rounding, Stroke::new(thickness, color))
+                    .with_blur_width(blur_radius)
+            }
+        }
     }
 
-    /// Calculate the `outer_rect` from the `content_rect`.
+    /// Where the shadow of a frame covering `rect` may be painted, according to [`Self::sides`].
     ///
-    /// This is what is allocated in the outer [`Ui`], and is what is returned by [`Response::rect`].
-    pub fn outer_rect(&self, content_rect: Rect) -> Rect {
-        content_rect + self.inner_margin + Marginf::from(self.stroke.width) + self.outer_margin
+    /// The shadow is cut off at the frame edges of the sides it is not cast on.
+    pub fn clip_rect(&self, rect: Rect) -> Rect {
+        let ShadowSides {
+            left,
+            right,
+            top,
+            bottom,
+        } = self.sides;
+        if self.shadow_type == ShadowType::Inner {
+            return Rect::EVERYTHING;
+        }
+        let mut clip_rect = Rect::EVERYTHING;
+        if !left {
+            clip_rect.min.x = rect.left();
+        }
+        if !right {
+            clip_rect.max.x = rect.right();
+        }
+        if !top {
+            clip_rect.min.y = rect.top();
+        }
+        if !bottom {
+            clip_rect.max.y = rect.bottom();
+        }
+        clip_rect
     }
 }
 
-// ----------------------------------------------------------------------------
+/// A texture sliced into a 3×3 grid, for drawing ornate borders of any size
+/// from a single image, see [`ExtFrame::nine_patch`].
+///
+/// The corners are drawn at their size in the image (one texel per point),
+/// the edges are stretched along the border and the center is stretched to fill the rest.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct NinePatch {
+    /// The image to slice.
+    pub texture_id: TextureId,
 
-pub struct Prepared {
-    /// The frame that was prepared.
-    ///
-    /// The margin has already been read and used,
-    /// but the rest of the fields may be modified.
-    pub frame: Frame,
+    /// Size of the image, in texels.
+    pub image_size: Vec2,
 
-    /// This is where we will insert the frame shape so it ends up behind the content.
-    where_to_put_background: ShapeIdx,
+    /// How far in from each side of the image the slices are, in texels.
+    pub slices: Marginf,
 
-    /// Add your widgets to this UI so it ends up within the frame.
-    pub content_ui: Ui,
+    /// Multiplied with the texture color.
+    pub tint: Color32,
 }
 
-impl Frame {
-    /// Begin a dynamically colored frame.
-    ///
-    /// This is a more advanced API.
-    /// Usually you want to use [`Self::show`] instead.
+impl NinePatch {
+    pub fn new(
+        texture_id: TextureId,
+        image_size: impl Into<Vec2>,
+        slices: impl Into<Marginf>,
+    ) -> Self {
+        Self {
+            texture_id,
+            image_size: image_size.into(),
+            slices: slices.into(),
+            tint: Color32::WHITE,
+        }
+    }
+
+    #[inline]
+    pub fn tint(mut self, tint: impl Into<Color32>) -> Self {
+        self.tint = tint.into();
+        self
+    }
+
+    /// The nine textured quads covering `rect`, row by row from the top left.
     ///
-    /// See docs for [`Frame`] for an example.
-    pub fn begin(self, ui: &mut Ui) -> Prepared {
-        let where_to_put_background = ui.painter().add(Shape::Noop);
-        let outer_rect_bounds = ui.available_rect_before_wrap();
+    /// If `rect` is too small for the corners, they are scaled down to fit.
+    pub fn mesh(&self, rect: Rect) -> Mesh {
+        let Marginf {
+            left,
+            right,
+            top,
+            bottom,
+        } = self.slices;
+        let fit = |a: f32, b: f32, size: f32| {
+            let scale = if a + b > size { size / (a + b) } else { 1.0 };
+            (a * scale, b * scale)
+        };
+        let (left_width, right_width) = fit(left, right, rect.width());
+        let (top_height, bottom_height) = fit(top, bottom, rect.height());
 
-        let mut max_content_rect = outer_rect_bounds - self.total_margin();
+        let xs = [
+            rect.left(),
+            rect.left() + left_width,
+            rect.right() - right_width,
+            rect.right(),
+        ];
+        let ys = [
+            rect.top(),
+            rect.top() + top_height,
+            rect.bottom() - bottom_height,
+            rect.bottom(),
+        ];
+        let us = [
+            0.0,
+            left / self.image_size.x,
+            1.0 - right / self.image_size.x,
+            1.0,
+        ];
+        let vs = [
+            0.0,
+            top / self.image_size.y,
+            1.0 - bottom / self.image_size.y,
+            1.0,
+        ];
 
-        // Make sure we don't shrink to the negative:
-        max_content_rect.max.x = max_content_rect.max.x.max(max_content_rect.min.x);
-        max_content_rect.max.y = max_content_rect.max.y.max(max_content_rect.min.y);
+        let mut mesh = Mesh::with_texture(self.texture_id);
+        for row in 0..3 {
+            for column in 0..3 {
+                let quad = Rect::from_min_max(
+                    Pos2::new(xs[column], ys[row]),
+                    Pos2::new(xs[column + 1], ys[row + 1]),
+                );
+                let uv = Rect::from_min_max(
+                    Pos2::new(us[column], vs[row]),
+                    Pos2::new(us[column + 1], vs[row + 1]),
+                );
+                if quad.is_positive() {
+                    mesh.add_rect_with_uv(quad, uv, self.tint);
+                }
+            }
+        }
+        mesh
+    }
+}
 
-        let content_ui = ui.new_child(
-            UiBuilder::new()
-                .ui_stack_info(UiStackInfo::new(UiKind::Frame).with_frame(self))
-                .max_rect(max_content_rect),
-        );
+/// A band across the top or bottom of an [`ExtFrame`], e.g. for a title or card actions,
+/// see [`ExtFrame::header`] and [`ExtFrame::footer`].
+///
+/// It spans the whole width of the `fill_rect`, and the content is placed
+/// between the header and footer, inside the [`ExtFrame::inner_margin`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct FrameBand {
+    /// The height of the band.
+    pub height: f32,
+
+    /// Painted over the [`ExtFrame::fill`].
+    pub fill: Color32,
+
+    /// A line between the band and the content.
+    pub divider: Stroke,
+}
 
-        Prepared {
-            frame: self,
-            where_to_put_background,
-            content_ui,
+impl FrameBand {
+    pub fn new(height: f32) -> Self {
+        Self {
+            height,
+            fill: Color32::TRANSPARENT,
+            divider: Stroke::NONE,
         }
     }
 
-    /// Show the given ui surrounded by this frame.
-    pub fn show<R>(self, ui: &mut Ui, add_contents: impl FnOnce(&mut Ui) -> R) -> InnerResponse<R> {
-        self.show_dyn(ui, Box::new(add_contents))
+    #[inline]
+    pub fn fill(mut self, fill: impl Into<Color32>) -> Self {
+        self.fill = fill.into();
+        self
     }
 
-    /// Show using dynamic dispatch.
-    pub fn show_dyn<'c, R>(
-        self,
-        ui: &mut Ui,
-        add_contents: Box<dyn FnOnce(&mut Ui) -> R + 'c>,
-    ) -> InnerResponse<R> {
-        let mut prepared = self.begin(ui);
-        let ret = add_contents(&mut prepared.content_ui);
-        let response = prepared.end(ui);
-        InnerResponse::new(ret, response)
+    #[inline]
+    pub fn divider(mut self, divider: impl Into<Stroke>) -> Self {
+        self.divider = divider.into();
+        self
     }
+}
+
+/// Everything that decides the tessellated mesh of an [`ExtShadow`].
+#[derive(Clone, Copy, Debug)]
+struct ShadowKey {
+    shadow: ExtShadow,
+    rect: Rect,
+    rounding: Rounding,
+    pixels_per_point: f32,
+}
 
-    /// Paint this frame as a shape.
-    pub fn paint(&self, content_rect: Rect) -> Shape {
+impl std::hash::Hash for ShadowKey {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
         let Self {
-            inner_margin: _,
-            fill,
-            stroke,
-            corner_radius,
-            outer_margin: _,
             shadow,
+            rect,
+            rounding,
+            pixels_per_point,
         } = *self;
+        let ExtShadow {
+            offset,
+            blur_radius,
+            spread,
+            color,
+            shadow_type,
+            sides: _, // Only decides the clip rect, not the mesh
+        } = shadow;
+        for value in [
+            offset.x,
+            offset.y,
+            blur_radius,
+            spread,
+            rect.min.x,
+            rect.min.y,
+            rect.max.x,
+            rect.max.y,
+            pixels_per_point,
+        ] {
+            emath::OrderedFloat(value).hash(state);
+        }
+        color.hash(state);
+        shadow_type.hash(state);
+        rounding.hash(state);
+    }
+}
+
+#[derive(Default)]
+struct ShadowTessellator;
+
+impl ComputerMut<ShadowKey, Arc<Mesh>> for ShadowTessellator {
+    fn compute(&mut self, key: ShadowKey) -> Arc<Mesh> {
+        let ShadowKey {
+            shadow,
+            rect,
+            rounding,
+            pixels_per_point,
+        } = key;
+        let mut tessellator =
+            epaint::Tessellator::new(pixels_per_point, Default::default(), [1, 1], vec![]);
+        let mut mesh = Mesh::default();
+        tessellator.tessellate_rect(&shadow.as_shape(rect, rounding), &mut mesh);
+        Arc::new(mesh)
+    }
+}
+
+/// Shadows are blurry and thus expensive to tessellate,
+/// so we keep the meshes around for as long as they are being painted.
+type ShadowCache = FrameCache<Arc<Mesh>, ShadowTessellator>;
+
+/// The tessellated shadow, reused from last frame if nothing changed.
+fn shadow_mesh(painter: &Painter, shadow: ExtShadow, rect: Rect, rounding: Rounding) -> Arc<Mesh> {
+    let key = ShadowKey {
+        shadow,
+        rect,
+        rounding,
+        pixels_per_point: painter.pixels_per_point(),
+    };
+    painter
+        .ctx()
+        .memory_mut(|mem| mem.caches.cache::<ShadowCache>().get(key))
+}
+
+/// The look of an [`ExtFrame`], without its size and position, see [`ExtFrame::style_key`].
+///
+/// Frames with equal keys paint the same in rects of the same size,
+/// so the key can be used to share cached geometry between them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct StyleKey(u64);
 
-        let widget_rect = self.widget_rect(content_rect);
+/// Hashes the fields of an [`ExtFrame`] that decide how it looks, for its [`StyleKey`].
+struct StyleHash<'a>(&'a ExtFrame);
 
-        let frame_shape = Shape::Rect(epaint::RectShape::new(
-            widget_rect,
-            corner_radius,
+impl std::hash::Hash for StyleHash<'_> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        let ExtFrame {
+            inner_margin: _, // Decides where the content goes, not how the frame looks
             fill,
             stroke,
-            epaint::StrokeKind::Inside,
-        ));
+            outline,
+            rounding,
+            outer_margin: _,
+            shadows,
+            embedded,
+            size_mode: _,
+            box_sizing: _,
+            sense: _,
+            click_through_shadow: _,
+            backdrop_blur,
+            nine_patch,
+            pixel_snap,
+            ripple,
+            header,
+            footer,
+            overflow_fade,
+            draggable: _, // Only moves the frame
+            debug_box_model,
+        } = self.0;
+        let float = |value: f32, state: &mut H| emath::OrderedFloat(value).hash(state);
 
-        if shadow == Default::default() {
-            frame_shape
-        } else {
-            let shadow = shadow.as_shape(widget_rect, corner_radius);
-            Shape::Vec(vec![Shape::from(shadow), frame_shape])
+        fill.hash(state);
+
+        let ExtStroke {
+            width,
+            color,
+            style,
+            stroke_alignment,
+            dash_speed,
+        } = stroke;
+        float(*width, state);
+        color.hash(state);
+        match style {
+            StrokeStyle::Solid => 0.hash(state),
+            StrokeStyle::Dashed {
+                dash_length,
+                gap_length,
+            } => {
+                1.hash(state);
+                float(*dash_length, state);
+                float(*gap_length, state);
+            }
+            StrokeStyle::DashPattern(pattern) => {
+                2.hash(state);
+                pattern.len().hash(state);
+                for &length in pattern {
+                    float(length, state);
+                }
+            }
+        }
+        stroke_alignment.hash(state);
+        float(*dash_speed, state);
+
+        outline
+            .map(|(stroke, offset)| (stroke, emath::OrderedFloat(offset)))
+            .hash(state);
+        rounding.hash(state);
+
+        shadows.len().hash(state);
+        for shadow in shadows {
+            let ExtShadow {
+                offset,
+                blur_radius,
+                spread,
+                color,
+                shadow_type,
+                sides,
+            } = shadow;
+            for value in [offset.x, offset.y, *blur_radius, *spread] {
+                float(value, state);
+            }
+            color.hash(state);
+            shadow_type.hash(state);
+            sides.hash(state);
+        }
+
+        embedded
+            .as_ref()
+            .map(|embedded| embedded.style_key())
+            .hash(state);
+        backdrop_blur.map(emath::OrderedFloat).hash(state);
+
+        nine_patch.is_some().hash(state);
+        if let Some(NinePatch {
+            texture_id,
+            image_size,
+            slices,
+            tint,
+        }) = nine_patch
+        {
+            texture_id.hash(state);
+            for value in [
+                image_size.x,
+                image_size.y,
+                slices.left,
+                slices.right,
+                slices.top,
+                slices.bottom,
+            ] {
+                float(value, state);
+            }
+            tint.hash(state);
+        }
+
+        pixel_snap.hash(state);
+        ripple.hash(state);
+        for band in [header, footer] {
+            band.map(
+                |FrameBand {
+                     height,
+                     fill,
+                     divider,
+                 }| { (emath::OrderedFloat(height), fill, divider) },
+            )
+            .hash(state);
         }
+        overflow_fade.hash(state);
+        debug_box_model.hash(state);
     }
 }
 
-impl Prepared {
-    fn outer_rect(&self) -> Rect {
-        let content_rect = self.content_ui.min_rect();
-        content_rect
-            + self.frame.inner_margin
-            + Marginf::from(self.frame.stroke.width)
-            + self.frame.outer_margin
-    }
+/// How the size of an [`ExtFrame`] is decided.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum FrameSize {
+    /// The frame (excluding [`ExtFrame::outer_margin`]) has exactly this size.
+    Fixed { width: f32, height: f32 },
 
-    /// Allocate the space that was used by [`Self::content_ui`].
+    /// The frame fills all the available space.
+    #[default]
+    Fill,
+
+    /// Pick a size mode depending on the available width, like CSS media queries.
     ///
-    /// This MUST be called, or the parent ui will not know how much space this widget used.
+    /// Each entry is a minimum available width and the mode to use from that width on.
+    /// The entry with the largest minimum width that fits is used,
+    /// and [`Self::Fill`] if none does.
     ///
-    /// This can be called before or after [`Self::paint`].
-    pub fn allocate_space(&self, ui: &mut Ui) -> Response {
-        ui.allocate_rect(self.outer_rect(), Sense::hover())
+    /// For example, a card that is full-width on narrow screens:
+    /// ```
+    /// # use egui::containers::frame_ext::FrameSize;
+    /// let size_mode = FrameSize::Responsive(vec![
+    ///     (0.0, FrameSize::Fill),
+    ///     (600.0, FrameSize::Fixed { width: 300.0, height: 200.0 }),
+    /// ]);
+    /// assert_eq!(size_mode.at_width(400.0), FrameSize::Fill);
+    /// ```
+    Responsive(Vec<(f32, FrameSize)>),
+}
+
+impl FrameSize {
+    /// The [`Self::Fixed`] or [`Self::Fill`] mode to use with this much available width.
+    pub fn at_width(&self, available_width: f32) -> Self {
+        match self {
+            Self::Fixed { .. } | Self::Fill => self.clone(),
+            Self::Responsive(breakpoints) => breakpoints
+                .iter()
+                .filter(|(min_width, _)| *min_width <= available_width)
+                .max_by(|(a, _), (b, _)| a.total_cmp(b))
+                .map_or(Self::Fill, |(_, size_mode)| {
+                    size_mode.at_width(available_width)
+                }),
+        }
     }
+}
+
+/// What the size of an [`FrameSize::Fixed`] [`ExtFrame`] applies to, like `box-sizing` in CSS.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum BoxSizing {
+    /// The size is that of the `widget_rect`, i.e. it includes the border and the padding.
+    ///
+    /// A thicker inside border leaves less room for the content.
+    #[default]
+    BorderBox,
 
-    /// Paint the frame.
+    /// The size is that of the `content_rect`.
     ///
-    /// This can be called before or after [`Self::allocate_space`].
-    pub fn paint(&self, ui: &Ui) {
-        let content_rect = self.content_ui.min_rect();
-        let widget_rect = self.frame.widget_rect(content_rect);
+    /// The border and the padding are added around it,
+    /// so a thicker border grows the frame instead of shrinking the content.
+    ContentBox,
+}
 
-        if ui.is_rect_visible(widget_rect) {
-            let shape = self.frame.paint(content_rect);
-            ui.painter().set(self.where_to_put_background, shape);
+/// Rows and columns painted inside an [`ExtFrame`] with [`ExtFrame::table`].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct FrameTable {
+    /// The height of each row. Rows are added until the content rect is filled.
+    pub row_height: f32,
+
+    /// Where the column separators go, relative to the left of the content rect.
+    pub column_xs: Vec<f32>,
+
+    /// The background of every other row, starting with the second.
+    pub stripe_color: Color32,
+
+    /// The lines between rows and columns.
+    pub separator: Stroke,
+}
+
+impl FrameTable {
+    pub fn new(row_height: f32, column_xs: impl Into<Vec<f32>>) -> Self {
+        Self {
+            row_height,
+            column_xs: column_xs.into(),
+            stripe_color: Color32::from_black_alpha(10),
+            separator: Stroke::new(1.0, Color32::from_black_alpha(40)),
         }
     }
 
-    /// Convenience for calling [`Self::allocate_space`] and [`Self::paint`].
-    ///
-    /// Returns the outer rect, i.e. including the outer margin.
-    pub fn end(self, ui: &mut Ui) -> Response {
-        self.paint(ui);
-        self.allocate_space(ui)
+    #[inline]
+    pub fn stripe_color(mut self, stripe_color: Color32) -> Self {
+        self.stripe_color = stripe_color;
+        self
+    }
+
+    #[inline]
+    pub fn separator(mut self, separator: impl Into<Stroke>) -> Self {
+        self.separator = separator.into();
+        self
+    }
+}
+
+/// A frame with more styling options than [`crate::Frame`]:
+/// any number of inner and outer shadows, dashed borders, border alignment,
+/// and a size that doesn't depend on the content.
+///
+/// ## Definitions
+/// ```text
+/// +---------------------- outer_rect ------------------------+
+/// |                       outer_margin                       |
+/// |    +----------------- widget_rect ------------------+    |
+/// |    |                  stroke (if Inside)            |    |
+/// |    |    +------------ fill_rect ---------------+    |    |
+/// |    |    |      inner_margin (the padding)      |    |    |
+/// |    |    |    +------- content_rect -------+    |    |    |
+/// |    |    |    |                            |    |    |    |
+/// |    |    |    +----------------------------+    |    |    |
+/// |    |    +--------------------------------------+    |    |
+/// |    +------------------------------------------------+    |
+/// +----------------------------------------------------------+
+/// ```
+///
+/// The `widget_rect` is the frame edge, and its size is decided by [`Self::size_mode`].
+/// Where the border is drawn relative to that edge depends on [`ExtStroke::stroke_alignment`],
+/// and this in turn decides the `fill_rect`.
+///
+/// The [`Self::inner_margin`] is pure padding: it is measured from the inner side of the border,
+/// never overlaps it, and doesn't change with the border width.
+/// The total distance from the frame edge to the content is [`Self::content_padding`].
+/// Whether a [`FrameSize::Fixed`] size includes the border and padding is decided by
+/// [`Self::box_sizing`], like `box-sizing` in CSS.
+/// Both margins can also be a percentage of the frame width, see [`MarginSpec`].
+///
+/// ## Usage
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// use egui::containers::frame_ext::{ExtFrame, ExtStroke, FrameSize};
+///
+/// ExtFrame::new()
+///     .fill(egui::Color32::LIGHT_GRAY)
+///     .stroke(ExtStroke::new(2.0, egui::Color32::BLACK))
+///     .size_mode(FrameSize::Fixed { width: 200.0, height: 100.0 })
+///     .end(ui);
+/// # });
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[must_use = "You should call .show() or .end()"]
+pub struct ExtFrame {
+    /// Padding between the inner side of the border and the content.
+    pub inner_margin: MarginSpec,
+
+    /// The background fill color of the frame.
+    pub fill: Color32,
+
+    /// The border of the frame.
+    pub stroke: ExtStroke,
+
+    /// A ring drawn this far outside the border, like `outline` with `outline-offset` in CSS,
+    /// e.g. for focus and selection states.
+    ///
+    /// Unlike the [`Self::stroke`], it never touches the border. It takes up space
+    /// outside the [`Self::outer_margin`], see [`Self::outside_margin`].
+    pub outline: Option<(Stroke, f32)>,
+
+    /// The rounding of the frame edge.
+    pub rounding: Rounding,
+
+    /// Margin outside the painted frame.
+    pub outer_margin: MarginSpec,
+
+    /// Shadows, painted in order.
+    pub shadows: Vec<ExtShadow>,
+
+    /// Another frame painted inside the content rect of this one.
+    pub embedded: Option<Box<ExtFrame>>,
+
+    /// How the size of the frame is decided.
+    pub size_mode: FrameSize,
+
+    /// Whether a [`FrameSize::Fixed`] size includes the border and padding.
+    pub box_sizing: BoxSizing,
+
+    /// What interactions the frame senses. [`Sense::hover`] by default.
+    #[cfg_attr(feature = "serde", serde(skip, default = "Sense::hover"))]
+    pub sense: Sense,
+
+    /// If true (the default), only the frame itself (`widget_rect`) is interactive,
+    /// and clicks on the outer margin and outer shadows go to whatever is below.
+    ///
+    /// If false, the outer margin and the outer shadows are part of the frame too.
+    pub click_through_shadow: bool,
+
+    /// Blur radius for a frosted-glass look of whatever is painted behind the frame.
+    ///
+    /// egui paints everything in a single pass and can't read back what is behind the frame,
+    /// so for now this is approximated by a translucent overlay in the panel color,
+    /// painted below [`Self::fill`] and getting more opaque with a larger radius.
+    /// Use a translucent [`Self::fill`] to let the frosted backdrop show through.
+    pub backdrop_blur: Option<f32>,
+
+    /// A sliced image drawn over the whole `widget_rect`, on top of the fill
+    /// and below the stroke, for ornate borders.
+    pub nine_patch: Option<NinePatch>,
+
+    /// Snap the painted `widget_rect` and `fill_rect` to the physical pixel grid,
+    /// so thin borders are crisp instead of blurry. Off by default.
+    ///
+    /// Use a border width of a whole number of pixels for crisp edges on both sides.
+    /// See [`Self::pixel_snapped`].
+    pub pixel_snap: bool,
+
+    /// The color of a ripple that expands from where the frame is clicked and fades out
+    /// over [`Self::RIPPLE_DURATION`], like in Material Design. Off by default.
+    ///
+    /// The ripple stays within the `fill_rect`, and makes the frame sense clicks.
+    pub ripple: Option<Color32>,
+
+    /// A band across the top of the `fill_rect`, above the content.
+    pub header: Option<FrameBand>,
+
+    /// A band across the bottom of the `fill_rect`, below the content, e.g. for card actions.
+    pub footer: Option<FrameBand>,
+
+    /// Fade the content out toward an edge of the `content_rect` where it is clipped,
+    /// to hint that there is more of it. Off by default.
+    ///
+    /// The fade goes from transparent to the [`Self::fill`] over [`Self::OVERFLOW_FADE_HEIGHT`],
+    /// see [`Self::paint_overflow_fade`].
+    pub overflow_fade: bool,
+
+    /// Let the frame be dragged around with the pointer, e.g. for dashboard cards. Off by default.
+    ///
+    /// The frame is painted and sensed where it was dragged to, but still takes up space
+    /// at its place in the layout. The offset is remembered under the id of its [`Response`],
+    /// made from this id salt, see [`Self::drag_offset`].
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub draggable: Option<Id>,
+
+    /// Outline the `outer_rect`, `widget_rect`, `fill_rect` and `content_rect`
+    /// (see [`Self::BOX_MODEL_COLORS`]) on top of the frame, like the box model view
+    /// of browser devtools. For debugging layouts only. Off by default.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub debug_box_model: bool,
+}
+
+impl Default for ExtFrame {
+    fn default() -> Self {
+        Self {
+            inner_margin: MarginSpec::ZERO,
+            fill: Color32::TRANSPARENT,
+            stroke: ExtStroke::NONE,
+            outline: None,
+            rounding: Rounding::ZERO,
+            outer_margin: MarginSpec::ZERO,
+            shadows: Vec::new(),
+            embedded: None,
+            size_mode: FrameSize::Fill,
+            box_sizing: BoxSizing::BorderBox,
+            sense: Sense::hover(),
+            click_through_shadow: true,
+            backdrop_blur: None,
+            nine_patch: None,
+            pixel_snap: false,
+            ripple: None,
+            header: None,
+            footer: None,
+            overflow_fade: false,
+            draggable: None,
+            debug_box_model: false,
+        }
+    }
+}
+
+/// ## Constructors
+impl ExtFrame {
+    /// No colors, no margins, no border, filling the available space.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Convert an [`crate::Frame`], to ease migrating to [`ExtFrame`].
+    ///
+    /// The stroke is drawn inside the frame edge like in [`crate::Frame`],
+    /// and a visible shadow becomes an outer [`ExtShadow`].
+    /// The frame fills the available space, see [`Self::size_mode`].
+    pub fn from_frame(frame: crate::Frame) -> Self {
+        let crate::Frame {
+            inner_margin,
+            fill,
+            stroke,
+            rounding,
+            outer_margin,
+            shadow,
+        } = frame;
+        let shadows = if shadow == epaint::Shadow::NONE {
+            vec![]
+        } else {
+            vec![ExtShadow {
+                offset: Vec2::new(shadow.offset[0] as f32, shadow.offset[1] as f32),
+                blur_radius: shadow.blur as f32,
+                spread: shadow.spread as f32,
+                color: shadow.color,
+                shadow_type: ShadowType::Outer,
+                sides: ShadowSides::ALL,
+            }]
+        };
+        Self {
+            inner_margin: inner_margin.into(),
+            fill,
+            stroke: stroke.into(),
+            rounding,
+            outer_margin: outer_margin.into(),
+            shadows,
+            ..Default::default()
+        }
+    }
+
+    /// Convert to the closest [`crate::Frame`].
+    ///
+    /// The stroke loses its [`StrokeStyle`] and [`StrokeAlignment`],
+    /// only the first outer shadow is kept (rounded to whole points),
+    /// and embedded frames are dropped. [`MarginSpec::Percent`] margins become zero.
+    pub fn to_frame(&self) -> crate::Frame {
+        let shadow = self
+            .shadows
+            .iter()
+            .find(|shadow| shadow.shadow_type == ShadowType::Outer)
+            .map_or(epaint::Shadow::NONE, |shadow| epaint::Shadow {
+                offset: [shadow.offset.x.round() as i8, shadow.offset.y.round() as i8],
+                blur: shadow.blur_radius.round() as u8,
+                spread: shadow.spread.round() as u8,
+                color: shadow.color,
+            });
+        crate::Frame {
+            inner_margin: fixed_margin(self.inner_margin),
+            fill: self.fill,
+            stroke: self.stroke.as_stroke(),
+            rounding: self.rounding,
+            outer_margin: fixed_margin(self.outer_margin),
+            shadow,
+        }
+    }
+}
+
+/// ## Builders
+impl ExtFrame {
+    #[inline]
+    pub fn inner_margin(mut self, inner_margin: impl Into<MarginSpec>) -> Self {
+        self.inner_margin = inner_margin.into();
+        self
+    }
+
+    #[inline]
+    pub fn fill(mut self, fill: Color32) -> Self {
+        self.fill = fill;
+        self
+    }
+
+    #[inline]
+    pub fn stroke(mut self, stroke: impl Into<ExtStroke>) -> Self {
+        self.stroke = stroke.into();
+        self
+    }
+
+    /// Draw a ring `offset` points outside the border, see [`Self::outline`].
+    #[inline]
+    pub fn outline(mut self, stroke: impl Into<Stroke>, offset: f32) -> Self {
+        self.outline = Some((stroke.into(), offset));
+        self
+    }
+
+    #[inline]
+    pub fn rounding(mut self, rounding: impl Into<Rounding>) -> Self {
+        self.rounding = rounding.into();
+        self
+    }
+
+    #[inline]
+    pub fn outer_margin(mut self, outer_margin: impl Into<MarginSpec>) -> Self {
+        self.outer_margin = outer_margin.into();
+        self
+    }
+
+    /// Add another shadow, painted after the existing ones.
+    #[inline]
+    pub fn shadow(mut self, shadow: ExtShadow) -> Self {
+        self.shadows.push(shadow);
+        self
+    }
+
+    /// Add an outer shadow cast by a light shining in the given direction,
+    /// see [`ExtShadow::from_light_direction`].
+    #[inline]
+    pub fn with_light_direction(self, light_direction: Vec2) -> Self {
+        self.shadow(ExtShadow::from_light_direction(light_direction))
+    }
+
+    #[inline]
+    pub fn embedded(mut self, embedded: Self) -> Self {
+        self.embedded = Some(Box::new(embedded));
+        self
+    }
+
+    #[inline]
+    pub fn size_mode(mut self, size_mode: FrameSize) -> Self {
+        self.size_mode = size_mode;
+        self
+    }
+
+    #[inline]
+    pub fn box_sizing(mut self, box_sizing: BoxSizing) -> Self {
+        self.box_sizing = box_sizing;
+        self
+    }
+
+    /// Make the frame interactive.
+    #[inline]
+    pub fn sense(mut self, sense: Sense) -> Self {
+        self.sense = sense;
+        self
+    }
+
+    /// See [`Self::click_through_shadow`].
+    #[inline]
+    pub fn click_through_shadow(mut self, click_through_shadow: bool) -> Self {
+        self.click_through_shadow = click_through_shadow;
+        self
+    }
+
+    /// See [`Self::backdrop_blur`].
+    #[inline]
+    pub fn backdrop_blur(mut self, blur_radius: f32) -> Self {
+        self.backdrop_blur = Some(blur_radius);
+        self
+    }
+
+    /// See [`Self::nine_patch`].
+    #[inline]
+    pub fn nine_patch(mut self, nine_patch: NinePatch) -> Self {
+        self.nine_patch = Some(nine_patch);
+        self
+    }
+
+    /// See [`Self::pixel_snap`].
+    #[inline]
+    pub fn pixel_snap(mut self, pixel_snap: bool) -> Self {
+        self.pixel_snap = pixel_snap;
+        self
+    }
+
+    /// See [`Self::ripple`].
+    #[inline]
+    pub fn ripple(mut self, color: impl Into<Color32>) -> Self {
+        self.ripple = Some(color.into());
+        self
+    }
+
+    /// See [`Self::header`].
+    #[inline]
+    pub fn header(mut self, header: FrameBand) -> Self {
+        self.header = Some(header);
+        self
+    }
+
+    /// See [`Self::footer`].
+    #[inline]
+    pub fn footer(mut self, footer: FrameBand) -> Self {
+        self.footer = Some(footer);
+        self
+    }
+
+    /// See [`Self::overflow_fade`].
+    #[inline]
+    pub fn overflow_fade(mut self, overflow_fade: bool) -> Self {
+        self.overflow_fade = overflow_fade;
+        self
+    }
+
+    /// Make the frame draggable, see [`Self::draggable`].
+    ///
+    /// The `id_salt` must be unique within the parent [`Ui`].
+    #[inline]
+    pub fn draggable(mut self, id_salt: impl std::hash::Hash) -> Self {
+        self.draggable = Some(Id::new(id_salt));
+        self
+    }
+
+    /// Outline the rects of the box model, see [`Self::debug_box_model`].
+    #[inline]
+    pub fn debug_box_model(mut self, debug_box_model: bool) -> Self {
+        self.debug_box_model = debug_box_model;
+        self
+    }
+}
+
+/// ## Inspectors
+impl ExtFrame {
+    /// The outline colors of [`Self::debug_box_model`]: for the outer margin (`outer_rect`),
+    /// the border (`widget_rect`), the inner margin (`fill_rect`) and the `content_rect`.
+    pub const BOX_MODEL_COLORS: [Color32; 4] = [
+        Color32::from_rgb(250, 160, 80),
+        Color32::from_rgb(250, 220, 100),
+        Color32::from_rgb(130, 200, 120),
+        Color32::from_rgb(100, 160, 220),
+    ];
+
+    /// A key that is equal for frames that look the same, whatever their size and margins.
+    ///
+    /// Use it to cache geometry per style, e.g. in a [`crate::cache::FrameCache`].
+    pub fn style_key(&self) -> StyleKey {
+        StyleKey(epaint::util::hash(StyleHash(self)))
+    }
+
+    /// How many seconds a [`Self::ripple`] takes to expand and fade out.
+    pub const RIPPLE_DURATION: f32 = 0.45;
+
+    /// How far into the `content_rect` the [`Self::overflow_fade`] reaches, in points.
+    pub const OVERFLOW_FADE_HEIGHT: f32 = 16.0;
+
+    /// How far the [`Self::draggable`] frame with the given id (the id of its [`Response`])
+    /// was dragged from its place in the layout.
+    pub fn drag_offset(ctx: &Context, id: Id) -> Vec2 {
+        ctx.data_mut(|d| d.get_persisted(id.with("drag_offset")))
+            .unwrap_or_default()
+    }
+
+    /// Move the [`Self::draggable`] frame with the given id back to its place in the layout.
+    pub fn reset_drag_offset(ctx: &Context, id: Id) {
+        ctx.data_mut(|d| d.remove::<Vec2>(id.with("drag_offset")));
+    }
+
+    /// The size of the `widget_rect` given the available space for the `outer_rect`.
+    pub fn widget_size(&self, available_size: Vec2) -> Vec2 {
+        match self.size_mode.at_width(available_size.x) {
+            FrameSize::Fixed { width, height } => match self.box_sizing {
+                BoxSizing::BorderBox => Vec2::new(width, height),
+                BoxSizing::ContentBox => {
+                    let width = solve_width(width, |w| self.content_padding(w).sum().x);
+                    Vec2::new(width, height + self.content_padding(width).sum().y)
+                }
+            },
+            FrameSize::Fill | FrameSize::Responsive(_) => {
+                let width = self.widget_width(available_size.x);
+                let height = available_size.y - self.outside_margin(width).sum().y;
+                Vec2::new(width, height).max(Vec2::ZERO)
+            }
+        }
+    }
+
+    /// The width of the `widget_rect` in an `outer_rect` of the given width.
+    fn widget_width(&self, outer_width: f32) -> f32 {
+        // outer_width = width + outside margins, and the margins may depend on the width:
+        let width = outer_width - self.outside_margin(0.0).sum().x;
+        let per_point = self.outside_margin(1.0).sum().x - self.outside_margin(0.0).sum().x;
+        width / (1.0 + per_point)
+    }
+
+    /// The space around a `widget_rect` of the given width: the [`Self::outer_margin`],
+    /// plus room for the [`Self::outline`] and the part of the border it surrounds.
+    pub fn outside_margin(&self, widget_width: f32) -> Marginf {
+        let outline = self.outline.map_or(0.0, |(stroke, offset)| {
+            self.stroke.width - self.stroke.inset() + offset + stroke.width
+        });
+        self.outer_margin.resolve(widget_width) + outline
+    }
+
+    /// Calculate the `outer_rect` from the `widget_rect`.
+    pub fn outer_rect(&self, widget_rect: Rect) -> Rect {
+        widget_rect + self.outside_margin(widget_rect.width())
+    }
+
+    /// Calculate the `widget_rect` from the `outer_rect`.
+    pub fn widget_rect(&self, outer_rect: Rect) -> Rect {
+        outer_rect - self.outside_margin(self.widget_width(outer_rect.width()))
+    }
+
+    /// The inner edge of the [`Self::outline`] ring, if any:
+    /// the outside of the border moved outwards by the outline offset.
+    pub fn outline_rect(&self, widget_rect: Rect) -> Option<Rect> {
+        let (_, offset) = self.outline?;
+        let border_outside = widget_rect.expand(self.stroke.width - self.stroke.inset());
+        Some(border_outside.expand(offset))
+    }
+
+    /// Calculate the `fill_rect` from the `widget_rect`.
+    ///
+    /// This is the area within the border, and depends on [`ExtStroke::stroke_alignment`].
+    pub fn fill_rect(&self, widget_rect: Rect) -> Rect {
+        widget_rect.shrink(self.stroke.inset())
+    }
+
+    /// Calculate the `content_rect` from the `widget_rect`.
+    pub fn content_rect(&self, widget_rect: Rect) -> Rect {
+        widget_rect - self.content_padding(widget_rect.width())
+    }
+
+    /// The distance from the frame edge to the content of a frame of the given width:
+    /// the border inset (see [`ExtStroke::inset`]) plus the [`Self::inner_margin`],
+    /// and the heights of the [`Self::header`] and [`Self::footer`].
+    pub fn content_padding(&self, widget_width: f32) -> Marginf {
+        let mut padding = self.inner_margin.resolve(widget_width) + self.stroke.inset();
+        padding.top += self.header.map_or(0.0, |header| header.height);
+        padding.bottom += self.footer.map_or(0.0, |footer| footer.height);
+        padding
+    }
+
+    /// The area of the [`Self::header`] at the top of the `fill_rect`, if any.
+    pub fn header_rect(&self, widget_rect: Rect) -> Option<Rect> {
+        let fill_rect = self.fill_rect(widget_rect);
+        let header = self.header?;
+        Some(
+            fill_rect
+                .split_top_bottom_at_y(fill_rect.top() + header.height)
+                .0,
+        )
+    }
+
+    /// The area of the [`Self::footer`] at the bottom of the `fill_rect`, if any.
+    pub fn footer_rect(&self, widget_rect: Rect) -> Option<Rect> {
+        let fill_rect = self.fill_rect(widget_rect);
+        let footer = self.footer?;
+        Some(
+            fill_rect
+                .split_top_bottom_at_y(fill_rect.bottom() - footer.height)
+                .1,
+        )
+    }
+
+    /// The `widget_rect` as painted: with [`Self::pixel_snap`], its edges are moved to the
+    /// nearest physical pixel edges for the given `pixels_per_point`.
+    pub fn pixel_snapped(&self, widget_rect: Rect, pixels_per_point: f32) -> Rect {
+        if self.pixel_snap {
+            widget_rect.round_to_pixels(pixels_per_point)
+        } else {
+            widget_rect
+        }
+    }
+
+    /// The rounding of the `fill_rect`, i.e. [`Self::rounding`] minus the border inset.
+    pub fn fill_rounding(&self) -> Rounding {
+        let inset = self.stroke.inset().round() as u8;
+        let r = self.rounding;
+        Rounding {
+            nw: r.nw.saturating_sub(inset),
+            ne: r.ne.saturating_sub(inset),
+            sw: r.sw.saturating_sub(inset),
+            se: r.se.saturating_sub(inset),
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+impl ExtFrame {
+    /// Allocate space for the frame according to [`Self::size_mode`], and paint it.
+    ///
+    /// Returns the response for the `outer_rect`.
+    pub fn end(self, ui: &mut Ui) -> Response {
+        let outer_rect = self.next_outer_rect(ui);
+        let response = self.allocate(ui, outer_rect);
+        if let Some(color) = self.ripple {
+            self.ripple_ui(ui, &response, color);
+        }
+        response
+    }
+
+    /// Like [`Self::end`], but with content added by `add_contents` in the `content_rect`,
+    /// like [`crate::Frame::show`].
+    ///
+    /// The size of the frame still comes from [`Self::size_mode`], not from the content.
+    /// Content that doesn't fit is clipped, see [`Self::overflow_fade`].
+    pub fn show<R>(self, ui: &mut Ui, add_contents: impl FnOnce(&mut Ui) -> R) -> InnerResponse<R> {
+        let outer_rect = self.next_outer_rect(ui);
+        // Sense before adding the content, so the content gets the interactions on top of it:
+        let response = self.allocate(ui, outer_rect);
+        let outer_rect = response.rect;
+
+        let content_rect = self.content_rect(self.widget_rect(outer_rect));
+        let mut content_ui = ui.new_child(
+            crate::UiBuilder::new()
+                .id_salt(response.id.with("content"))
+                .max_rect(content_rect),
+        );
+        content_ui.set_clip_rect(content_rect.intersect(ui.clip_rect()));
+        let inner = add_contents(&mut content_ui);
+        self.paint_overflow_fade(ui, outer_rect, content_ui.min_rect());
+
+        if let Some(color) = self.ripple {
+            self.ripple_ui(ui, &response, color);
+        }
+        InnerResponse::new(inner, response)
+    }
+
+    /// Where [`Self::end`] would put the frame in `ui` right now.
+    fn next_outer_rect(&self, ui: &Ui) -> Rect {
+        let available = ui.available_rect_before_wrap();
+        Rect::from_min_size(available.min, self.measure(ui)).round_ui()
+    }
+
+    /// Advance the cursor past `outer_rect`, sense the frame and paint it.
+    ///
+    /// The rect of the response is where the frame was painted,
+    /// which is moved from `outer_rect` if the frame is [`Self::draggable`].
+    fn allocate(&self, ui: &mut Ui, outer_rect: Rect) -> Response {
+        let auto_id = ui.advance_cursor_after_rect(outer_rect);
+        let mut sense = self.sense;
+        if self.ripple.is_some() {
+            sense |= Sense::click();
+        }
+        let (id, outer_rect) = match self.draggable {
+            Some(id_salt) => {
+                sense |= Sense::drag();
+                let id = ui.make_persistent_id(id_salt);
+                (id, outer_rect.translate(Self::drag_offset(ui.ctx(), id)))
+            }
+            None => (auto_id, outer_rect),
+        };
+
+        let hit_rect = self.hit_rect(outer_rect, self.widget_rect(outer_rect));
+        let mut response = ui.ctx().create_widget(
+            WidgetRect {
+                id,
+                layer_id: ui.layer_id(),
+                rect: outer_rect,
+                interact_rect: ui.clip_rect().intersect(hit_rect),
+                sense,
+                enabled: ui.is_enabled(),
+            },
+            true,
+        );
+
+        let delta = response.drag_delta();
+        if self.draggable.is_some() && delta != Vec2::ZERO {
+            ui.data_mut(|d| {
+                *d.get_persisted_mut_or_default::<Vec2>(id.with("drag_offset")) += delta;
+            });
+            // Follow the pointer right away, instead of a frame later:
+            response.rect = response.rect.translate(delta);
+            response.interact_rect = response.interact_rect.translate(delta);
+        }
+
+        self.paint_at(ui, response.rect);
+        response
+    }
+
+    /// Start a [`Self::ripple`] where the frame was clicked, and paint the current one.
+    fn ripple_ui(&self, ui: &Ui, response: &Response, color: Color32) {
+        let key = response.id.with("ripple");
+        let time = ui.input(|i| i.time);
+        if let Some(center) = response
+            .interact_pointer_pos()
+            .filter(|_| response.clicked())
+        {
+            ui.data_mut(|data| {
+                data.insert_temp(
+                    key,
+                    Ripple {
+                        center,
+                        start_time: time,
+                    },
+                );
+            });
+        }
+        let Some(ripple) = ui.data(|data| data.get_temp::<Ripple>(key)) else {
+            return;
+        };
+        let progress = (time - ripple.start_time) as f32 / Self::RIPPLE_DURATION;
+        if progress >= 1.0 {
+            ui.data_mut(|data| data.remove::<Ripple>(key));
+            return;
+        }
+        ui.ctx().request_repaint();
+
+        let widget_rect =
+            self.pixel_snapped(self.widget_rect(response.rect), ui.pixels_per_point());
+        let fill_rect = self.fill_rect(widget_rect);
+        let max_radius = [
+            fill_rect.left_top(),
+            fill_rect.right_top(),
+            fill_rect.left_bottom(),
+            fill_rect.right_bottom(),
+        ]
+        .map(|corner| corner.distance(ripple.center))
+        .into_iter()
+        .fold(0.0, f32::max);
+        let radius = max_radius * emath::easing::cubic_out(progress);
+
+        let circle: Vec<Pos2> = (0..RIPPLE_SEGMENTS)
+            .map(|i| {
+                let angle = std::f32::consts::TAU * i as f32 / RIPPLE_SEGMENTS as f32;
+                ripple.center + radius * Vec2::angled(angle)
+            })
+            .collect();
+        let mut frame_path = vec![];
+        epaint::tessellator::path::rounded_rectangle(
+            &mut frame_path,
+            fill_rect,
+            self.fill_rounding(),
+        );
+        ui.painter().add(Shape::convex_polygon(
+            clip_convex_polygon(&circle, &frame_path),
+            color.gamma_multiply(1.0 - progress),
+            Stroke::NONE,
+        ));
+    }
+
+    /// The size of the `outer_rect` that [`Self::end`] would allocate in `ui` right now,
+    /// without allocating or painting anything, e.g. to plan a custom layout.
+    ///
+    /// This includes the [`Self::outer_margin`] and [`Self::outline`].
+    /// Outer shadows don't take up space, see [`Self::shadow_bounds`] for where they paint.
+    pub fn measure(&self, ui: &Ui) -> Vec2 {
+        let widget_size = self.widget_size(ui.available_rect_before_wrap().size());
+        widget_size + self.outside_margin(widget_size.x).sum()
+    }
+
+    /// Paint the frame with the given `outer_rect` (including [`Self::outer_margin`]).
+    ///
+    /// This only paints: it doesn't allocate any space nor sense any interaction,
+    /// so it can be called any number of times, e.g. in a rect you already allocated.
+    ///
+    /// Everything of the frame, outer shadows included, is painted in one go,
+    /// so a frame painted later covers the shadows of earlier overlapping frames.
+    pub fn paint_at(&self, ui: &Ui, outer_rect: Rect) {
+        let widget_rect = self.widget_rect(outer_rect);
+        if ui.is_rect_visible(widget_rect) {
+            self.paint_into(ui.painter(), widget_rect);
+        }
+    }
+
+    /// Show the frame with a clickable header and a body that can be collapsed.
+    ///
+    /// The frame is as tall as its content, so its [`Self::size_mode`] is ignored.
+    /// A chevron in the header turns from pointing right (closed) to pointing down (open),
+    /// in sync with the animated height of the body. The frame starts out open.
+    pub fn show_collapsible<R>(
+        self,
+        ui: &mut Ui,
+        id_salt: impl std::hash::Hash,
+        header: impl Into<WidgetText>,
+        add_body: impl FnOnce(&mut Ui) -> R,
+    ) -> CollapsibleFrameResponse<R> {
+        let id = ui.make_persistent_id(id_salt);
+        let body_height_id = id.with("body_height");
+        let open = ui.data_mut(|d| *d.get_persisted_mut_or(id, true));
+        let openness = ui.ctx().animate_bool_responsive(id, open);
+        let full_body_height = ui
+            .data(|d| d.get_temp::<f32>(body_height_id))
+            .unwrap_or(0.0);
+
+        let available = ui.available_rect_before_wrap();
+        let widget_width = self.widget_width(available.width()).max(0.0);
+        let padding = self.content_padding(widget_width).sum();
+        let outside = self.outside_margin(widget_width).sum();
+        let content_width = (widget_width - padding.x).max(0.0);
+        let spacing = ui.spacing().item_spacing;
+        let icon_width = ui.spacing().icon_width;
+        let header_galley = header.into().into_galley(
+            ui,
+            Some(crate::TextWrapMode::Truncate),
+            content_width - icon_width - spacing.x,
+            crate::TextStyle::Button,
+        );
+        let header_height = ui.spacing().interact_size.y.max(header_galley.size().y);
+        let content_height = header_height + openness * (spacing.y + full_body_height);
+
+        let outer_size = Vec2::new(content_width, content_height) + padding + outside;
+        let outer_rect = Rect::from_min_size(available.min, outer_size).round_ui();
+        self.paint_at(ui, outer_rect);
+        let content_rect = self.content_rect(self.widget_rect(outer_rect));
+
+        let header_rect = Rect::from_min_size(
+            content_rect.min,
+            Vec2::new(content_rect.width(), header_height),
+        );
+        let header_response = ui.interact(header_rect, id, Sense::click());
+        if header_response.clicked() {
+            ui.data_mut(|d| d.insert_persisted(id, !open));
+        }
+        if ui.is_rect_visible(header_rect) {
+            let visuals = ui.style().interact(&header_response);
+            let icon_rect = Rect::from_center_size(
+                header_rect.left_center() + Vec2::new(0.5 * icon_width, 0.0),
+                Vec2::splat(icon_width),
+            );
+            ui.painter().add(Shape::line(
+                chevron_points(icon_rect, openness),
+                visuals.fg_stroke,
+            ));
+            let text_pos = Pos2::new(
+                icon_rect.right() + spacing.x,
+                header_rect.center().y - 0.5 * header_galley.size().y,
+            );
+            ui.painter()
+                .galley(text_pos, header_galley, visuals.text_color());
+        }
+
+        let mut body_returned = None;
+        if openness > 0.0 {
+            let body_rect = Rect::from_min_max(
+                Pos2::new(content_rect.left(), header_rect.bottom() + spacing.y),
+                Pos2::new(content_rect.right(), f32::INFINITY),
+            );
+            let mut body_ui = ui.new_child(
+                crate::UiBuilder::new()
+                    .id_salt(id.with("body"))
+                    .max_rect(body_rect),
+            );
+            // Only the part that fits in the animated height is visible:
+            body_ui.set_clip_rect(content_rect.intersect(ui.clip_rect()));
+            body_returned = Some(add_body(&mut body_ui));
+            self.paint_overflow_fade(ui, outer_rect, body_ui.min_rect());
+
+            let body_height = body_ui.min_rect().height();
+            if body_height != full_body_height {
+                ui.data_mut(|d| d.insert_temp(body_height_id, body_height));
+                ui.ctx().request_repaint();
+            }
+        }
+
+        ui.advance_cursor_after_rect(outer_rect);
+
+        CollapsibleFrameResponse {
+            header_response,
+            body_returned,
+            openness,
+        }
+    }
+
+    /// Paint alternating row backgrounds and row/column separators
+    /// in the content rect of the frame with the given `outer_rect`, for tabular content.
+    ///
+    /// Like [`Self::paint_at`], this only paints. Call it after painting the frame itself.
+    pub fn table(&self, ui: &Ui, outer_rect: Rect, table: &FrameTable) {
+        let content_rect = self.content_rect(self.widget_rect(outer_rect));
+        if !ui.is_rect_visible(content_rect) || table.row_height <= 0.0 {
+            return;
+        }
+        let painter = ui
+            .painter()
+            .with_clip_rect(content_rect.intersect(ui.clip_rect()));
+
+        let rows = (content_rect.height() / table.row_height).ceil() as usize;
+        let row_top = |row: usize| content_rect.top() + row as f32 * table.row_height;
+
+        for row in (1..rows).step_by(2) {
+            let stripe = Rect::from_x_y_ranges(
+                content_rect.x_range(),
+                row_top(row)..=row_top(row + 1).min(content_rect.bottom()),
+            );
+            painter.rect_filled(stripe, 0.0, table.stripe_color);
+        }
+        for row in 1..rows {
+            painter.hline(content_rect.x_range(), row_top(row), table.separator);
+        }
+        for &x in &table.column_xs {
+            painter.vline(
+                content_rect.left() + x,
+                content_rect.y_range(),
+                table.separator,
+            );
+        }
+    }
+
+    /// With [`Self::overflow_fade`], fade out the top and/or bottom edge of the content rect
+    /// of the frame with the given `outer_rect`, where `content_bounds` is clipped by it.
+    ///
+    /// Like [`Self::paint_at`], this only paints. Call it after adding the content,
+    /// with the bounds of all of it (e.g. [`Ui::min_rect`]), so the fade is painted on top.
+    /// Nothing is painted if the content fits, or the frame has no [`Self::fill`] to fade to.
+    pub fn paint_overflow_fade(&self, ui: &Ui, outer_rect: Rect, content_bounds: Rect) {
+        if !self.overflow_fade || self.fill == Color32::TRANSPARENT {
+            return;
+        }
+        let widget_rect = self.widget_rect(outer_rect);
+        let fill_rect = self.fill_rect(widget_rect);
+        let content_rect = self.content_rect(widget_rect);
+        if !ui.is_rect_visible(content_rect) {
+            return;
+        }
+
+        const TOP_TO_BOTTOM: f32 = std::f32::consts::TAU / 4.0;
+        let height = Self::OVERFLOW_FADE_HEIGHT.min(0.5 * content_rect.height());
+        let mut mesh = Mesh::default();
+        if content_bounds.top() < content_rect.top() {
+            let top = content_rect.top();
+            mesh.append(Mesh::rounded_gradient(
+                Rect::from_x_y_ranges(fill_rect.x_range(), top..=top + height),
+                Rounding::ZERO,
+                self.fill,
+                Color32::TRANSPARENT,
+                TOP_TO_BOTTOM,
+            ));
+        }
+        if content_bounds.bottom() > content_rect.bottom() {
+            let bottom = content_rect.bottom();
+            mesh.append(Mesh::rounded_gradient(
+                Rect::from_x_y_ranges(fill_rect.x_range(), bottom - height..=bottom),
+                Rounding::ZERO,
+                Color32::TRANSPARENT,
+                self.fill,
+                TOP_TO_BOTTOM,
+            ));
+        }
+        if !mesh.is_empty() {
+            ui.painter()
+                .with_clip_rect(fill_rect.intersect(ui.clip_rect()))
+                .add(Shape::mesh(mesh));
+        }
+    }
+
+    /// The part of the frame that senses interaction, see [`Self::click_through_shadow`].
+    fn hit_rect(&self, outer_rect: Rect, widget_rect: Rect) -> Rect {
+        if self.click_through_shadow {
+            return widget_rect;
+        }
+        outer_rect.union(self.shadow_bounds(outer_rect))
+    }
+
+    /// Everything the outer shadows of a frame in `outer_rect` paint,
+    /// including their offset, blur and spread, e.g. to reserve space for them.
+    ///
+    /// Inner shadows stay within the frame and are not included.
+    /// [`Rect::NOTHING`] if there are no outer shadows.
+    pub fn shadow_bounds(&self, outer_rect: Rect) -> Rect {
+        let widget_rect = self.widget_rect(outer_rect);
+        self.shadows
+            .iter()
+            .filter(|shadow| shadow.shadow_type == ShadowType::Outer)
+            .fold(Rect::NOTHING, |bounds, shadow| {
+                bounds.union(
+                    shadow
+                        .as_shape(widget_rect, self.rounding)
+                        .visual_bounding_rect()
+                        .intersect(shadow.clip_rect(widget_rect)),
+                )
+            })
+    }
+
+    fn paint_into(&self, painter: &Painter, widget_rect: Rect) {
+        let widget_rect = self.pixel_snapped(widget_rect, painter.pixels_per_point());
+        let fill_rect = self.pixel_snapped(self.fill_rect(widget_rect), painter.pixels_per_point());
+        let fill_rounding = self.fill_rounding();
+
+        for shadow in &self.shadows {
+            if shadow.shadow_type == ShadowType::Outer {
+                let clip_rect = shadow.clip_rect(widget_rect).intersect(painter.clip_rect());
+                painter.with_clip_rect(clip_rect).add(shadow_mesh(
+                    painter,
+                    *shadow,
+                    widget_rect,
+                    self.rounding,
+                ));
+            }
+        }
+
+        if let Some(blur_radius) = self.backdrop_blur {
+            painter.add(RectShape::filled(
+                fill_rect,
+                fill_rounding,
+                frosted_backdrop_color(painter, blur_radius),
+            ));
+        }
+
+        painter.add(RectShape::filled(fill_rect, fill_rounding, self.fill));
+
+        let inner_painter = painter.with_clip_rect(fill_rect.intersect(painter.clip_rect()));
+        if let (Some(header), Some(header_rect)) = (self.header, self.header_rect(widget_rect)) {
+            let rounding = Rounding {
+                sw: 0,
+                se: 0,
+                ..fill_rounding
+            };
+            inner_painter.add(RectShape::filled(header_rect, rounding, header.fill));
+            inner_painter.hline(header_rect.x_range(), header_rect.bottom(), header.divider);
+        }
+        if let (Some(footer), Some(footer_rect)) = (self.footer, self.footer_rect(widget_rect)) {
+            let rounding = Rounding {
+                nw: 0,
+                ne: 0,
+                ..fill_rounding
+            };
+            inner_painter.add(RectShape::filled(footer_rect, rounding, footer.fill));
+            inner_painter.hline(footer_rect.x_range(), footer_rect.top(), footer.divider);
+        }
+        for shadow in &self.shadows {
+            if shadow.shadow_type == ShadowType::Inner {
+                inner_painter.add(shadow_mesh(painter, *shadow, fill_rect, fill_rounding));
+            }
+        }
+
+        if let Some(nine_patch) = &self.nine_patch {
+            painter
+                .with_clip_rect(widget_rect.intersect(painter.clip_rect()))
+                .add(Shape::mesh(nine_patch.mesh(widget_rect)));
+        }
+
+        if !self.stroke.is_empty() {
+            let dash_offset = if self.stroke.is_animated() {
+                painter.ctx().request_repaint();
+                self.stroke.dash_offset(painter.ctx().input(|i| i.time))
+            } else {
+                0.0
+            };
+            painter.add(self.stroke_shape(fill_rect, fill_rounding, dash_offset));
+        }
+
+        if let (Some((stroke, _)), Some(outline_rect)) =
+            (self.outline, self.outline_rect(widget_rect))
+        {
+            // Centered on the middle of the ring, so its inner edge is at the `outline_rect`:
+            let half_width = 0.5 * stroke.width;
+            let grow = (outline_rect.width() - widget_rect.width()) / 2.0 + half_width;
+            painter.add(RectShape::stroke(
+                outline_rect.expand(half_width),
+                self.rounding + Rounding::from(grow),
+                stroke,
+            ));
+        }
+
+        if let Some(embedded) = &self.embedded {
+            let content_rect = self.content_rect(widget_rect);
+            let embedded_size = embedded.widget_size(content_rect.size());
+            let inner_outer = Rect::from_min_size(
+                content_rect.min,
+                embedded_size + embedded.outside_margin(embedded_size.x).sum(),
+            );
+            embedded.paint_into(painter, embedded.widget_rect(inner_outer));
+        }
+
+        if self.debug_box_model {
+            let rects = [
+                self.outer_rect(widget_rect),
+                widget_rect,
+                fill_rect,
+                self.content_rect(widget_rect),
+            ];
+            for (rect, color) in rects.into_iter().zip(Self::BOX_MODEL_COLORS) {
+                painter.rect_stroke(rect, 0.0, Stroke::new(1.0, color));
+            }
+        }
+    }
+
+    /// The border, hugging the outside of the `fill_rect`.
+    ///
+    /// Dashes are shifted `dash_offset` along the border, see [`ExtStroke::dash_offset`].
+    fn stroke_shape(&self, fill_rect: Rect, fill_rounding: Rounding, dash_offset: f32) -> Shape {
+        let stroke = self.stroke.as_stroke();
+        match &self.stroke.style {
+            StrokeStyle::Solid => RectShape::stroke(fill_rect, fill_rounding, stroke).into(),
+            &StrokeStyle::Dashed {
+                dash_length,
+                gap_length,
+            } => {
+                let path = border_center_line(fill_rect, fill_rounding, stroke.width);
+                let mut shapes = Shape::dashed_line_with_offset(
+                    &path,
+                    stroke,
+                    &[dash_length],
+                    &[gap_length],
+                    dash_offset,
+                );
+                // The end of the dash that was shifted past the start of the path:
+                let tail = dash_offset - gap_length;
+                if tail > 0.0 {
+                    shapes.extend(Shape::dashed_line_with_offset(
+                        &path,
+                        stroke,
+                        &[tail],
+                        &[f32::INFINITY],
+                        0.0,
+                    ));
+                }
+                Shape::Vec(shapes)
+            }
+            StrokeStyle::DashPattern(pattern) => {
+                let path = border_center_line(fill_rect, fill_rounding, stroke.width);
+                Shape::Vec(
+                    dash_pattern_segments(&path, pattern, dash_offset)
+                        .into_iter()
+                        .map(|dash| Shape::line(dash, stroke))
+                        .collect(),
+                )
+            }
+        }
+    }
+}
+
+/// The closed loop along the center of a border of the given width around `fill_rect`,
+/// which dashed borders are dashed along.
+fn border_center_line(fill_rect: Rect, fill_rounding: Rounding, width: f32) -> Vec<Pos2> {
+    let half_width = 0.5 * width;
+    let mut path: Vec<Pos2> = vec![];
+    epaint::tessellator::path::rounded_rectangle(
+        &mut path,
+        fill_rect.expand(half_width),
+        fill_rounding + Rounding::from(half_width),
+    );
+    if let Some(first) = path.first().copied() {
+        path.push(first); // close the loop
+    }
+    path
+}
+
+/// The dashes of a [`StrokeStyle::DashPattern`] along the polyline `path`,
+/// starting `offset` into the pattern. Dashes follow the corners of the path.
+fn dash_pattern_segments(path: &[Pos2], pattern: &[f32], offset: f32) -> Vec<Vec<Pos2>> {
+    let pattern = if pattern.len() % 2 == 1 {
+        pattern.repeat(2)
+    } else {
+        pattern.to_vec()
+    };
+    let Some(period) = StrokeStyle::DashPattern(pattern.clone()).period() else {
+        return vec![path.to_vec()];
+    };
+    if path.len() < 2 || pattern.iter().any(|length| *length < 0.0) {
+        return vec![path.to_vec()];
+    }
+    let is_dash = |index: usize| index % 2 == 0;
+
+    // Where in the pattern the path starts:
+    let mut index = 0;
+    let mut remaining = pattern[0];
+    let mut skip = offset.rem_euclid(period);
+    while skip >= remaining {
+        skip -= remaining;
+        index = (index + 1) % pattern.len();
+        remaining = pattern[index];
+    }
+    remaining -= skip;
+
+    let mut dashes = vec![];
+    let mut dash = if is_dash(index) {
+        vec![path[0]]
+    } else {
+        vec![]
+    };
+    for edge in path.windows(2) {
+        let (mut from, to) = (edge[0], edge[1]);
+        let mut edge_left = from.distance(to);
+        while edge_left > remaining {
+            // The current dash or gap ends on this edge:
+            from += remaining * (to - from).normalized();
+            edge_left -= remaining;
+            if is_dash(index) {
+                dash.push(from);
+                dashes.push(std::mem::take(&mut dash));
+            } else {
+                dash = vec![from];
+            }
+            index = (index + 1) % pattern.len();
+            remaining = pattern[index];
+        }
+        remaining -= edge_left;
+        if is_dash(index) {
+            dash.push(to);
+        }
+    }
+    if dash.len() >= 2 {
+        dashes.push(dash);
+    }
+    dashes
+}
+
+/// The width `w` for which `w = content_width + padding(w)`, for a `padding` linear in `w`.
+fn solve_width(content_width: f32, padding: impl Fn(f32) -> f32) -> f32 {
+    let per_point = padding(1.0) - padding(0.0);
+    (content_width + padding(0.0)) / (1.0 - per_point).max(f32::EPSILON)
+}
+
+/// The margin of a [`MarginSpec::Fixed`], or zero for a [`MarginSpec::Percent`] of an unknown size.
+fn fixed_margin(margin: MarginSpec) -> Margin {
+    match margin {
+        MarginSpec::Fixed(margin) => margin,
+        MarginSpec::Percent(_) => Margin::ZERO,
+    }
+}
+
+/// What happened to a frame shown with [`ExtFrame::show_collapsible`].
+pub struct CollapsibleFrameResponse<R> {
+    /// The response of the header. Clicking it opens or closes the frame.
+    pub header_response: Response,
+
+    /// What the body returned, if any of it was shown.
+    pub body_returned: Option<R>,
+
+    /// How far open the frame is, in `0..=1`, animated.
+    ///
+    /// The chevron in the header is turned by `openness * 90°`.
+    pub openness: f32,
+}
+
+/// The three points of a chevron in `rect`, pointing right when closed and down when open.
+fn chevron_points(rect: Rect, openness: f32) -> Vec<Pos2> {
+    let rotation = emath::Rot2::from_angle(openness * std::f32::consts::FRAC_PI_2);
+    let half = 0.25 * rect.width();
+    [
+        Vec2::new(-0.5 * half, -half),
+        Vec2::new(0.5 * half, 0.0),
+        Vec2::new(-0.5 * half, half),
+    ]
+    .into_iter()
+    .map(|v| rect.center() + rotation * v)
+    .collect()
+}
+
+/// A running [`ExtFrame::ripple`], stored by the id of the frame.
+#[derive(Clone, Copy, Debug)]
+struct Ripple {
+    center: Pos2,
+    start_time: f64,
+}
+
+/// The number of points on the circle of an [`ExtFrame::ripple`].
+const RIPPLE_SEGMENTS: usize = 64;
+
+/// The part of the convex polygon `subject` that is inside the convex polygon `clip`,
+/// with the Sutherland–Hodgman algorithm.
+fn clip_convex_polygon(subject: &[Pos2], clip: &[Pos2]) -> Vec<Pos2> {
+    let edges = |points: &[Pos2]| -> Vec<(Pos2, Pos2)> {
+        points
+            .iter()
+            .zip(points.iter().cycle().skip(1))
+            .map(|(&a, &b)| (a, b))
+            .collect()
+    };
+    // Which side of an edge is inside depends on the winding of `clip`:
+    let winding = edges(clip)
+        .iter()
+        .map(|(a, b)| a.x * b.y - b.x * a.y)
+        .sum::<f32>()
+        .signum();
+
+    let mut points = subject.to_vec();
+    for (a, b) in edges(clip) {
+        let inside = |p: Pos2| {
+            let (edge, to_p) = (b - a, p - a);
+            winding * (edge.x * to_p.y - edge.y * to_p.x)
+        };
+        let input = std::mem::take(&mut points);
+        for (p, q) in edges(&input) {
+            let (p_inside, q_inside) = (inside(p), inside(q));
+            if p_inside >= 0.0 {
+                points.push(p);
+            }
+            if (p_inside >= 0.0) != (q_inside >= 0.0) {
+                points.push(p + (q - p) * (p_inside / (p_inside - q_inside)));
+            }
+        }
+    }
+    points
+}
+
+/// The overlay used instead of actually blurring the backdrop, see [`ExtFrame::backdrop_blur`].
+fn frosted_backdrop_color(painter: &Painter, blur_radius: f32) -> Color32 {
+    // Fully blurred content is just its average color, which we guess is the panel color.
+    let opacity = 1.0 - (-blur_radius.max(0.0) / 8.0).exp();
+    let alpha = (opacity * 0.85 * 255.0).round() as u8;
+    painter.ctx().style().visuals.panel_fill.with_alpha(alpha)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vec2;
+
+    #[test]
+    fn stroke_alignment_affects_fill_rect() {
+        let widget_rect = Rect::from_min_size(Pos2::ZERO, vec2(100.0, 50.0));
+        let frame = |stroke_alignment| {
+            ExtFrame::new()
+                .stroke(ExtStroke::new(4.0, Color32::BLACK).stroke_alignment(stroke_alignment))
+        };
+
+        let inside = frame(StrokeAlignment::Inside).fill_rect(widget_rect);
+        let center = frame(StrokeAlignment::Center).fill_rect(widget_rect);
+        let outside = frame(StrokeAlignment::Outside).fill_rect(widget_rect);
+
+        assert_eq!(outside, widget_rect);
+        assert_eq!(inside, outside.shrink(4.0));
+        assert_eq!(center, outside.shrink(2.0));
+    }
+
+    #[test]
+    fn shadow_mesh_is_reused_across_frames() {
+        let ctx = crate::Context::default();
+        let frame = ExtFrame::new()
+            .fill(Color32::WHITE)
+            .size_mode(FrameSize::Fixed {
+                width: 100.0,
+                height: 50.0,
+            })
+            .shadow(ExtShadow {
+                offset: vec2(2.0, 2.0),
+                blur_radius: 8.0,
+                spread: 1.0,
+                color: Color32::BLACK,
+                shadow_type: ShadowType::Outer,
+                ..Default::default()
+            });
+
+        let paint = || {
+            let output = ctx.run(Default::default(), |ctx| {
+                crate::CentralPanel::default().show(ctx, |ui| {
+                    frame.clone().end(ui);
+                });
+            });
+            output
+                .shapes
+                .into_iter()
+                .find_map(|clipped| match clipped.shape {
+                    Shape::Mesh(mesh) => Some(mesh),
+                    _ => None,
+                })
+                .unwrap()
+        };
+
+        let first = paint();
+        let second = paint();
+        assert!(
+            Arc::ptr_eq(&first, &second),
+            "The shadow should not be tessellated again"
+        );
+    }
+
+    #[test]
+    fn clicks_on_the_shadow_pass_through() {
+        let frame = ExtFrame::new()
+            .fill(Color32::WHITE)
+            .outer_margin(Margin::same(4))
+            .size_mode(FrameSize::Fixed {
+                width: 100.0,
+                height: 50.0,
+            })
+            .shadow(ExtShadow {
+                offset: vec2(8.0, 8.0),
+                blur_radius: 8.0,
+                spread: 4.0,
+                color: Color32::BLACK,
+                shadow_type: ShadowType::Outer,
+                ..Default::default()
+            })
+            .sense(Sense::click());
+
+        let clicked_at = |frame: &ExtFrame, offset: crate::Vec2| {
+            let ctx = crate::Context::default();
+            let run = |events: Vec<crate::Event>| {
+                let input = crate::RawInput {
+                    events,
+                    ..Default::default()
+                };
+                let mut response = None;
+                let _ = ctx.run(input, |ctx| {
+                    crate::CentralPanel::default().show(ctx, |ui| {
+                        response = Some(frame.clone().end(ui));
+                    });
+                });
+                response.unwrap()
+            };
+
+            let widget_rect = frame.widget_rect(run(vec![]).rect);
+            let pos = widget_rect.right_bottom() + offset;
+            let button = |pressed| crate::Event::PointerButton {
+                pos,
+                button: crate::PointerButton::Primary,
+                pressed,
+                modifiers: Default::default(),
+            };
+            run(vec![crate::Event::PointerMoved(pos), button(true)]);
+            run(vec![button(false)]).clicked()
+        };
+
+        let in_frame = vec2(-4.0, -4.0);
+        let in_shadow = vec2(6.0, 6.0);
+
+        assert!(clicked_at(&frame, in_frame));
+        assert!(!clicked_at(&frame, in_shadow));
+
+        let frame = frame.click_through_shadow(false);
+        assert!(clicked_at(&frame, in_frame));
+        assert!(clicked_at(&frame, in_shadow));
+    }
+
+    #[test]
+    fn backdrop_blur_falls_back_to_an_overlay() {
+        let frame = ExtFrame::new()
+            .fill(Color32::from_white_alpha(40))
+            .size_mode(FrameSize::Fixed {
+                width: 100.0,
+                height: 50.0,
+            });
+
+        let fills = |frame: &ExtFrame| {
+            let ctx = crate::Context::default();
+            let output = ctx.run(Default::default(), |ctx| {
+                crate::CentralPanel::default().show(ctx, |ui| {
+                    frame.clone().end(ui);
+                });
+            });
+            output
+                .shapes
+                .into_iter()
+                .filter_map(|clipped| match clipped.shape {
+                    Shape::Rect(rect) if rect.rect.size() == vec2(100.0, 50.0) => Some(rect.fill),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(fills(&frame), [Color32::from_white_alpha(40)]);
+
+        let blurred = fills(&frame.clone().backdrop_blur(12.0));
+        assert_eq!(blurred.len(), 2, "An overlay below the fill");
+        assert!(0 < blurred[0].a() && blurred[0].a() < 255);
+        assert_eq!(blurred[1], Color32::from_white_alpha(40));
+
+        let more_blurred = fills(&frame.backdrop_blur(24.0));
+        assert!(more_blurred[0].a() > blurred[0].a());
+    }
+
+    #[test]
+    fn paint_at_allocates_nothing() {
+        let frame = ExtFrame::new()
+            .fill(Color32::WHITE)
+            .stroke(ExtStroke::new(1.0, Color32::BLACK))
+            .sense(Sense::click());
+
+        let ctx = crate::Context::default();
+        let output = ctx.run(Default::default(), |ctx| {
+            crate::CentralPanel::default().show(ctx, |ui| {
+                let before = (ui.cursor(), ui.min_rect(), ui.next_auto_id());
+                let rect = Rect::from_min_size(ui.cursor().min, vec2(80.0, 40.0));
+                frame.paint_at(ui, rect);
+                frame.paint_at(ui, rect);
+                assert_eq!((ui.cursor(), ui.min_rect(), ui.next_auto_id()), before);
+            });
+        });
+        assert!(!output.shapes.is_empty());
+    }
+
+    #[test]
+    fn frame_round_trip() {
+        let frame = crate::Frame {
+            inner_margin: Margin::symmetric(8, 4),
+            fill: Color32::LIGHT_GRAY,
+            stroke: Stroke::new(2.0, Color32::BLACK),
+            rounding: Rounding::same(6),
+            outer_margin: Margin::same(3),
+            shadow: epaint::Shadow {
+                offset: [2, 4],
+                blur: 8,
+                spread: 1,
+                color: Color32::from_black_alpha(96),
+            },
+        };
+        let ext_frame = ExtFrame::from_frame(frame);
+        assert_eq!(ext_frame.stroke.stroke_alignment, StrokeAlignment::Inside);
+        assert_eq!(ext_frame.shadows.len(), 1);
+        assert_eq!(ext_frame.to_frame(), frame);
+
+        let plain = crate::Frame::default();
+        assert!(ExtFrame::from_frame(plain).shadows.is_empty());
+        assert_eq!(ExtFrame::from_frame(plain).to_frame(), plain);
+
+        // Only the common fields survive the other way around:
+        let ext_frame = ExtFrame::new()
+            .fill(Color32::WHITE)
+            .stroke(
+                ExtStroke::new(1.0, Color32::RED).style(StrokeStyle::Dashed {
+                    dash_length: 4.0,
+                    gap_length: 2.0,
+                }),
+            )
+            .rounding(4)
+            .inner_margin(Margin::same(6));
+        let round_tripped = ExtFrame::from_frame(ext_frame.to_frame());
+        assert_eq!(round_tripped.fill, ext_frame.fill);
+        assert_eq!(round_tripped.rounding, ext_frame.rounding);
+        assert_eq!(round_tripped.inner_margin, ext_frame.inner_margin);
+        assert_eq!(round_tripped.stroke, ExtStroke::new(1.0, Color32::RED));
+    }
+
+    #[test]
+    fn content_rect_of_padding_and_border() {
+        let widget_rect = Rect::from_min_size(Pos2::ZERO, vec2(100.0, 50.0));
+        for (padding, width, alignment, expected_padding) in [
+            (4, 0.0, StrokeAlignment::Inside, 4.0),
+            (4, 2.0, StrokeAlignment::Inside, 6.0),
+            (4, 2.0, StrokeAlignment::Center, 5.0),
+            (4, 2.0, StrokeAlignment::Outside, 4.0),
+            (0, 3.0, StrokeAlignment::Inside, 3.0),
+        ] {
+            let frame = ExtFrame::new()
+                .inner_margin(Margin::same(padding))
+                .stroke(ExtStroke::new(width, Color32::BLACK).stroke_alignment(alignment));
+            assert_eq!(
+                frame.content_padding(100.0),
+                Marginf::same(expected_padding)
+            );
+            assert_eq!(
+                frame.content_rect(widget_rect),
+                widget_rect.shrink(expected_padding),
+                "padding {padding}, {width} {alignment:?}"
+            );
+        }
+
+        // With a content box size, a thicker border grows the frame instead of the content:
+        for width in [0.0, 1.0, 5.0] {
+            let frame = ExtFrame::new()
+                .inner_margin(Margin::same(4))
+                .stroke(ExtStroke::new(width, Color32::BLACK))
+                .size_mode(FrameSize::Fixed {
+                    width: 100.0,
+                    height: 50.0,
+                })
+                .box_sizing(BoxSizing::ContentBox);
+            let widget_size = frame.widget_size(Vec2::INFINITY);
+            assert_eq!(widget_size, vec2(108.0, 58.0) + Vec2::splat(2.0 * width));
+            let widget_rect = Rect::from_min_size(Pos2::ZERO, widget_size);
+            assert_eq!(frame.content_rect(widget_rect).size(), vec2(100.0, 50.0));
+        }
+    }
+
+    #[test]
+    fn bottom_only_shadow_is_not_painted_above_the_frame() {
+        let shadow = ExtShadow {
+            offset: vec2(0.0, 4.0),
+            blur_radius: 8.0,
+            spread: 2.0,
+            color: Color32::BLACK,
+            ..Default::default()
+        };
+        let shadow_area = |sides: ShadowSides| {
+            let frame = ExtFrame::new()
+                .fill(Color32::WHITE)
+                .size_mode(FrameSize::Fixed {
+                    width: 100.0,
+                    height: 50.0,
+                })
+                .shadow(ExtShadow { sides, ..shadow });
+            let ctx = crate::Context::default();
+            let mut frame_rect = Rect::NOTHING;
+            let output = ctx.run(Default::default(), |ctx| {
+                crate::CentralPanel::default().show(ctx, |ui| {
+                    ui.add_space(20.0);
+                    frame_rect = frame.clone().end(ui).rect;
+                });
+            });
+            let painted = output
+                .shapes
+                .iter()
+                .find_map(|clipped| match &clipped.shape {
+                    Shape::Mesh(mesh) => Some(mesh.calc_bounds().intersect(clipped.clip_rect)),
+                    _ => None,
+                })
+                .unwrap();
+            (frame_rect, painted)
+        };
+
+        let (frame_rect, painted) = shadow_area(ShadowSides::ALL);
+        assert!(painted.top() < frame_rect.top());
+
+        let (frame_rect, painted) = shadow_area(ShadowSides::BOTTOM);
+        assert!(
+            painted.top() >= frame_rect.top(),
+            "{painted:?} {frame_rect:?}"
+        );
+        assert!(painted.left() >= frame_rect.left());
+        assert!(painted.right() <= frame_rect.right());
+        assert!(painted.bottom() > frame_rect.bottom());
+    }
+
+    #[test]
+    fn debug_box_model_outlines_four_rects() {
+        let frame = ExtFrame::new()
+            .fill(Color32::WHITE)
+            .outer_margin(Margin::same(4))
+            .stroke(ExtStroke::new(2.0, Color32::BLACK))
+            .inner_margin(Margin::same(6));
+        let overlay_rects = |frame: &ExtFrame| {
+            let ctx = crate::Context::default();
+            let output = ctx.run(Default::default(), |ctx| {
+                crate::CentralPanel::default().show(ctx, |ui| {
+                    let outer_rect = Rect::from_min_size(ui.cursor().min, vec2(100.0, 60.0));
+                    frame.paint_at(ui, outer_rect);
+                });
+            });
+            output
+                .shapes
+                .into_iter()
+                .filter_map(|clipped| match clipped.shape {
+                    Shape::Rect(rect)
+                        if ExtFrame::BOX_MODEL_COLORS.contains(&rect.stroke.color) =>
+                    {
+                        Some(rect.rect)
+                    }
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+        };
+
+        assert!(overlay_rects(&frame).is_empty());
+
+        let rects = overlay_rects(&frame.debug_box_model(true));
+        assert_eq!(rects.len(), 4);
+        let sizes: Vec<Vec2> = rects.iter().map(|rect| rect.size()).collect();
+        assert_eq!(
+            sizes,
+            [
+                vec2(100.0, 60.0),
+                vec2(92.0, 52.0),
+                vec2(88.0, 48.0),
+                vec2(76.0, 36.0)
+            ]
+        );
+    }
+
+    #[test]
+    fn responsive_size_switches_at_the_breakpoint() {
+        let card = FrameSize::Fixed {
+            width: 300.0,
+            height: 100.0,
+        };
+        let frame = ExtFrame::new().size_mode(FrameSize::Responsive(vec![
+            (600.0, card.clone()),
+            (0.0, FrameSize::Fill),
+        ]));
+        assert_eq!(frame.size_mode.at_width(599.0), FrameSize::Fill);
+        assert_eq!(frame.size_mode.at_width(600.0), card);
+        assert_eq!(
+            frame.widget_size(vec2(599.0, 400.0)),
+            vec2(599.0, 400.0),
+            "full width on narrow screens"
+        );
+        assert_eq!(frame.widget_size(vec2(600.0, 400.0)), vec2(300.0, 100.0));
+
+        // Evaluated against the available width of the Ui:
+        let ctx = crate::Context::default();
+        let width_in_panel = |panel_width: f32| {
+            let mut width = 0.0;
+            let _ = ctx.run(Default::default(), |ctx| {
+                crate::CentralPanel::default().show(ctx, |ui| {
+                    ui.allocate_ui(vec2(panel_width, 400.0), |ui| {
+                        width = frame.clone().end(ui).rect.width();
+                    });
+                });
+            });
+            width
+        };
+        assert_eq!(width_in_panel(500.0), 500.0);
+        assert_eq!(width_in_panel(700.0), 300.0);
+    }
+
+    #[test]
+    fn table_separators() {
+        let frame = ExtFrame::new()
+            .fill(Color32::WHITE)
+            .inner_margin(Margin::same(4))
+            .size_mode(FrameSize::Fixed {
+                width: 208.0,
+                height: 108.0,
+            });
+        // 5 rows and 3 columns:
+        let table = FrameTable::new(20.0, [50.0, 120.0]);
+
+        let ctx = crate::Context::default();
+        let output = ctx.run(Default::default(), |ctx| {
+            crate::CentralPanel::default().show(ctx, |ui| {
+                let rect = Rect::from_min_size(ui.cursor().min, vec2(208.0, 108.0));
+                frame.table(ui, rect, &table);
+            });
+        });
+
+        let separators = output
+            .shapes
+            .iter()
+            .filter(|clipped| matches!(clipped.shape, Shape::LineSegment { .. }))
+            .count();
+        let stripes = output
+            .shapes
+            .iter()
+            .filter(|clipped| matches!(&clipped.shape, Shape::Rect(rect) if rect.fill == table.stripe_color))
+            .count();
+        assert_eq!(separators, (5 - 1) + (3 - 1));
+        assert_eq!(stripes, 2);
+    }
+
+    #[test]
+    fn marching_ants_advance_with_time() {
+        let dashed = ExtStroke::new(2.0, Color32::RED).style(StrokeStyle::Dashed {
+            dash_length: 4.0,
+            gap_length: 2.0,
+        });
+        assert!(!dashed.is_animated());
+        assert_eq!(dashed.dash_offset(1.0), 0.0);
+
+        let marching = dashed.marching_ants(10.0);
+        assert!(marching.is_animated());
+        assert_eq!(marching.dash_offset(0.0), 0.0);
+        assert!(marching.dash_offset(0.2) > marching.dash_offset(0.1));
+        assert!(
+            (marching.dash_offset(0.7) - 1.0).abs() < 1e-4,
+            "wraps after one dash and gap"
+        );
+
+        let frame = ExtFrame::new().stroke(marching);
+        let ctx = crate::Context::default();
+        let paint = |time: f64| {
+            let output = ctx.run(
+                crate::RawInput {
+                    time: Some(time),
+                    ..Default::default()
+                },
+                |ctx| {
+                    crate::CentralPanel::default().show(ctx, |ui| {
+                        frame.paint_at(ui, Rect::from_min_size(Pos2::ZERO, vec2(60.0, 30.0)));
+                    });
+                },
+            );
+            let repaint_delay = output.viewport_output[&crate::ViewportId::ROOT].repaint_delay;
+            assert_eq!(repaint_delay, std::time::Duration::ZERO);
+            output
+                .shapes
+                .into_iter()
+                .find_map(|clipped| match clipped.shape {
+                    Shape::Vec(dashes) => dashes.into_iter().find_map(|dash| match dash {
+                        Shape::LineSegment { points, .. } => Some(points),
+                        _ => None,
+                    }),
+                    _ => None,
+                })
+                .unwrap()
+        };
+        assert_ne!(paint(0.0), paint(0.1), "the first dash moved");
+    }
+
+    #[test]
+    fn light_direction_drives_the_shadow() {
+        let light = vec2(3.0, 4.0);
+        let frame = ExtFrame::new().with_light_direction(light);
+        let [shadow] = frame.shadows.as_slice() else {
+            panic!("expected one shadow, got {:?}", frame.shadows);
+        };
+        assert_eq!(shadow.offset, light);
+        assert_eq!(shadow.shadow_type, ShadowType::Outer);
+        assert_eq!(shadow.blur_radius, 10.0);
+
+        // Frames lit from further away get a longer, fainter shadow in the same direction:
+        let far = ExtShadow::from_light_direction(2.0 * light);
+        assert_eq!(far.offset.normalized(), shadow.offset.normalized());
+        assert!(far.color.a() < shadow.color.a());
+    }
+
+    #[test]
+    fn chevron_rotation_tracks_the_openness() {
+        let ctx = crate::Context::default();
+        let mut time = 0.0;
+        let mut run = |events: Vec<crate::Event>| {
+            time += 1.0 / 60.0;
+            let mut header_rect = Rect::NOTHING;
+            let mut openness = 0.0;
+            let output = ctx.run(
+                crate::RawInput {
+                    time: Some(time),
+                    events,
+                    ..Default::default()
+                },
+                |ctx| {
+                    crate::CentralPanel::default().show(ctx, |ui| {
+                        let response = ExtFrame::new()
+                            .inner_margin(Margin::same(4))
+                            .show_collapsible(ui, "details", "Details", |ui| {
+                                ui.label("body");
+                            });
+                        header_rect = response.header_response.rect;
+                        openness = response.openness;
+                    });
+                },
+            );
+            let chevron = output
+                .shapes
+                .into_iter()
+                .find_map(|clipped| match clipped.shape {
+                    Shape::Path(path) if path.points.len() == 3 => Some(path.points),
+                    _ => None,
+                })
+                .unwrap();
+            // The angle of the tip, measured from pointing right:
+            let tip = chevron[1] - chevron[0].lerp(chevron[2], 0.5);
+            (header_rect, openness, tip.angle())
+        };
+
+        let (header_rect, openness, angle) = run(vec![]);
+        assert_eq!(openness, 1.0, "open by default");
+        assert!((angle - std::f32::consts::FRAC_PI_2).abs() < 1e-4);
+
+        let click = |pressed| crate::Event::PointerButton {
+            pos: header_rect.center(),
+            button: crate::PointerButton::Primary,
+            pressed,
+            modifiers: Default::default(),
+        };
+        run(vec![
+            crate::Event::PointerMoved(header_rect.center()),
+            click(true),
+        ]);
+        run(vec![click(false)]);
+
+        let mut saw_halfway = false;
+        for _ in 0..30 {
+            let (_, openness, angle) = run(vec![]);
+            saw_halfway |= 0.0 < openness && openness < 1.0;
+            let expected = openness * std::f32::consts::FRAC_PI_2;
+            assert!(
+                (angle - expected).abs() < 1e-4,
+                "{angle} at openness {openness}"
+            );
+        }
+        assert!(saw_halfway, "the chevron animates");
+        assert_eq!(run(vec![]).1, 0.0);
+    }
+
+    #[test]
+    fn outline_is_offset_outside_the_border() {
+        let widget_rect = Rect::from_min_size(Pos2::ZERO, vec2(100.0, 50.0));
+        for (alignment, border_outside) in [
+            (StrokeAlignment::Inside, 0.0),
+            (StrokeAlignment::Center, 1.0),
+            (StrokeAlignment::Outside, 2.0),
+        ] {
+            let frame = ExtFrame::new()
+                .stroke(ExtStroke::new(2.0, Color32::BLACK).stroke_alignment(alignment))
+                .outline(Stroke::new(1.0, Color32::BLUE), 3.0)
+                .outer_margin(Margin::same(4));
+            assert_eq!(
+                frame.outline_rect(widget_rect),
+                Some(widget_rect.expand(border_outside + 3.0)),
+                "{alignment:?}"
+            );
+            // The outline takes up space, on top of the outer margin:
+            assert_eq!(
+                frame.outside_margin(100.0),
+                Marginf::same(4.0 + border_outside + 3.0 + 1.0)
+            );
+            assert_eq!(
+                frame.widget_rect(frame.outer_rect(widget_rect)),
+                widget_rect
+            );
+        }
+        assert_eq!(ExtFrame::new().outline_rect(widget_rect), None);
+    }
+
+    #[test]
+    fn percent_margins_scale_with_the_frame_width() {
+        let frame = ExtFrame::new()
+            .inner_margin(MarginSpec::Percent(5.0))
+            .outer_margin(MarginSpec::Percent(25.0));
+        for width in [100.0, 200.0, 400.0] {
+            let widget_rect = Rect::from_min_size(Pos2::ZERO, vec2(width, 50.0));
+            assert_eq!(
+                frame.content_rect(widget_rect),
+                widget_rect.shrink(width / 20.0),
+                "width {width}"
+            );
+            let outer_rect = frame.outer_rect(widget_rect);
+            assert_eq!(outer_rect, widget_rect.expand(width / 4.0), "width {width}");
+            assert_eq!(frame.widget_rect(outer_rect), widget_rect, "width {width}");
+        }
+
+        // Filling the available space leaves room for the margins of the resulting frame:
+        let frame = frame.size_mode(FrameSize::Fill);
+        let widget_size = frame.widget_size(vec2(240.0, 100.0));
+        assert_eq!(widget_size, vec2(160.0, 20.0));
+
+        // Fixed margins don't depend on the width:
+        let fixed = MarginSpec::from(Margin::same(4));
+        assert_eq!(fixed.resolve(100.0), fixed.resolve(400.0));
+    }
+
+    #[test]
+    fn shadow_bounds_cover_all_outer_shadows() {
+        let frame = ExtFrame::new()
+            .outer_margin(Margin::same(2))
+            .shadow(ExtShadow {
+                offset: vec2(4.0, 8.0),
+                blur_radius: 6.0,
+                spread: 1.0,
+                color: Color32::BLACK,
+                ..Default::default()
+            })
+            .shadow(ExtShadow {
+                offset: vec2(-10.0, 0.0),
+                blur_radius: 2.0,
+                color: Color32::BLACK,
+                ..Default::default()
+            })
+            .shadow(ExtShadow {
+                offset: vec2(0.0, -50.0),
+                color: Color32::BLACK,
+                shadow_type: ShadowType::Inner,
+                ..Default::default()
+            });
+        let outer_rect = Rect::from_min_size(Pos2::ZERO, vec2(104.0, 54.0));
+        let widget_rect = frame.widget_rect(outer_rect);
+
+        // Half the blur width is painted outside the shadow rect:
+        let first = widget_rect.translate(vec2(4.0, 8.0)).expand(1.0 + 3.0);
+        let second = widget_rect.translate(vec2(-10.0, 0.0)).expand(1.0);
+        assert_eq!(frame.shadow_bounds(outer_rect), first.union(second));
+
+        assert_eq!(ExtFrame::new().shadow_bounds(outer_rect), Rect::NOTHING);
+    }
+
+    #[test]
+    fn dash_pattern_segments_follow_the_pattern() {
+        // Dot-dash: dashes at 0..10, 15..17, 22..32, 37..39, 44..54 and 59..60.
+        let line = [Pos2::ZERO, Pos2::new(60.0, 0.0)];
+        let dashes = dash_pattern_segments(&line, &[10.0, 5.0, 2.0, 5.0], 0.0);
+        assert_eq!(dashes.len(), 6);
+        assert_eq!(dashes[1], [Pos2::new(15.0, 0.0), Pos2::new(17.0, 0.0)]);
+
+        // An odd pattern is repeated, so `[6.0]` dashes 6 and skips 6:
+        let dashes = dash_pattern_segments(&line, &[6.0], 3.0);
+        assert_eq!(dashes.len(), 6, "{dashes:?}");
+        assert_eq!(dashes[0], [Pos2::ZERO, Pos2::new(3.0, 0.0)]);
+
+        // Around a 40x20 border, a 10 point period fits 12 times, dashes bending around corners:
+        let frame = ExtFrame::new().stroke(
+            ExtStroke::new(2.0, Color32::BLACK)
+                .style(StrokeStyle::DashPattern(vec![6.0, 4.0]))
+                .stroke_alignment(StrokeAlignment::Center),
+        );
+        let widget_rect = Rect::from_min_size(Pos2::ZERO, vec2(40.0, 20.0));
+        let fill_rect = frame.fill_rect(widget_rect);
+        let Shape::Vec(dashes) = frame.stroke_shape(fill_rect, frame.fill_rounding(), 0.0) else {
+            panic!("expected a dash per shape");
+        };
+        assert_eq!(dashes.len(), 12);
+    }
+
+    #[test]
+    fn nine_patch_slices_the_texture_into_nine_quads() {
+        let texture_id = TextureId::Managed(1);
+        let nine_patch = NinePatch::new(
+            texture_id,
+            vec2(64.0, 32.0),
+            Marginf {
+                left: 8.0,
+                right: 16.0,
+                top: 4.0,
+                bottom: 12.0,
+            },
+        );
+        let rect = Rect::from_min_size(Pos2::new(10.0, 20.0), vec2(200.0, 100.0));
+        let mesh = nine_patch.mesh(rect);
+        assert_eq!(mesh.texture_id, texture_id);
+        assert_eq!(mesh.vertices.len(), 9 * 4);
+        assert_eq!(mesh.indices.len(), 9 * 6);
+
+        let quads: Vec<(Rect, Rect)> = mesh
+            .vertices
+            .chunks(4)
+            .map(|quad| {
+                let pos = Rect::from_points(&quad.iter().map(|v| v.pos).collect::<Vec<_>>());
+                let uv = Rect::from_points(&quad.iter().map(|v| v.uv).collect::<Vec<_>>());
+                (pos, uv)
+            })
+            .collect();
+
+        // The corners keep their size in texels:
+        let (top_left, top_left_uv) = quads[0];
+        assert_eq!(top_left, Rect::from_min_size(rect.min, vec2(8.0, 4.0)));
+        assert_eq!(
+            top_left_uv,
+            Rect::from_min_max(Pos2::ZERO, Pos2::new(0.125, 0.125))
+        );
+        let (bottom_right, bottom_right_uv) = quads[8];
+        assert_eq!(
+            bottom_right,
+            Rect::from_min_max(rect.max - vec2(16.0, 12.0), rect.max)
+        );
+        assert_eq!(
+            bottom_right_uv,
+            Rect::from_min_max(Pos2::new(0.75, 0.625), Pos2::new(1.0, 1.0))
+        );
+
+        // The center stretches the middle of the image over the rest:
+        let (center, center_uv) = quads[4];
+        assert_eq!(
+            center,
+            Rect::from_min_max(rect.min + vec2(8.0, 4.0), rect.max - vec2(16.0, 12.0))
+        );
+        assert_eq!(
+            center_uv,
+            Rect::from_min_max(Pos2::new(0.125, 0.125), Pos2::new(0.75, 0.625))
+        );
+
+        // Too small for the corners, they shrink to fit:
+        let small = Rect::from_min_size(Pos2::ZERO, vec2(12.0, 100.0));
+        let mesh = nine_patch.mesh(small);
+        let left_width = mesh.vertices[1].pos.x - mesh.vertices[0].pos.x;
+        assert_eq!(left_width, 4.0);
+    }
+
+    #[test]
+    fn header_and_footer_shrink_the_content_rect() {
+        let widget_rect = Rect::from_min_size(Pos2::ZERO, vec2(200.0, 150.0));
+        let frame = ExtFrame::new()
+            .inner_margin(8)
+            .stroke(ExtStroke::new(2.0, Color32::BLACK));
+        let plain = frame.content_rect(widget_rect);
+
+        let header = FrameBand::new(30.0).fill(Color32::RED);
+        let footer = FrameBand::new(40.0)
+            .fill(Color32::BLUE)
+            .divider(Stroke::new(1.0, Color32::GRAY));
+        let frame = frame.header(header).footer(footer);
+        let content_rect = frame.content_rect(widget_rect);
+        assert_eq!(content_rect.width(), plain.width());
+        assert_eq!(content_rect.top(), plain.top() + 30.0);
+        assert_eq!(content_rect.bottom(), plain.bottom() - 40.0);
+        assert_eq!(content_rect.height(), plain.height() - 30.0 - 40.0);
+
+        // The bands are at the edges of the fill, outside the inner margin:
+        let fill_rect = frame.fill_rect(widget_rect);
+        let header_rect = frame.header_rect(widget_rect).unwrap();
+        let footer_rect = frame.footer_rect(widget_rect).unwrap();
+        assert_eq!(header_rect.top(), fill_rect.top());
+        assert_eq!(header_rect.height(), 30.0);
+        assert_eq!(footer_rect.bottom(), fill_rect.bottom());
+        assert_eq!(footer_rect.height(), 40.0);
+        assert_eq!(footer_rect.width(), fill_rect.width());
+
+        let ctx = crate::Context::default();
+        let output = ctx.run(Default::default(), |ctx| {
+            frame.paint_into(
+                &ctx.layer_painter(crate::LayerId::background()),
+                widget_rect,
+            );
+        });
+        let fills: Vec<(Rect, Color32)> = output
+            .shapes
+            .iter()
+            .filter_map(|clipped| match &clipped.shape {
+                Shape::Rect(rect) if rect.stroke.is_empty() => Some((rect.rect, rect.fill)),
+                _ => None,
+            })
+            .collect();
+        assert!(fills.contains(&(header_rect, Color32::RED)));
+        assert!(fills.contains(&(footer_rect, Color32::BLUE)));
+    }
+
+    #[test]
+    fn pixel_snap_rounds_to_the_physical_pixels() {
+        let widget_rect = Rect::from_min_max(Pos2::new(10.3, 20.7), Pos2::new(110.2, 80.4));
+        let frame = ExtFrame::new()
+            .fill(Color32::WHITE)
+            .stroke(ExtStroke::new(1.0, Color32::BLACK));
+        assert_eq!(frame.pixel_snapped(widget_rect, 1.5), widget_rect);
+
+        let frame = frame.pixel_snap(true);
+        assert_eq!(
+            frame.pixel_snapped(widget_rect, 1.5),
+            Rect::from_min_max(
+                Pos2::new(15.0 / 1.5, 31.0 / 1.5),
+                Pos2::new(165.0 / 1.5, 121.0 / 1.5)
+            )
+        );
+        assert_eq!(
+            frame.pixel_snapped(widget_rect, 2.0),
+            Rect::from_min_max(Pos2::new(10.5, 20.5), Pos2::new(110.0, 80.5))
+        );
+
+        let ctx = crate::Context::default();
+        ctx.set_pixels_per_point(1.5);
+        let output = ctx.run(Default::default(), |ctx| {
+            frame.paint_into(
+                &ctx.layer_painter(crate::LayerId::background()),
+                widget_rect,
+            );
+        });
+        let fill = output
+            .shapes
+            .iter()
+            .find_map(|clipped| match &clipped.shape {
+                Shape::Rect(rect) if rect.fill == Color32::WHITE => Some(rect.rect),
+                _ => None,
+            })
+            .unwrap();
+        for coordinate in [fill.min.x, fill.min.y, fill.max.x, fill.max.y] {
+            let pixels = coordinate * 1.5;
+            assert!((pixels - pixels.round()).abs() < 1e-3, "{fill:?}");
+        }
+    }
+
+    #[test]
+    fn ripple_grows_from_the_click_position() {
+        let ripple_color = Color32::from_rgb(0, 0, 255);
+        let frame = ExtFrame::new()
+            .fill(Color32::WHITE)
+            .rounding(12)
+            .size_mode(FrameSize::Fixed {
+                width: 300.0,
+                height: 200.0,
+            })
+            .ripple(ripple_color);
+
+        let ctx = crate::Context::default();
+        let run = |time: f64, events: Vec<crate::Event>| {
+            let input = crate::RawInput {
+                time: Some(time),
+                events,
+                ..Default::default()
+            };
+            let mut rect = Rect::NOTHING;
+            let output = ctx.run(input, |ctx| {
+                crate::CentralPanel::default().show(ctx, |ui| {
+                    rect = frame.clone().end(ui).rect;
+                });
+            });
+            let ripple = output
+                .shapes
+                .into_iter()
+                .find_map(|clipped| match clipped.shape {
+                    Shape::Path(path) if path.fill != Color32::TRANSPARENT => Some(path.points),
+                    _ => None,
+                });
+            (rect, ripple)
+        };
+
+        let (rect, ripple) = run(1.0, vec![]);
+        assert!(ripple.is_none());
+        let click_pos = rect.center() - vec2(20.0, 10.0);
+        let button = |pressed| crate::Event::PointerButton {
+            pos: click_pos,
+            button: crate::PointerButton::Primary,
+            pressed,
+            modifiers: Default::default(),
+        };
+        run(
+            1.0,
+            vec![crate::Event::PointerMoved(click_pos), button(true)],
+        );
+
+        let radius = |points: &[Pos2]| {
+            let centroid =
+                points.iter().fold(Vec2::ZERO, |sum, p| sum + p.to_vec2()) / points.len() as f32;
+            assert!(centroid.to_pos2().distance(click_pos) < 0.5, "{centroid:?}");
+            points
+                .iter()
+                .map(|p| p.distance(click_pos))
+                .fold(0.0, f32::max)
+        };
+        let radii: Vec<f32> = [1.1, 1.13, 1.16]
+            .into_iter()
+            .enumerate()
+            .map(|(i, time)| {
+                let events = if i == 0 { vec![button(false)] } else { vec![] };
+                let (_, ripple) = run(time, events);
+                radius(&ripple.expect("a ripple while animating"))
+            })
+            .collect();
+        assert_eq!(radii[0], 0.0, "starts on the click");
+        assert!(0.0 < radii[1] && radii[1] < radii[2], "{radii:?}");
+        assert!(ctx.has_requested_repaint());
+
+        // Clipped to the frame, and gone when done:
+        let (_, ripple) = run(1.4, vec![]);
+        let fill_rect = frame.fill_rect(frame.widget_rect(rect));
+        assert!(ripple
+            .unwrap()
+            .iter()
+            .all(|p| fill_rect.expand(1e-3).contains(*p)));
+        assert!(run(1.6, vec![]).1.is_none());
+    }
+
+    #[test]
+    fn auto_contrast_flips_at_mid_luma() {
+        let border = |fill: Color32| ExtStroke::new(1.0, Color32::RED).auto_contrast(fill).color;
+
+        // Just below and above the threshold:
+        let dark_fill = Color32::from_gray(120);
+        let light_fill = Color32::from_gray(136);
+        assert!(border(dark_fill).luma() > dark_fill.luma(), "lightened");
+        assert!(border(light_fill).luma() < light_fill.luma(), "darkened");
+        for fill in [dark_fill, light_fill] {
+            let delta = (border(fill).luma() - fill.luma()).abs();
+            assert!(
+                (delta - ExtStroke::AUTO_CONTRAST_LUMA_DELTA).abs() < 0.01,
+                "{delta}"
+            );
+        }
+
+        // Opaque, also for a translucent fill:
+        assert_eq!(border(Color32::from_black_alpha(100)).a(), 255);
+        assert!(border(Color32::BLACK).luma() > 0.0);
+        assert!(border(Color32::WHITE).luma() < 1.0);
+    }
+
+    #[test]
+    fn measure_matches_the_allocated_size() {
+        let frame = ExtFrame::new()
+            .inner_margin(6)
+            .outer_margin(Margin::symmetric(4, 2))
+            .stroke(ExtStroke::new(2.0, Color32::BLACK))
+            .outline(Stroke::new(1.0, Color32::BLUE), 2.0)
+            .box_sizing(BoxSizing::ContentBox)
+            .size_mode(FrameSize::Fixed {
+                width: 120.0,
+                height: 60.0,
+            });
+
+        let ctx = crate::Context::default();
+        let _ = ctx.run(Default::default(), |ctx| {
+            crate::CentralPanel::default().show(ctx, |ui| {
+                let measured = frame.measure(ui);
+                let cursor = ui.cursor().min;
+                let response = frame.clone().end(ui);
+                assert_eq!(measured, response.rect.size());
+                assert_eq!(response.rect.min, cursor);
+                // Content, padding, border, outline offset and width, and outer margin:
+                assert_eq!(
+                    measured,
+                    vec2(
+                        120.0 + 2.0 * (6.0 + 2.0 + 2.0 + 1.0 + 4.0),
+                        60.0 + 2.0 * (6.0 + 2.0 + 2.0 + 1.0 + 2.0)
+                    )
+                );
+            });
+        });
+    }
+
+    #[test]
+    fn later_frame_covers_the_shadow_of_an_earlier_one() {
+        let card = |fill: Color32| {
+            ExtFrame::new().fill(fill).shadow(ExtShadow {
+                blur_radius: 16.0,
+                spread: 4.0,
+                color: Color32::BLACK,
+                ..Default::default()
+            })
+        };
+        let (below, above) = (Color32::RED, Color32::BLUE);
+        let below_rect = Rect::from_min_size(Pos2::new(20.0, 20.0), vec2(100.0, 50.0));
+        let above_rect = below_rect.translate(vec2(0.0, 40.0));
+
+        let ctx = crate::Context::default();
+        let output = ctx.run(Default::default(), |ctx| {
+            crate::CentralPanel::default().show(ctx, |ui| {
+                card(below).paint_at(ui, below_rect);
+                card(above).paint_at(ui, above_rect);
+            });
+        });
+        let shapes: Vec<&Shape> = output.shapes.iter().map(|clipped| &clipped.shape).collect();
+        let fill_index = |color: Color32| {
+            shapes
+                .iter()
+                .position(|shape| matches!(shape, Shape::Rect(rect) if rect.fill == color))
+                .unwrap()
+        };
+        let shadows: Vec<(usize, Rect)> = shapes
+            .iter()
+            .enumerate()
+            .filter_map(|(i, shape)| match shape {
+                Shape::Mesh(mesh) => Some((i, mesh.calc_bounds())),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(shadows.len(), 2);
+
+        // The shadow of the frame below reaches under the frame above, which is painted later:
+        let (below_shadow_index, below_shadow_bounds) = shadows[0];
+        assert!(below_shadow_bounds.intersects(above_rect));
+        assert!(below_shadow_index < fill_index(below));
+        assert!(fill_index(below) < shadows[1].0);
+        assert!(shadows[1].0 < fill_index(above));
+    }
+
+    #[test]
+    fn overflow_fade_only_when_the_content_overflows() {
+        let frame = ExtFrame::new()
+            .fill(Color32::WHITE)
+            .inner_margin(4)
+            .overflow_fade(true);
+        let outer_rect = Rect::from_min_size(Pos2::new(10.0, 10.0), vec2(100.0, 80.0));
+        let content_rect = frame.content_rect(frame.widget_rect(outer_rect));
+        let fades = |frame: &ExtFrame, content_bounds: Rect| {
+            let ctx = crate::Context::default();
+            let output = ctx.run(Default::default(), |ctx| {
+                crate::CentralPanel::default().show(ctx, |ui| {
+                    frame.paint_overflow_fade(ui, outer_rect, content_bounds);
+                });
+            });
+            output
+                .shapes
+                .into_iter()
+                .filter_map(|clipped| match clipped.shape {
+                    Shape::Mesh(mesh) => Some(mesh),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+        };
+
+        assert!(fades(&frame, content_rect.shrink(5.0)).is_empty());
+
+        let taller = content_rect.with_max_y(content_rect.bottom() + 50.0);
+        let bottom_fade = fades(&frame, taller);
+        assert_eq!(bottom_fade.len(), 1);
+        let bounds = bottom_fade[0].calc_bounds();
+        assert_eq!(bounds.bottom(), content_rect.bottom());
+        assert_eq!(bounds.height(), ExtFrame::OVERFLOW_FADE_HEIGHT);
+        let bottom_vertex = bottom_fade[0]
+            .vertices
+            .iter()
+            .find(|vertex| vertex.pos.y == content_rect.bottom())
+            .unwrap();
+        assert_eq!(bottom_vertex.color, Color32::WHITE);
+
+        // Scrolled down, content is clipped at both edges:
+        let both = fades(&frame, taller.translate(vec2(0.0, -20.0)));
+        assert_eq!(both[0].vertices.len(), 2 * bottom_fade[0].vertices.len());
+
+        assert!(fades(&frame.clone().overflow_fade(false), taller).is_empty());
+    }
+
+    #[test]
+    fn equal_styles_have_equal_style_keys() {
+        let card = || {
+            ExtFrame::new()
+                .fill(Color32::WHITE)
+                .rounding(8)
+                .stroke(ExtStroke::new(1.0, Color32::GRAY))
+                .shadow(ExtShadow {
+                    blur_radius: 8.0,
+                    color: Color32::BLACK,
+                    ..Default::default()
+                })
+        };
+        assert_eq!(card().style_key(), card().style_key());
+
+        // The size and margins don't change the look:
+        let resized = card()
+            .inner_margin(12)
+            .outer_margin(4)
+            .size_mode(FrameSize::Fixed {
+                width: 100.0,
+                height: 40.0,
+            });
+        assert_eq!(resized.style_key(), card().style_key());
+
+        assert_ne!(card().fill(Color32::RED).style_key(), card().style_key());
+        assert_ne!(card().rounding(4).style_key(), card().style_key());
+        assert_ne!(
+            card()
+                .stroke(
+                    ExtStroke::new(1.0, Color32::GRAY).style(StrokeStyle::Dashed {
+                        dash_length: 4.0,
+                        gap_length: 2.0,
+                    })
+                )
+                .style_key(),
+            card().style_key()
+        );
+        assert_ne!(card().embedded(card()).style_key(), card().style_key());
+    }
+
+    #[test]
+    fn show_returns_the_inner_value() {
+        let frame = ExtFrame::new()
+            .inner_margin(8)
+            .stroke(ExtStroke::new(2.0, Color32::BLACK))
+            .size_mode(FrameSize::Fixed {
+                width: 120.0,
+                height: 60.0,
+            });
+
+        let ctx = crate::Context::default();
+        let _ = ctx.run(Default::default(), |ctx| {
+            crate::CentralPanel::default().show(ctx, |ui| {
+                let cursor = ui.cursor().min;
+                let response = frame.clone().show(ui, |ui| {
+                    let label = ui.label("Inside");
+                    (42, label.rect)
+                });
+                let (answer, label_rect) = response.inner;
+                assert_eq!(answer, 42);
+                assert_eq!(response.response.rect.min, cursor);
+                assert_eq!(response.response.rect.size(), vec2(120.0, 60.0));
+
+                // The content starts inside the border and padding:
+                let content_rect = frame.content_rect(frame.widget_rect(response.response.rect));
+                assert_eq!(label_rect.min, content_rect.min);
+                assert_eq!(content_rect.min, cursor + vec2(10.0, 10.0));
+            });
+        });
+    }
+
+    #[test]
+    fn draggable_frame_moves_with_the_pointer() {
+        let frame = ExtFrame::new()
+            .fill(Color32::WHITE)
+            .size_mode(FrameSize::Fixed {
+                width: 100.0,
+                height: 50.0,
+            })
+            .draggable("card");
+
+        let ctx = crate::Context::default();
+        let run = |events: Vec<crate::Event>| {
+            let mut response = None;
+            let _ = ctx.run(
+                crate::RawInput {
+                    events,
+                    ..Default::default()
+                },
+                |ctx| {
+                    crate::CentralPanel::default().show(ctx, |ui| {
+                        response = Some(frame.clone().end(ui));
+                    });
+                },
+            );
+            response.unwrap()
+        };
+        let button = |pos, pressed| crate::Event::PointerButton {
+            pos,
+            button: crate::PointerButton::Primary,
+            pressed,
+            modifiers: Default::default(),
+        };
+
+        let home = run(vec![]).rect;
+        let grab = home.center();
+        run(vec![crate::Event::PointerMoved(grab), button(grab, true)]);
+        let moved = run(vec![crate::Event::PointerMoved(grab + vec2(30.0, 20.0))]);
+        assert_eq!(moved.drag_delta(), vec2(30.0, 20.0));
+        assert_eq!(moved.rect, home.translate(vec2(30.0, 20.0)));
+
+        let released = run(vec![button(grab + vec2(30.0, 20.0), false)]);
+        assert_eq!(released.drag_delta(), Vec2::ZERO);
+        assert_eq!(released.rect, home.translate(vec2(30.0, 20.0)));
+        assert_eq!(ExtFrame::drag_offset(&ctx, released.id), vec2(30.0, 20.0));
+
+        ExtFrame::reset_drag_offset(&ctx, released.id);
+        assert_eq!(run(vec![]).rect, home);
     }
 }
@@ -3,9 +3,11 @@
 //! For instance, a [`Frame`] adds a frame and background to some contained UI.
 
 pub(crate) mod area;
+pub mod card_group;
 pub mod collapsing_header;
 mod combo_box;
 pub mod frame;
+pub mod frame_ext;
 pub mod modal;
 pub mod panel;
 pub mod popup;
@@ -16,9 +18,11 @@ pub(crate) mod window;
 
 pub use {
     area::{Area, AreaState},
+    card_group::{CardGroup, CardGroupResponse},
     collapsing_header::{CollapsingHeader, CollapsingResponse},
     combo_box::*,
     frame::Frame,
+    frame_ext::ExtFrame,
     modal::{Modal, ModalResponse},
     panel::{CentralPanel, SidePanel, TopBottomPanel},
     popup::*,
@@ -303,6 +303,21 @@ pub struct Options {
     ///
     /// Default is `false`.
     pub reduce_texture_memory: bool,
+
+    /// Multiplier for the speed of all [`crate::Skeleton`] shimmer animations.
+    ///
+    /// Set to `0.0` to freeze them, e.g. for screenshots.
+    ///
+    /// Default is `1.0`.
+    pub skeleton_speed: f32,
+
+    /// Freeze animations at a fixed phase and don't request repaints for them,
+    /// so automated screenshots are reproducible.
+    ///
+    /// [`crate::Skeleton`]s then stand still in their base color, without fading in.
+    ///
+    /// Default is `false`.
+    pub screenshot_mode: bool,
 }
 
 impl Default for Options {
@@ -335,6 +350,8 @@ impl Default for Options {
             scroll_zoom_speed: 1.0 / 200.0,
             input_options: Default::default(),
             reduce_texture_memory: false,
+            skeleton_speed: 1.0,
+            screenshot_mode: false,
         }
     }
 }
@@ -392,6 +409,8 @@ impl Options {
             scroll_zoom_speed,
             input_options,
             reduce_texture_memory,
+            skeleton_speed,
+            screenshot_mode,
         } = self;
 
         use crate::containers::CollapsingHeader;
@@ -418,6 +437,12 @@ impl Options {
                 ui.checkbox(warn_on_id_clash, "Warn if two widgets have the same Id");
 
                 ui.checkbox(reduce_texture_memory, "Reduce texture memory");
+
+                ui.add(
+                    crate::Slider::new(skeleton_speed, 0.0..=4.0).text("Skeleton animation speed"),
+                );
+
+                ui.checkbox(screenshot_mode, "Freeze animations for screenshots");
             });
 
         CollapsingHeader::new("🎑 Style")
@@ -22,7 +22,7 @@ use crate::{
     vec2, widgets,
     widgets::{
         color_picker, Button, Checkbox, DragValue, Hyperlink, Image, ImageSource, Label, Link,
-        RadioButton, SelectableLabel, Separator, Spinner, TextEdit, Widget,
+        Loadable, RadioButton, SelectableLabel, Separator, SkeletonList, Spinner, TextEdit, Widget,
     },
     Align, Color32, Context, CursorIcon, DragAndDrop, Id, InnerResponse, InputState, LayerId,
     Memory, Order, Painter, PlatformOutput, Pos2, Rangef, Rect, Response, Rgba, RichText, Sense,
@@ -2070,6 +2070,36 @@ impl Ui {
         Spinner::new().ui(self)
     }
 
+    /// Show data that is loaded in the background.
+    ///
+    /// While [`Loadable::Loading`] this shows a [`SkeletonList`] placeholder,
+    /// on [`Loadable::Error`] it shows the error message,
+    /// and on [`Loadable::Ready`] it calls `on_ready` and returns its result.
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// let state: egui::Loadable<String> = egui::Loadable::Loading;
+    /// ui.loadable(&state, |ui, text| ui.label(text));
+    /// # });
+    /// ```
+    pub fn loadable<T, E: std::fmt::Display, R>(
+        &mut self,
+        state: &Loadable<T, E>,
+        on_ready: impl FnOnce(&mut Self, &T) -> R,
+    ) -> Option<R> {
+        match state {
+            Loadable::Loading => {
+                self.add(SkeletonList::default());
+                None
+            }
+            Loadable::Ready(value) => Some(on_ready(self, value)),
+            Loadable::Error(err) => {
+                self.colored_label(self.visuals().error_fg_color, err.to_string());
+                None
+            }
+        }
+    }
+
     /// Modify an angle. The given angle should be in radians, but is shown to the user in degrees.
     /// The angle is NOT wrapped, so the user may select, for instance 720° = 2𝞃 = 4π
     pub fn drag_angle(&mut self, radians: &mut f32) -> Response {
@@ -18,6 +18,7 @@ mod progress_bar;
 mod radio_button;
 mod selected_label;
 mod separator;
+pub mod skeleton;
 mod slider;
 mod spinner;
 pub mod text_edit;
@@ -37,6 +38,9 @@ pub use self::{
     radio_button::RadioButton,
     selected_label::SelectableLabel,
     separator::Separator,
+    skeleton::{
+        FillMode, HasSkeleton, Loadable, Orientation, Skeleton, SkeletonList, SkeletonShapeType,
+    },
     slider::{Slider, SliderClamping, SliderOrientation},
     spinner::Spinner,
     text_edit::{TextBuffer, TextEdit},
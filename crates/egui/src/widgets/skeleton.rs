@@ -3,38 +3,195 @@
 //! The Skeleton component is a temporary animation placeholder
 //! for when a service call takes time to return data and we don't want
 //! to block rendering the rest of the UI. It is intended to be used
-//! inside an ExtFrame to fill the entire frame.
+//! inside an [`ExtFrame`] to fill the entire frame.
 //!
 //! Reference: [Fluent UI Skeleton](https://react.fluentui.dev/?path=/docs/components-skeleton--docs)
 
 use std::sync::Arc;
 
-use crate::egui::{self, Color32, Pos2, Response, Sense, Ui, Widget};
-use crate::epaint::{self, Mesh, Rect, Stroke, StrokeKind, TextureId, Vertex};
+use crate::containers::frame_ext::ExtFrame;
+use crate::epaint::{self, Mesh, Rect, Rounding, Stroke, Vertex};
+use crate::{Color32, Painter, Pos2, Response, Sense, Ui, Vec2, Widget, WidgetInfo, WidgetType};
 
 /// 不同的 Skeleton 外观类型
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum SkeletonShapeType {
     /// 矩形占位（默认）
     Rectangle,
     /// 正方形占位
-    Square,
+    Square {
+        /// The corner rounding of the square.
+        rounding: Rounding,
+    },
     /// 圆形占位
     Circle,
+    /// A fully rounded capsule filling the area, e.g. for buttons and chips.
+    ///
+    /// The rounding is half of the height.
+    Pill,
+
+    /// An arc rotating once per [`Skeleton::animation_duration`],
+    /// for small loading indicators where a bar doesn't fit.
+    Spinner {
+        /// The length of the arc, in radians.
+        arc_length: f32,
+
+        /// The width of the arc.
+        thickness: f32,
+    },
+
+    /// Pick a shape from the size of the area when painting, see [`Self::for_size`].
+    ///
+    /// Useful for generic loading regions, where the content could be text or an avatar.
+    Auto,
+}
+
+impl SkeletonShapeType {
+    /// A square with slightly rounded corners.
+    pub const SQUARE: Self = Self::Square {
+        rounding: Rounding::same(2),
+    };
+
+    /// A spinner with a three-quarter arc.
+    pub const SPINNER: Self = Self::Spinner {
+        arc_length: 1.5 * std::f32::consts::PI,
+        thickness: 2.0,
+    };
+
+    /// Near-square areas up to this size are shown as a [`Self::Circle`] by [`Self::Auto`],
+    /// like an avatar. Larger ones get a [`Self::SQUARE`].
+    pub const AUTO_CIRCLE_MAX_SIZE: f32 = 48.0;
+
+    /// The shape [`Self::Auto`] picks for an area of the given size:
+    ///
+    /// - near-square (aspect ratio between 3:4 and 4:3) and small: a [`Self::Circle`] avatar;
+    /// - near-square and larger: a [`Self::SQUARE`];
+    /// - anything else: a [`Self::Rectangle`], like a line of text.
+    pub fn for_size(size: Vec2) -> Self {
+        let aspect_ratio = size.x / size.y;
+        if !(0.75..=4.0 / 3.0).contains(&aspect_ratio) {
+            Self::Rectangle
+        } else if size.max_elem() <= Self::AUTO_CIRCLE_MAX_SIZE {
+            Self::Circle
+        } else {
+            Self::SQUARE
+        }
+    }
+
+    /// This shape, or the one picked by [`Self::Auto`] for an area of the given size.
+    pub fn resolve(&self, size: Vec2) -> Self {
+        match self {
+            Self::Auto => Self::for_size(size),
+            _ => self.clone(),
+        }
+    }
+
+    /// The rounding of the outline around a [`Self::Rectangle`].
+    const OUTLINE_ROUNDING: Rounding = Rounding::same(2);
+}
+
+/// The direction the shimmer of a [`Skeleton`] sweeps in.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Orientation {
+    /// From left to right, like reading a line of text.
+    #[default]
+    Horizontal,
+
+    /// From top to bottom, for tall and narrow placeholders.
+    Vertical,
+}
+
+impl Orientation {
+    /// `rect` with the sweep direction along x, i.e. transposed for [`Self::Vertical`].
+    ///
+    /// The shimmer is always computed from left to right in this space.
+    fn along_x(self, rect: Rect) -> Rect {
+        match self {
+            Self::Horizontal => rect,
+            Self::Vertical => transposed(rect),
+        }
+    }
+}
+
+/// `rect` mirrored across the diagonal, swapping x and y.
+fn transposed(rect: Rect) -> Rect {
+    Rect::from_min_max(
+        Pos2::new(rect.min.y, rect.min.x),
+        Pos2::new(rect.max.y, rect.max.x),
+    )
+}
+
+/// The time driving the shimmer animation, scaled by [`crate::Options::skeleton_speed`].
+///
+/// This is the absolute [`crate::InputState::time`], the same on every backend.
+/// The animations are a function of it, never accumulated frame by frame,
+/// so they stay in step when frames are skipped or coalesced by `request_repaint_after`.
+///
+/// `None` in [`crate::Options::screenshot_mode`], when skeletons stand still.
+fn animation_time(ui: &Ui) -> Option<f32> {
+    let (speed, screenshot_mode) = ui
+        .ctx()
+        .options(|options| (options.skeleton_speed, options.screenshot_mode));
+    (!screenshot_mode).then(|| ui.input(|i| i.time) as f32 * speed)
+}
+
+/// A small, fast pseudo-random number generator step (`SplitMix64`).
+fn split_mix64(state: u64) -> u64 {
+    let mut z = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// The rounding that turns `rect` into a capsule.
+fn pill_rounding(rect: Rect) -> Rounding {
+    Rounding::from(rect.height() / 2.0)
 }
 
 /// Skeleton 占位组件
 ///
 /// - `base_color` 是背景色；
 /// - `highlight_color` 是移动高光带的颜色；
-/// - `animation_duration` 控制一个动画循环的时长（秒）；
-/// - `shape_type` 指定占位的形状类型。
+/// - `auto_highlight` makes [`Skeleton::with_base_color`] derive `highlight_color` from the base;
+/// - `highlight_alpha` blends the highlight over `base_color` like a translucent overlay;
+/// - `band_count` is the number of evenly spaced highlight bands;
+/// - `animation_duration` 控制一个动画循环的时长（秒），at least [`Skeleton::MIN_ANIMATION_DURATION`]；
+/// - `shape_type` 指定占位的形状类型；
+/// - `outline` is an optional faint border around the placeholder;
+/// - `progress` turns the shimmer into a filling bar for determinate loading;
+/// - `accessible_label` is what screen readers announce for the busy placeholder;
+/// - `size` is an exact size to allocate, instead of filling the available space;
+/// - `texture_size` is the size of a loading image, scaled down to fit the available space;
+/// - `start_delay` is how many seconds the placeholder stays still after it first appears;
+/// - `fade_in` is how many seconds the placeholder takes to fade in when it first appears;
+/// - `max_animation_time` is how many seconds the placeholder animates before it stands still;
+/// - `rounding` rounds the corners of rectangle placeholders;
+/// - `orientation` is the direction the shimmer sweeps in;
+/// - `sweep_pause` is how many seconds the highlight waits off-screen between sweeps;
+/// - `accent` tints the highlight toward a brand color;
+/// - `wireframe` draws only the outlines of the shapes, without any fill.
 #[derive(Clone, Debug)]
 pub struct Skeleton {
     pub base_color: Color32,
     pub highlight_color: Color32,
+    pub auto_highlight: bool,
+    pub highlight_alpha: f32,
+    pub band_count: usize,
     pub animation_duration: f32,
     pub shape_type: SkeletonShapeType,
+    pub outline: Option<Stroke>,
+    pub progress: Option<f32>,
+    pub accessible_label: Option<String>,
+    pub size: Option<Vec2>,
+    pub texture_size: Option<Vec2>,
+    pub start_delay: f32,
+    pub fade_in: Option<f32>,
+    pub max_animation_time: Option<f32>,
+    pub rounding: Rounding,
+    pub orientation: Orientation,
+    pub sweep_pause: f32,
+    pub accent: Option<Color32>,
+    pub wireframe: bool,
 }
 
 impl Default for Skeleton {
@@ -42,111 +199,2186 @@ impl Default for Skeleton {
         Self {
             base_color: Color32::from_gray(200),
             highlight_color: Color32::from_gray(230),
+            auto_highlight: true,
+            highlight_alpha: 1.0,
+            band_count: 1,
             animation_duration: 1.5, // seconds per cycle,
             shape_type: SkeletonShapeType::Rectangle,
+            outline: None,
+            progress: None,
+            accessible_label: Some("Loading…".to_owned()),
+            size: None,
+            texture_size: None,
+            start_delay: 0.0,
+            fade_in: None,
+            max_animation_time: None,
+            rounding: Rounding::ZERO,
+            orientation: Orientation::Horizontal,
+            sweep_pause: 0.0,
+            accent: None,
+            wireframe: false,
         }
     }
 }
 
 impl Skeleton {
+    /// How much lighter or darker than the base color [`Self::from_visuals`] makes the highlight,
+    /// as a difference in luma (`0..=1`).
+    pub const HIGHLIGHT_LUMA_DELTA: f32 = 0.1;
+
+    /// How far [`Self::accent`] moves the highlight toward the accent color, in `0..=1`.
+    pub const ACCENT_TINT: f32 = 0.4;
+
+    /// The width of the lines of a [`Self::wireframe`] placeholder without an [`Self::outline`].
+    pub const WIREFRAME_WIDTH: f32 = 1.0;
+
+    /// The shortest [`Self::animation_duration`], in seconds.
+    ///
+    /// Shorter durations, including zero, are treated as this, so the animations never
+    /// divide by zero.
+    pub const MIN_ANIMATION_DURATION: f32 = 0.05;
+
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// A placeholder in the colors of the given theme.
+    ///
+    /// The base color is the fill of inactive widgets. The highlight is the base color nudged
+    /// toward the text color by [`Self::HIGHLIGHT_LUMA_DELTA`], so the shimmer is equally
+    /// visible in light and dark mode.
+    pub fn from_visuals(visuals: &crate::Visuals) -> Self {
+        let base_color = visuals.widgets.inactive.bg_fill;
+        Self {
+            base_color,
+            highlight_color: base_color
+                .nudged_toward(visuals.text_color(), Self::HIGHLIGHT_LUMA_DELTA),
+            ..Default::default()
+        }
+    }
+
+    /// A placeholder that looks like the given frame: its fill and rounding,
+    /// with a highlight as visible as in [`Self::from_visuals`].
+    ///
+    /// Use this for a placeholder filling a card, so it has the same shape and color.
+    /// A frame without a fill keeps the default colors.
+    pub fn matching(frame: &ExtFrame) -> Self {
+        let mut skeleton = Self {
+            rounding: frame.rounding,
+            ..Default::default()
+        };
+        if frame.fill != Color32::TRANSPARENT {
+            skeleton.base_color = frame.fill;
+            skeleton.highlight_color = frame.fill.nudged_for_contrast(Self::HIGHLIGHT_LUMA_DELTA);
+        }
+        skeleton
+    }
+
+    /// Round the corners of [`SkeletonShapeType::Rectangle`] placeholders. Default: square corners.
+    #[inline]
+    pub fn rounding(mut self, rounding: impl Into<Rounding>) -> Self {
+        self.rounding = rounding.into();
+        self
+    }
+
+    /// Sweep the shimmer (and fill a [`Self::progress`] bar) in this direction.
+    /// Default: [`Orientation::Horizontal`].
+    ///
+    /// [`Orientation::Vertical`] suits tall and narrow placeholders better.
+    #[inline]
+    pub fn orientation(mut self, orientation: Orientation) -> Self {
+        self.orientation = orientation;
+        self
+    }
+
+    /// A placeholder of exactly this size, instead of one filling the available space.
+    ///
+    /// Use this to put a skeleton inline among other widgets, e.g. in a horizontal layout.
+    #[inline]
+    pub fn with_size(mut self, size: impl Into<Vec2>) -> Self {
+        self.size = Some(size.into());
+        self
+    }
+
+    /// A placeholder the size of an image that is still loading, so the layout doesn't shift
+    /// when the real [`crate::Image`] replaces it.
+    ///
+    /// If the image doesn't fit the available space, the placeholder is scaled down to fit,
+    /// keeping its aspect ratio. It is never scaled up.
+    #[inline]
+    pub fn for_texture_size(mut self, texture_size: impl Into<Vec2>) -> Self {
+        self.texture_size = Some(texture_size.into());
+        self
+    }
+
+    /// The size of the placeholder for [`Self::for_texture_size`] in the given available space.
+    fn fit_texture_size(texture_size: Vec2, available: Vec2) -> Vec2 {
+        let scale = (available / texture_size).min_elem().min(1.0);
+        if scale.is_finite() && scale > 0.0 {
+            texture_size * scale
+        } else {
+            Vec2::ZERO
+        }
+    }
+
+    /// Wait this many seconds between two sweeps of the shimmer, with the highlight off-screen,
+    /// instead of starting the next sweep right away. Default: no pause.
+    ///
+    /// A sweep still takes [`Self::animation_duration`], so a cycle takes the sum of both.
+    #[inline]
+    pub fn sweep_pause(mut self, sweep_pause: f32) -> Self {
+        self.sweep_pause = sweep_pause;
+        self
+    }
+
+    /// Keep the placeholder still for this many seconds after it first appears,
+    /// before the shimmer starts.
+    ///
+    /// See also [`SkeletonList::stagger`].
+    #[inline]
+    pub fn start_delay(mut self, start_delay: f32) -> Self {
+        self.start_delay = start_delay;
+        self
+    }
+
+    /// Fade the placeholder in over this many seconds the first time it appears,
+    /// so loading states don't pop in. Off by default.
+    ///
+    /// When it first appeared is remembered by the id of the widget.
+    #[inline]
+    pub fn fade_in(mut self, duration: f32) -> Self {
+        self.fade_in = Some(duration);
+        self
+    }
+
+    /// Stop animating this many seconds after the placeholder first appeared,
+    /// and stand still in the base color, so a hanging load doesn't use CPU forever.
+    /// Off by default.
+    ///
+    /// Call [`Self::reset_animation`] to start animating again, e.g. when retrying.
+    #[inline]
+    pub fn max_animation_time(mut self, max_animation_time: f32) -> Self {
+        self.max_animation_time = Some(max_animation_time);
+        self
+    }
+
+    /// Forget when the placeholder with the given id (the id of its [`Response`]) first appeared,
+    /// restarting its [`Self::max_animation_time`] and [`Self::fade_in`].
+    pub fn reset_animation(ctx: &crate::Context, id: crate::Id) {
+        ctx.data_mut(|d| d.remove::<f64>(id.with("skeleton_first_seen")));
+    }
+
+    /// How many seconds ago the placeholder with the given id was first shown.
+    fn age(ui: &Ui, id: crate::Id) -> f64 {
+        let now = ui.input(|i| i.time);
+        let first_seen = ui.data_mut(|d| *d.get_temp_mut_or(id.with("skeleton_first_seen"), now));
+        now - first_seen
+    }
+
+    /// Count the [`Self::start_delay`] from when the placeholder with the given id first appeared,
+    /// rather than from the start of the [`animation_time`].
+    fn delayed_from(mut self, ui: &Ui, id: crate::Id) -> Self {
+        let speed = ui.ctx().options(|options| options.skeleton_speed);
+        let first_seen = ui.input(|i| i.time) - Self::age(ui, id);
+        self.start_delay += first_seen as f32 * speed;
+        self
+    }
+
+    /// Whether the placeholder with the given id has been shown for longer than
+    /// [`Self::max_animation_time`], and should stand still.
+    fn timed_out(&self, ui: &Ui, id: crate::Id) -> bool {
+        self.max_animation_time
+            .is_some_and(|max| Self::age(ui, id) >= max as f64)
+    }
+
+    /// The opacity of a placeholder with the given id, fading in over `duration` seconds
+    /// from the first frame it was shown in.
+    fn fade_in_opacity(ui: &Ui, id: crate::Id, duration: f32) -> f32 {
+        let age = Self::age(ui, id);
+        let opacity = if duration > 0.0 {
+            (age as f32 / duration).clamp(0.0, 1.0)
+        } else {
+            1.0
+        };
+        if opacity < 1.0 {
+            ui.ctx().request_repaint();
+        }
+        opacity
+    }
+
+    /// Paint the placeholder in this color.
+    ///
+    /// Unless a [`Self::highlight_color`] was set, the highlight is derived from it
+    /// with [`Self::auto_highlight_color`], so setting the base color alone is enough.
+    #[inline]
+    pub fn with_base_color(mut self, base_color: impl Into<Color32>) -> Self {
+        self.base_color = base_color.into();
+        if self.auto_highlight {
+            self.highlight_color = Self::auto_highlight_color(self.base_color);
+        }
+        self
+    }
+
+    /// Sweep a highlight of this color over the base color.
+    ///
+    /// This turns off [`Self::auto_highlight`], so [`Self::with_base_color`] keeps it.
+    #[inline]
+    pub fn highlight_color(mut self, highlight_color: impl Into<Color32>) -> Self {
+        self.highlight_color = highlight_color.into();
+        self.auto_highlight = false;
+        self
+    }
+
+    /// The highlight [`Self::with_base_color`] picks for `base_color`:
+    /// lighter and less saturated, by moving it toward white by [`Self::HIGHLIGHT_LUMA_DELTA`].
+    pub fn auto_highlight_color(base_color: Color32) -> Color32 {
+        base_color.nudged_toward(Color32::WHITE, Self::HIGHLIGHT_LUMA_DELTA)
+    }
+
+    #[inline]
+    pub fn shape_type(mut self, shape_type: SkeletonShapeType) -> Self {
+        self.shape_type = shape_type;
+        self
+    }
+
+    /// Blend the highlight band over the base color with this opacity in `0..=1`,
+    /// instead of painting it opaque (`1.0`, the default).
+    ///
+    /// With a translucent white or black highlight, the shimmer works on any base color.
+    #[inline]
+    pub fn highlight_alpha(mut self, highlight_alpha: f32) -> Self {
+        self.highlight_alpha = highlight_alpha.clamp(0.0, 1.0);
+        self
+    }
+
+    /// How many seconds one sweep of the shimmer (or one turn of a spinner) takes. Default: 1.5.
+    ///
+    /// Clamped to at least [`Self::MIN_ANIMATION_DURATION`].
+    #[inline]
+    pub fn animation_duration(mut self, seconds: f32) -> Self {
+        self.animation_duration = seconds.max(Self::MIN_ANIMATION_DURATION);
+        self
+    }
+
+    /// Tint the highlight toward this color by [`Self::ACCENT_TINT`],
+    /// e.g. for a subtle sweep in a brand color instead of a plain lighter gray.
+    #[inline]
+    pub fn accent(mut self, accent: impl Into<Color32>) -> Self {
+        self.accent = Some(accent.into());
+        self
+    }
+
+    /// Sweep this many evenly spaced highlight bands across the placeholder. Default: 1.
+    ///
+    /// More bands make wide placeholders look less sparse.
+    #[inline]
+    pub fn band_count(mut self, band_count: usize) -> Self {
+        self.band_count = band_count.max(1);
+        self
+    }
+
+    /// The [`Self::animation_duration`], but at least [`Self::MIN_ANIMATION_DURATION`],
+    /// also when the field was set directly.
+    fn cycle_duration(&self) -> f32 {
+        self.animation_duration.max(Self::MIN_ANIMATION_DURATION)
+    }
+
+    /// The color at the peak of the highlight band.
+    fn peak_color(&self) -> Color32 {
+        let highlight_color = match self.accent {
+            Some(accent) => self.highlight_color.lerp(accent, Self::ACCENT_TINT),
+            None => self.highlight_color,
+        };
+        self.base_color.lerp(highlight_color, self.highlight_alpha)
+    }
+
+    /// Draw a border around the placeholder, on top of the fill and shimmer.
+    #[inline]
+    pub fn outline(mut self, outline: impl Into<Stroke>) -> Self {
+        self.outline = Some(outline.into());
+        self
+    }
+
+    /// Draw only the outlines of the placeholder shapes, for a lighter look. Off by default.
+    ///
+    /// The lines are the [`Self::outline`] if set, or else [`Self::WIREFRAME_WIDTH`] wide
+    /// in the base color. There is no shimmer nor [`Self::progress`] bar.
+    /// Spinners are lines already, and are painted as usual.
+    #[inline]
+    pub fn wireframe(mut self, wireframe: bool) -> Self {
+        self.wireframe = wireframe;
+        self
+    }
+
+    /// The lines of a [`Self::wireframe`] placeholder.
+    fn wireframe_stroke(&self) -> Stroke {
+        self.outline
+            .unwrap_or_else(|| Stroke::new(Self::WIREFRAME_WIDTH, self.base_color))
+    }
+
+    /// Show how far loading has come, in `0..=1`, instead of an indeterminate shimmer.
+    ///
+    /// Rectangle placeholders are then painted as a bar that fills up with the highlight color
+    /// from the left, e.g. for downloads.
+    #[inline]
+    pub fn progress(mut self, progress: f32) -> Self {
+        self.progress = Some(progress.clamp(0.0, 1.0));
+        self
+    }
+
+    /// What screen readers announce for the placeholder. Default: "Loading…".
+    ///
+    /// The placeholder is reported as a busy progress indicator.
+    /// Set [`Self::accessible_label`] to `None` for purely decorative placeholders.
+    #[inline]
+    pub fn accessible_label(mut self, accessible_label: impl Into<String>) -> Self {
+        self.accessible_label = Some(accessible_label.into());
+        self
+    }
+
+    /// Report the placeholder as a busy progress indicator to the accessibility output.
+    fn widget_info(&self, response: &Response) {
+        let Some(label) = &self.accessible_label else {
+            return;
+        };
+        response.widget_info(|| {
+            let mut info =
+                WidgetInfo::labeled(WidgetType::ProgressIndicator, response.enabled(), label);
+            info.value = self
+                .progress
+                .map(|progress| (progress as f64 * 100.0).floor());
+            info
+        });
+
+        #[cfg(feature = "accesskit")]
+        response
+            .ctx
+            .accesskit_node_builder(response.id, |node| node.set_busy());
+    }
+
+    /// The points along the arc of a [`SkeletonShapeType::Spinner`] centered in `rect`.
+    ///
+    /// The arc starts at an angle that turns once per [`Self::animation_duration`].
+    fn spinner_points(&self, rect: Rect, arc_length: f32, thickness: f32, time: f32) -> Vec<Pos2> {
+        const SEGMENTS: usize = 32;
+        let radius = (0.5 * rect.width().min(rect.height()) - 0.5 * thickness).max(0.0);
+        let start_angle = std::f32::consts::TAU * (time / self.cycle_duration()).fract();
+        (0..=SEGMENTS)
+            .map(|i| {
+                let angle = start_angle + arc_length * i as f32 / SEGMENTS as f32;
+                rect.center() + radius * crate::Vec2::angled(angle)
+            })
+            .collect()
+    }
+
+    /// Like [`Self::add_shimmer_rect`], but only for the part of `rect` within `clip_rect`.
+    ///
+    /// The highlight band sweeps across the visible part only,
+    /// so placeholders that are mostly scrolled out of view don't waste any geometry.
+    ///
+    /// With a [`Self::progress`], a filling bar is added instead of the shimmer.
+    /// Without a `time`, the rect is plain [`Self::base_color`].
+    fn add_visible_shimmer_rect(
+        &self,
+        mesh: &mut Mesh,
+        rect: Rect,
+        clip_rect: Rect,
+        time: Option<f32>,
+    ) {
+        let vertical_progress =
+            self.progress.is_some() && self.orientation == Orientation::Vertical;
+        if self.rounding != Rounding::ZERO || vertical_progress {
+            // Rounded corners need the whole rect; the painter clips away the rest.
+            let stops = self.gradient_stops(self.orientation.along_x(rect), time);
+            add_gradient_strip(mesh, rect, self.rounding, self.orientation, stops);
+            return;
+        }
+        if let Some(progress) = self.progress {
+            self.add_progress_rect(mesh, rect, clip_rect, progress);
+            return;
+        }
+        let visible_rect = rect.intersect(clip_rect);
+        if visible_rect.is_positive() {
+            self.add_shimmer_rect(mesh, visible_rect, time);
+        }
+    }
+
+    /// Append the visible part of `rect` to `mesh`, filled with the highlight
+    /// up to `progress` of its width and with the base color after that.
+    fn add_progress_rect(&self, mesh: &mut Mesh, rect: Rect, clip_rect: Rect, progress: f32) {
+        let fill_x = rect.left() + progress * rect.width();
+        let (filled, unfilled) = rect.split_left_right_at_x(fill_x);
+        for (part, color) in [(filled, self.peak_color()), (unfilled, self.base_color)] {
+            let visible_part = part.intersect(clip_rect);
+            if visible_part.is_positive() {
+                mesh.add_colored_rect(visible_part, color);
+            }
+        }
+    }
+
+    /// Append a rectangle with [`Self::band_count`] moving highlight bands to `mesh`.
+    ///
+    /// The band positions only depend on `rect` and `time`,
+    /// so every rectangle gets its own shimmer offset.
+    /// Until `time` reaches [`Self::start_delay`], or without a `time`,
+    /// the rectangle is plain [`Self::base_color`].
+    fn add_shimmer_rect(&self, mesh: &mut Mesh, rect: Rect, time: Option<f32>) {
+        let stops = self.shimmer_stops(self.orientation.along_x(rect), time);
+        add_gradient_strip(mesh, rect, Rounding::ZERO, self.orientation, stops);
+    }
+
+    /// The gradient from left to right across `rect`, as `(x, color)` stops:
+    /// the filled bar of a [`Self::progress`], or else the [`Self::shimmer_stops`].
+    fn gradient_stops(&self, rect: Rect, time: Option<f32>) -> Vec<(f32, Color32)> {
+        match self.progress {
+            Some(progress) => {
+                let fill_x = rect.left() + progress * rect.width();
+                vec![
+                    (rect.left(), self.peak_color()),
+                    (fill_x, self.peak_color()),
+                    (fill_x, self.base_color),
+                    (rect.right(), self.base_color),
+                ]
+            }
+            None => self.shimmer_stops(rect, time),
+        }
+    }
+
+    /// The gradient of [`Self::add_shimmer_rect`] from left to right, as `(x, color)` stops.
+    fn shimmer_stops(&self, rect: Rect, time: Option<f32>) -> Vec<(f32, Color32)> {
+        let sweep_duration = self.cycle_duration();
+        let cycle_duration = sweep_duration + self.sweep_pause.max(0.0);
+        let Some(time) = time
+            .map(|time| time - self.start_delay)
+            .filter(|&time| time >= 0.0)
+            .map(|time| time % cycle_duration)
+            .filter(|&time| time < sweep_duration)
+        else {
+            // Not started yet, standing still, or pausing between sweeps:
+            return vec![
+                (rect.left(), self.base_color),
+                (rect.right(), self.base_color),
+            ];
+        };
+
+        let band_count = self.band_count.max(1);
+        let shimmer_phase = (time / sweep_duration) % 1.0;
+        let shimmer_width = 0.2 * rect.width();
+        let peak_color = self.peak_color();
+
+        // The color eases from the base color up to the peak in the middle of the band and back,
+        // so the band edges have no visible seams:
+        let band_color = |x: f32, band_x: f32| {
+            let t = if shimmer_width > 0.0 {
+                ((x - band_x) / shimmer_width).clamp(0.0, 1.0)
+            } else {
+                0.5
+            };
+            let rise = 1.0 - (2.0 * t - 1.0).abs();
+            self.base_color
+                .lerp(peak_color, emath::easing::sin_in_out(rise))
+        };
+
+        // Evenly spaced bands, sweeping together:
+        let mut band_xs: Vec<f32> = (0..band_count)
+            .map(|band| {
+                let phase = (shimmer_phase + band as f32 / band_count as f32) % 1.0;
+                rect.left() + phase * (rect.width() + shimmer_width) - shimmer_width
+            })
+            .collect();
+        band_xs.sort_by(f32::total_cmp);
+
+        // Gradient stops: the base color, with [`SHIMMER_BAND_STEPS`] segments along each band.
+        // Where a band is cut off by the rect edge (or the band before it),
+        // it starts or ends with the color the band has there.
+        let mut stops = Vec::with_capacity((SHIMMER_BAND_STEPS + 1) * band_count + 2);
+        stops.push((rect.left(), self.base_color));
+        for band_x in band_xs {
+            let min_x = stops.last().map_or(rect.left(), |&(x, _)| x);
+            for i in 0..=SHIMMER_BAND_STEPS {
+                let x = band_x + shimmer_width * i as f32 / SHIMMER_BAND_STEPS as f32;
+                let x = x.clamp(min_x, rect.right());
+                let stop = (x, band_color(x, band_x));
+                if stops.last() != Some(&stop) {
+                    stops.push(stop);
+                }
+            }
+        }
+        stops.push((rect.right(), self.base_color));
+        stops
+    }
 }
 
-impl Widget for Skeleton {
+/// The number of segments of the gradient across a shimmer band.
+///
+/// Even, so that the peak in the middle of the band is one of the gradient stops.
+const SHIMMER_BAND_STEPS: usize = 8;
+
+/// Append `rect` with its corners rounded to `mesh`, colored by a gradient in the direction
+/// of `orientation`.
+///
+/// `stops` are `(x, color)` pairs sorted by `x`, from the left to the right edge of
+/// [`Orientation::along_x`] of `rect`.
+fn add_gradient_strip(
+    mesh: &mut Mesh,
+    rect: Rect,
+    rounding: Rounding,
+    orientation: Orientation,
+    mut stops: Vec<(f32, Color32)>,
+) {
+    const CORNER_STEPS: usize = 8;
+    let (rect, rounding) = match orientation {
+        Orientation::Horizontal => (rect, rounding),
+        Orientation::Vertical => (
+            transposed(rect),
+            Rounding {
+                ne: rounding.sw,
+                sw: rounding.ne,
+                ..rounding
+            },
+        ),
+    };
+    let max_radius = 0.5 * rect.width().min(rect.height());
+    let radius = |r: u8| f32::from(r).min(max_radius);
+    let (nw, ne, sw, se) = (
+        radius(rounding.nw),
+        radius(rounding.ne),
+        radius(rounding.sw),
+        radius(rounding.se),
+    );
+
+    // How far the rounded edge is from the straight edge at `x`:
+    let dip = |x: f32, left_radius: f32, right_radius: f32| {
+        let (offset, r) = if x < rect.left() + left_radius {
+            (rect.left() + left_radius - x, left_radius)
+        } else if x > rect.right() - right_radius {
+            (x - (rect.right() - right_radius), right_radius)
+        } else {
+            return 0.0;
+        };
+        r - (r * r - offset * offset).max(0.0).sqrt()
+    };
+
+    if rounding != Rounding::ZERO {
+        // Extra stops along the corners, with the color the gradient has there:
+        let left_width = nw.max(sw);
+        let right_width = ne.max(se);
+        let corner_xs: Vec<f32> = (1..CORNER_STEPS)
+            .map(|i| i as f32 / CORNER_STEPS as f32)
+            .flat_map(|t| [rect.left() + t * left_width, rect.right() - t * right_width])
+            .collect();
+        for x in corner_xs {
+            let Some(i) = stops.windows(2).position(|w| w[0].0 <= x && x <= w[1].0) else {
+                continue;
+            };
+            let ((x0, c0), (x1, c1)) = (stops[i], stops[i + 1]);
+            let t = if x1 > x0 { (x - x0) / (x1 - x0) } else { 0.0 };
+            stops.insert(i + 1, (x, c0.lerp(c1, t)));
+        }
+    }
+
+    let idx = mesh.vertices.len() as u32;
+    let uv = Pos2::new(0.0, 0.0);
+    for top in [true, false] {
+        for &(x, color) in &stops {
+            let y = if top {
+                rect.top() + dip(x, nw, ne)
+            } else {
+                rect.bottom() - dip(x, sw, se)
+            };
+            mesh.vertices.push(Vertex {
+                pos: Pos2::new(x, y),
+                uv,
+                color,
+            });
+        }
+    }
+    if orientation == Orientation::Vertical {
+        for vertex in &mut mesh.vertices[idx as usize..] {
+            vertex.pos = Pos2::new(vertex.pos.y, vertex.pos.x);
+        }
+    }
+    let n = stops.len() as u32;
+    for i in 0..n - 1 {
+        mesh.add_triangle(idx + i, idx + i + 1, idx + i + 1 + n);
+        mesh.add_triangle(idx + i, idx + i + 1 + n, idx + i + n);
+    }
+}
+
+/// Append a circle to `mesh`, colored by a gradient in the direction of `orientation`,
+/// with edges anti-aliased over `feather` points.
+///
+/// `stops` are `(x, color)` pairs sorted by `x`, from the left to the right edge of
+/// [`Orientation::along_x`] of the square around the circle.
+/// No vertex is further than `radius + feather / 2` from the `center`.
+fn add_gradient_circle(
+    mesh: &mut Mesh,
+    center: Pos2,
+    radius: f32,
+    orientation: Orientation,
+    stops: &[(f32, Color32)],
+    feather: f32,
+) {
+    const SEGMENTS: usize = 32;
+    let center = match orientation {
+        Orientation::Horizontal => center,
+        Orientation::Vertical => Pos2::new(center.y, center.x),
+    };
+    let inner_radius = (radius - 0.5 * feather).max(0.0);
+    let outer_radius = radius + 0.5 * feather;
+    let color_at = |stops: &[(f32, Color32)], x: f32| {
+        let i = stops.partition_point(|&(stop_x, _)| stop_x <= x);
+        match (i.checked_sub(1).map(|i| stops[i]), stops.get(i)) {
+            (Some((x0, c0)), Some(&(x1, c1))) if x1 > x0 => c0.lerp(c1, (x - x0) / (x1 - x0)),
+            (Some((_, color)), _) | (None, Some(&(_, color))) => color,
+            (None, None) => Color32::TRANSPARENT,
+        }
+    };
+
+    // Keep the stops within the circle, plus extra stops for a round outline.
+    // These are evenly spaced by angle, so they get denser towards the left and right:
+    let left = center.x - inner_radius;
+    let right = center.x + inner_radius;
+    let outline_xs = (0..=SEGMENTS).map(|i| {
+        let angle = std::f32::consts::PI * i as f32 / SEGMENTS as f32;
+        center.x - inner_radius * angle.cos()
+    });
+    let mut stops: Vec<(f32, Color32)> = outline_xs
+        .map(|x| (x, color_at(stops, x)))
+        .chain(
+            stops
+                .iter()
+                .copied()
+                .filter(|&(x, _)| left < x && x < right),
+        )
+        .collect();
+    stops.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    let idx = mesh.vertices.len() as u32;
+    let uv = Pos2::new(0.0, 0.0);
+    // Inner vertices on the top and bottom half, then transparent ones just outside them:
+    for (radius, opaque) in [(inner_radius, true), (outer_radius, false)] {
+        for top in [true, false] {
+            for &(x, color) in &stops {
+                let dx = ((x - center.x) / inner_radius.max(f32::EPSILON)).clamp(-1.0, 1.0);
+                let dy = (1.0 - dx * dx).max(0.0).sqrt();
+                let dy = if top { -dy } else { dy };
+                mesh.vertices.push(Vertex {
+                    pos: center + radius * Vec2::new(dx, dy),
+                    uv,
+                    color: if opaque { color } else { Color32::TRANSPARENT },
+                });
+            }
+        }
+    }
+    if orientation == Orientation::Vertical {
+        for vertex in &mut mesh.vertices[idx as usize..] {
+            vertex.pos = Pos2::new(vertex.pos.y, vertex.pos.x);
+        }
+    }
+
+    let n = stops.len() as u32;
+    let (top, bottom, outer_top, outer_bottom) = (idx, idx + n, idx + 2 * n, idx + 3 * n);
+    for i in 0..n - 1 {
+        // The fill between the top and bottom half:
+        mesh.add_triangle(top + i, top + i + 1, bottom + i + 1);
+        mesh.add_triangle(top + i, bottom + i + 1, bottom + i);
+        // The feathered edge:
+        for (inner, outer) in [(top, outer_top), (bottom, outer_bottom)] {
+            mesh.add_triangle(inner + i, inner + i + 1, outer + i + 1);
+            mesh.add_triangle(inner + i, outer + i + 1, outer + i);
+        }
+    }
+}
+
+/// Many rows of rectangle [`Skeleton`]s, painted as one [`Mesh`].
+///
+/// Adding 100 [`Skeleton`] widgets adds 100 shapes; a [`SkeletonList`] with 100 rows adds one.
+#[derive(Clone, Debug)]
+pub struct SkeletonList {
+    /// The style of each row.
+    pub skeleton: Skeleton,
+    pub rows: usize,
+    pub row_height: f32,
+    pub gap: f32,
+
+    /// Extra [`Skeleton::start_delay`] of each row compared to the row above, in seconds.
+    pub stagger: f32,
+
+    /// Vary the row widths, like lines of text, deterministically from this seed.
+    ///
+    /// `None` (the default) makes all rows full width.
+    pub seed: Option<u64>,
+}
+
+impl Default for SkeletonList {
+    fn default() -> Self {
+        Self {
+            skeleton: Skeleton::default(),
+            rows: 3,
+            row_height: 16.0,
+            gap: 4.0,
+            stagger: 0.0,
+            seed: None,
+        }
+    }
+}
+
+impl SkeletonList {
+    pub fn new(rows: usize) -> Self {
+        Self {
+            rows,
+            ..Default::default()
+        }
+    }
+
+    #[inline]
+    pub fn skeleton(mut self, skeleton: Skeleton) -> Self {
+        self.skeleton = skeleton;
+        self
+    }
+
+    #[inline]
+    pub fn row_height(mut self, row_height: f32) -> Self {
+        self.row_height = row_height;
+        self
+    }
+
+    #[inline]
+    pub fn gap(mut self, gap: f32) -> Self {
+        self.gap = gap;
+        self
+    }
+
+    /// Start the shimmer of each row this many seconds after the row above,
+    /// for a staggered entrance.
+    #[inline]
+    pub fn stagger(mut self, stagger: f32) -> Self {
+        self.stagger = stagger;
+        self
+    }
+
+    /// Make the rows look more like lines of text: slightly varying widths,
+    /// and a shorter last row. The same seed always gives the same widths.
+    #[inline]
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// The width of `row` as a fraction of the width of the list, see [`Self::seed`].
+    ///
+    /// Rows are between 85% and 100% wide, and the last of several rows between 50% and 75%.
+    pub fn row_width_fraction(&self, row: usize) -> f32 {
+        let Some(seed) = self.seed else {
+            return 1.0;
+        };
+        let random = (split_mix64(seed ^ split_mix64(row as u64)) >> 40) as f32 / (1 << 24) as f32;
+        if 1 < self.rows && row + 1 == self.rows {
+            0.5 + 0.25 * random
+        } else {
+            0.85 + 0.15 * random
+        }
+    }
+
+    /// The rect of each row, given the rect of the whole list.
+    pub fn row_rects(&self, rect: Rect) -> impl Iterator<Item = Rect> + '_ {
+        (0..self.rows).map(move |row| {
+            let top = rect.top() + row as f32 * (self.row_height + self.gap);
+            Rect::from_min_size(
+                Pos2::new(rect.left(), top),
+                crate::vec2(rect.width() * self.row_width_fraction(row), self.row_height),
+            )
+        })
+    }
+}
+
+impl Widget for SkeletonList {
     fn ui(self, ui: &mut Ui) -> Response {
-        let available_rect = ui.available_rect_before_wrap();
-        let painter = ui.painter();
-
-        match self.shape_type {
-            SkeletonShapeType::Rectangle => {
-                // 使用矩形+渐变高光效果
-                let time = ui.input().time;
-                let shimmer_phase = (time / self.animation_duration) % 1.0;
-                let shimmer_width = 0.2 * available_rect.width();
-                let shimmer_x = available_rect.left()
-                    + shimmer_phase * (available_rect.width() + shimmer_width)
-                    - shimmer_width;
-                let x0 = available_rect.left();
-                let x1 = shimmer_x.clamp(available_rect.left(), available_rect.right());
-                let x2 = (shimmer_x + shimmer_width).clamp(available_rect.left(), available_rect.right());
-                let x3 = available_rect.right();
-                let top = available_rect.top();
-                let bottom = available_rect.bottom();
-                let mut mesh = Mesh::default();
-                let uv = Pos2::new(0.0, 0.0);
-                mesh.vertices.push(Vertex { pos: Pos2::new(x0, top), uv: uv, color: self.base_color });
-                mesh.vertices.push(Vertex { pos: Pos2::new(x1, top), uv: uv, color: self.base_color });
-                mesh.vertices.push(Vertex { pos: Pos2::new(x2, top), uv: uv, color: self.highlight_color });
-                mesh.vertices.push(Vertex { pos: Pos2::new(x3, top), uv: uv, color: self.base_color });
-                mesh.vertices.push(Vertex { pos: Pos2::new(x0, bottom), uv: uv, color: self.base_color });
-                mesh.vertices.push(Vertex { pos: Pos2::new(x1, bottom), uv: uv, color: self.base_color });
-                mesh.vertices.push(Vertex { pos: Pos2::new(x2, bottom), uv: uv, color: self.highlight_color });
-                mesh.vertices.push(Vertex { pos: Pos2::new(x3, bottom), uv: uv, color: self.base_color });
-                mesh.indices.extend_from_slice(&[0, 1, 5, 0, 5, 4]);
-                mesh.indices.extend_from_slice(&[1, 2, 6, 1, 6, 5]);
-                mesh.indices.extend_from_slice(&[2, 3, 7, 2, 7, 6]);
-                painter.add(epaint::Shape::Mesh(Arc::new(mesh)));
-                ui.allocate_rect(available_rect, Sense::hover())
+        let height = if self.rows == 0 {
+            0.0
+        } else {
+            self.rows as f32 * self.row_height + (self.rows - 1) as f32 * self.gap
+        };
+        let (rect, response) =
+            ui.allocate_exact_size(crate::vec2(ui.available_width(), height), Sense::hover());
+
+        if ui.is_rect_visible(rect) && self.skeleton.wireframe {
+            let stroke = self.skeleton.wireframe_stroke();
+            for row_rect in self.row_rects(rect) {
+                ui.painter().add(epaint::RectShape::stroke(
+                    row_rect,
+                    self.skeleton.rounding,
+                    stroke,
+                ));
+            }
+        } else if ui.is_rect_visible(rect) {
+            let time = animation_time(ui).filter(|_| !self.skeleton.timed_out(ui, response.id));
+            let skeleton = self.skeleton.clone().delayed_from(ui, response.id);
+            let mut mesh = Mesh::default();
+            let clip_rect = ui.clip_rect();
+            for (row, row_rect) in self.row_rects(rect).enumerate() {
+                let row_time = time.map(|time| time - row as f32 * self.stagger);
+                skeleton.add_visible_shimmer_rect(&mut mesh, row_rect, clip_rect, row_time);
+            }
+            ui.painter().add(epaint::Shape::mesh(mesh));
+            if time.is_some() {
+                ui.ctx().request_repaint();
             }
-            SkeletonShapeType::Square => {
+        }
+        self.skeleton.widget_info(&response);
+
+        response
+    }
+}
+
+impl Skeleton {
+    /// Paint the placeholder into `rect`, without allocating any space in a [`Ui`].
+    ///
+    /// `time` drives the shimmer and spinner animations, in seconds,
+    /// and a repaint is requested to keep them moving.
+    /// Use this to show a skeleton inside a custom widget; [`Widget::ui`] calls it with
+    /// the input time scaled by [`crate::Options::skeleton_speed`],
+    /// and counts the [`Self::start_delay`] from when the placeholder first appeared.
+    pub fn paint_into(&self, painter: &Painter, rect: Rect, time: f32) {
+        self.paint(painter, rect, Some(time));
+    }
+
+    /// Whether the placeholder is a single flat color, with no shimmer nor progress bar to paint.
+    fn is_static(&self, time: Option<f32>) -> bool {
+        time.is_none() && self.progress.is_none()
+    }
+
+    /// Like [`Self::paint_into`], but without a `time` the placeholder stands still:
+    /// no shimmer, a spinner that doesn't turn, and no repaint requests.
+    fn paint(&self, painter: &Painter, rect: Rect, time: Option<f32>) {
+        let outline = self.outline.unwrap_or(Stroke::NONE);
+        let shape_type = self.shape_type.resolve(rect.size());
+        if self.wireframe && !matches!(shape_type, SkeletonShapeType::Spinner { .. }) {
+            self.paint_wireframe(painter, rect, &shape_type);
+            return;
+        }
+
+        match shape_type {
+            SkeletonShapeType::Rectangle | SkeletonShapeType::Auto => {
+                if self.is_static(time) {
+                    // Without a shimmer a plain rectangle is enough:
+                    painter.add(epaint::RectShape::filled(
+                        rect,
+                        self.rounding,
+                        self.base_color,
+                    ));
+                } else {
+                    // 使用矩形+渐变高光效果
+                    let mut mesh = Mesh::default();
+                    self.add_visible_shimmer_rect(&mut mesh, rect, painter.clip_rect(), time);
+                    painter.add(epaint::Shape::Mesh(Arc::new(mesh)));
+                    if time.is_some() {
+                        painter.ctx().request_repaint();
+                    }
+                }
+                if self.outline.is_some() {
+                    painter.add(epaint::RectShape::stroke(
+                        rect,
+                        if self.rounding == Rounding::ZERO {
+                            SkeletonShapeType::OUTLINE_ROUNDING
+                        } else {
+                            self.rounding
+                        },
+                        outline,
+                    ));
+                }
+            }
+            SkeletonShapeType::Square { rounding } => {
                 // 在区域中绘制一个正方形占位
-                let side = available_rect.width().min(available_rect.height());
-                let square_rect = Rect::from_center_size(available_rect.center(), crate::egui::vec2(side, side));
-                let shape = epaint::RectShape::new(
-                    square_rect,
-                    2.0,
-                    self.base_color,
-                    Stroke::NONE,
-                    StrokeKind::Outside,
-                );
-                painter.add(shape.into());
-                ui.allocate_rect(available_rect, Sense::hover())
+                let side = rect.width().min(rect.height());
+                let square_rect = Rect::from_center_size(rect.center(), crate::vec2(side, side));
+                let shape = epaint::RectShape::new(square_rect, rounding, self.base_color, outline);
+                painter.add(shape);
             }
             SkeletonShapeType::Circle => {
                 // 在区域中绘制一个圆形占位
-                let radius = available_rect.width().min(available_rect.height()) / 2.0;
-                let circle = epaint::Shape::Circle(epaint::CircleShape {
-                    center: available_rect.center(),
+                let radius = rect.width().min(rect.height()) / 2.0;
+                if self.is_static(time) {
+                    painter.add(epaint::CircleShape {
+                        center: rect.center(),
+                        radius,
+                        fill: self.base_color,
+                        stroke: outline,
+                    });
+                    return;
+                }
+                let square_rect = Rect::from_center_size(rect.center(), Vec2::splat(2.0 * radius));
+                let stops = self.gradient_stops(self.orientation.along_x(square_rect), time);
+                let mut mesh = Mesh::default();
+                add_gradient_circle(
+                    &mut mesh,
+                    rect.center(),
                     radius,
-                    fill: self.base_color,
-                    stroke: Stroke::default(),
-                });
-                painter.add(circle);
-                ui.allocate_rect(available_rect, Sense::hover())
+                    self.orientation,
+                    &stops,
+                    1.0 / painter.pixels_per_point(),
+                );
+                painter.add(epaint::Shape::Mesh(Arc::new(mesh)));
+                if time.is_some() {
+                    painter.ctx().request_repaint();
+                }
+                if self.outline.is_some() {
+                    painter.add(epaint::CircleShape::stroke(rect.center(), radius, outline));
+                }
+            }
+            SkeletonShapeType::Pill => {
+                let rounding = pill_rounding(rect);
+                painter.add(epaint::RectShape::new(
+                    rect,
+                    rounding,
+                    self.base_color,
+                    outline,
+                ));
+            }
+            SkeletonShapeType::Spinner {
+                arc_length,
+                thickness,
+            } => {
+                let points = self.spinner_points(rect, arc_length, thickness, time.unwrap_or(0.0));
+                painter.add(epaint::Shape::line(
+                    points,
+                    Stroke::new(thickness, self.base_color),
+                ));
+                if time.is_some() {
+                    painter.ctx().request_repaint();
+                }
+            }
+        }
+    }
+
+    /// Paint the outline of the (resolved) `shape_type` in `rect`, see [`Self::wireframe`].
+    fn paint_wireframe(&self, painter: &Painter, rect: Rect, shape_type: &SkeletonShapeType) {
+        let stroke = self.wireframe_stroke();
+        let side = rect.width().min(rect.height());
+        match shape_type {
+            SkeletonShapeType::Square { rounding } => {
+                let square_rect = Rect::from_center_size(rect.center(), Vec2::splat(side));
+                painter.add(epaint::RectShape::stroke(square_rect, *rounding, stroke));
+            }
+            SkeletonShapeType::Circle => {
+                painter.add(epaint::CircleShape::stroke(
+                    rect.center(),
+                    side / 2.0,
+                    stroke,
+                ));
+            }
+            SkeletonShapeType::Pill => {
+                painter.add(epaint::RectShape::stroke(rect, pill_rounding(rect), stroke));
             }
+            _ => {
+                painter.add(epaint::RectShape::stroke(rect, self.rounding, stroke));
+            }
+        }
+    }
+}
+
+impl Widget for Skeleton {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let (rect, response) = if let Some(size) = self.size {
+            ui.allocate_exact_size(size, Sense::hover())
+        } else if let Some(texture_size) = self.texture_size {
+            let size = Self::fit_texture_size(texture_size, ui.available_size_before_wrap());
+            ui.allocate_exact_size(size, Sense::hover())
+        } else {
+            let rect = ui.available_rect_before_wrap();
+            (rect, ui.allocate_rect(rect, Sense::hover()))
+        };
+        let mut painter = ui.painter().clone();
+        let time = animation_time(ui);
+        if let Some(duration) = self.fade_in.filter(|_| time.is_some()) {
+            painter.multiply_opacity(Self::fade_in_opacity(ui, response.id, duration));
         }
+        let time = time.filter(|_| !self.timed_out(ui, response.id));
+        let skeleton = self.delayed_from(ui, response.id);
+        skeleton.paint(&painter, rect, time);
+        skeleton.widget_info(&response);
+
+        response
     }
 }
 
+/// The state of some data that is loaded in the background, e.g. over the network.
+///
+/// Show it with [`Ui::loadable`], which paints a skeleton placeholder while it is loading.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Loadable<T, E = String> {
+    /// Still loading.
+    Loading,
+
+    /// Done loading.
+    Ready(T),
+
+    /// Loading failed.
+    Error(E),
+}
+
+impl<T, E> Loadable<T, E> {
+    /// Is this [`Self::Loading`]?
+    pub fn is_loading(&self) -> bool {
+        matches!(self, Self::Loading)
+    }
+
+    /// The data, if it is ready.
+    pub fn ready(&self) -> Option<&T> {
+        match self {
+            Self::Ready(value) => Some(value),
+            Self::Loading | Self::Error(_) => None,
+        }
+    }
+}
+
+impl<T, E> From<Result<T, E>> for Loadable<T, E> {
+    fn from(result: Result<T, E>) -> Self {
+        match result {
+            Ok(value) => Self::Ready(value),
+            Err(err) => Self::Error(err),
+        }
+    }
+}
+
+/// How [`HasSkeleton::fill_ui`] places its lines in the area.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FillMode {
+    /// Stack the lines from the top, leaving any leftover space at the bottom.
+    #[default]
+    Top,
+
+    /// Spread the leftover space evenly over the gaps,
+    /// so the last line ends at the bottom of the area.
+    Justified,
+}
+
+/// The lines of a skeleton text placeholder filling `rect`.
+///
+/// As many lines as fit completely are returned, placed according to `fill_mode`.
+pub fn skeleton_line_rects(
+    rect: Rect,
+    line_height: f32,
+    gap: f32,
+    fill_mode: FillMode,
+) -> Vec<Rect> {
+    if rect.height() < line_height {
+        return vec![];
+    }
+    let count = ((rect.height() + gap) / (line_height + gap)).floor() as usize;
+    let gap = if fill_mode == FillMode::Justified && count > 1 {
+        (rect.height() - count as f32 * line_height) / (count - 1) as f32
+    } else {
+        gap
+    };
+    (0..count)
+        .map(|i| {
+            Rect::from_min_size(
+                Pos2::new(rect.left(), rect.top() + i as f32 * (line_height + gap)),
+                crate::vec2(rect.width(), line_height),
+            )
+        })
+        .collect()
+}
+
 /// Trait for components that can display a skeleton placeholder while waiting for data.
 pub trait HasSkeleton {
+    /// How [`Self::fill_ui`] places its lines.
+    fn fill_mode(&self) -> FillMode {
+        FillMode::Top
+    }
+
     /// Fills the provided area with default skeleton placeholders.
     ///
+    /// This only paints; allocating the area is up to the caller,
+    /// e.g. with [`ExtFrame::end`] around it.
+    ///
     /// 默认实现：在给定矩形内绘制带 gap 的多行矩形 skeleton，占位效果类似文本行占位。
     fn fill_ui(&self, ui: &mut Ui, rect: Rect) {
         let line_height = 16.0;
         let gap = 4.0;
-        let mut y = rect.top();
-        let mut shapes = Vec::new();
-        while y + line_height <= rect.bottom() {
-            let line_rect = Rect::from_min_size(Pos2::new(rect.left(), y), crate::egui::vec2(rect.width(), line_height));
-            let skeleton_line = epaint::RectShape::new(
-                line_rect,
-                2.0,
-                Color32::from_gray(220),
-                Stroke::NONE,
-                StrokeKind::Outside,
-            );
-            shapes.push(skeleton_line.into());
-            y += line_height + gap;
-        }
+        let shapes = skeleton_line_rects(rect, line_height, gap, self.fill_mode())
+            .into_iter()
+            .map(|line_rect| {
+                epaint::Shape::from(epaint::RectShape::new(
+                    line_rect,
+                    2.0,
+                    Color32::from_gray(220),
+                    Stroke::NONE,
+                ))
+            })
+            .collect();
         ui.painter().add(epaint::Shape::Vec(shapes));
-        ui.allocate_rect(rect, Sense::hover());
     }
 }
 
-/// 为 ExtFrame 实现 HasSkeleton trait（默认实现）
-use crate::egui::containers::frame_ext::ExtFrame;
+/// 为 [`ExtFrame`] 实现 [`HasSkeleton`] trait（默认实现）
 impl HasSkeleton for ExtFrame {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shapes(add_contents: impl Fn(&mut Ui)) -> Vec<epaint::Shape> {
+        let ctx = crate::Context::default();
+        let output = ctx.run(Default::default(), |ctx| {
+            crate::CentralPanel::default().show(ctx, |ui| add_contents(ui));
+        });
+        output
+            .shapes
+            .into_iter()
+            .map(|clipped| clipped.shape)
+            .collect()
+    }
+
+    fn count_meshes(add_contents: impl Fn(&mut Ui)) -> usize {
+        shapes(add_contents)
+            .iter()
+            .filter(|shape| matches!(shape, epaint::Shape::Mesh(_)))
+            .count()
+    }
+
+    fn rect_shapes(add_contents: impl Fn(&mut Ui)) -> Vec<epaint::RectShape> {
+        shapes(add_contents)
+            .into_iter()
+            .filter_map(|shape| match shape {
+                epaint::Shape::Rect(rect_shape) => Some(rect_shape),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn skeleton_list_is_a_single_mesh() {
+        let individual = count_meshes(|ui| {
+            for _ in 0..100 {
+                ui.add_sized(crate::vec2(200.0, 16.0), Skeleton::new());
+            }
+        });
+        let list = count_meshes(|ui| {
+            ui.add(SkeletonList::new(100));
+        });
+        assert_eq!(individual, 100);
+        assert_eq!(list, 1);
+    }
+
+    #[test]
+    fn pill_rounding_is_half_the_height() {
+        let rects = rect_shapes(|ui| {
+            ui.add_sized(
+                crate::vec2(80.0, 24.0),
+                Skeleton::new().shape_type(SkeletonShapeType::Pill),
+            );
+        });
+        let pill = rects.last().unwrap();
+        assert_eq!(pill.rect.height(), 24.0);
+        assert_eq!(pill.rounding, Rounding::same(12));
+
+        let rects = rect_shapes(|ui| {
+            ui.add_sized(
+                crate::vec2(40.0, 40.0),
+                Skeleton::new().shape_type(SkeletonShapeType::Square {
+                    rounding: Rounding::same(6),
+                }),
+            );
+        });
+        assert_eq!(rects.last().unwrap().rounding, Rounding::same(6));
+    }
+
+    #[test]
+    fn shimmer_is_clipped_to_visible_part() {
+        let ctx = crate::Context::default();
+        let mut expected = Rect::NOTHING;
+        let output = ctx.run(Default::default(), |ctx| {
+            crate::CentralPanel::default().show(ctx, |ui| {
+                let rect = Rect::from_min_size(ui.cursor().min, crate::vec2(200.0, 40.0));
+                let clip_rect = Rect::from_min_max(rect.min, rect.center_bottom());
+                expected = clip_rect;
+                ui.set_clip_rect(clip_rect);
+                ui.put(rect, Skeleton::new());
+            });
+        });
+
+        let mesh_bounds = output
+            .shapes
+            .iter()
+            .find_map(|clipped| match &clipped.shape {
+                epaint::Shape::Mesh(mesh) => Some(mesh.calc_bounds()),
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(mesh_bounds, expected);
+    }
+
+    #[test]
+    fn sized_skeleton_sits_inline() {
+        let size = crate::vec2(60.0, 12.0);
+        let ctx = crate::Context::default();
+        let _ = ctx.run(Default::default(), |ctx| {
+            crate::CentralPanel::default().show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Name:");
+                    let skeleton = ui.add(Skeleton::new().with_size(size));
+                    let after = ui.label("…");
+                    assert_eq!(skeleton.rect.size(), size);
+                    assert!(after.rect.left() >= skeleton.rect.right());
+                });
+            });
+        });
+    }
+
+    #[test]
+    fn outline_is_painted_when_set() {
+        let outline = Stroke::new(1.0, Color32::from_gray(180));
+        let size = crate::vec2(80.0, 24.0);
+
+        let rects = rect_shapes(|ui| {
+            ui.add_sized(size, Skeleton::new());
+        });
+        assert!(rects.iter().all(|rect| rect.stroke.is_empty()));
+
+        let rects = rect_shapes(|ui| {
+            ui.add_sized(size, Skeleton::new().outline(outline));
+        });
+        let outline_shape = rects.last().unwrap();
+        assert_eq!(outline_shape.stroke, outline);
+        assert_eq!(outline_shape.rect.size(), size);
+        assert_eq!(outline_shape.fill, Color32::TRANSPARENT);
+
+        for shape_type in [SkeletonShapeType::SQUARE, SkeletonShapeType::Pill] {
+            let rects = rect_shapes(|ui| {
+                ui.add_sized(
+                    size,
+                    Skeleton::new()
+                        .shape_type(shape_type.clone())
+                        .outline(outline),
+                );
+            });
+            assert_eq!(rects.last().unwrap().stroke, outline);
+        }
+
+        let circles: Vec<_> = shapes(|ui| {
+            ui.add_sized(
+                size,
+                Skeleton::new()
+                    .shape_type(SkeletonShapeType::Circle)
+                    .outline(outline),
+            );
+        })
+        .into_iter()
+        .filter_map(|shape| match shape {
+            epaint::Shape::Circle(circle) => Some(circle),
+            _ => None,
+        })
+        .collect();
+        assert_eq!(circles.len(), 1);
+        assert_eq!(circles[0].stroke, outline);
+    }
+
+    #[test]
+    fn justified_fill_spreads_leftover_space() {
+        let rect = Rect::from_min_size(Pos2::ZERO, crate::vec2(100.0, 90.0));
+        let (line_height, gap) = (16.0, 4.0);
+
+        let top = skeleton_line_rects(rect, line_height, gap, FillMode::Top);
+        let justified = skeleton_line_rects(rect, line_height, gap, FillMode::Justified);
+        assert_eq!(top.len(), 4);
+        assert_eq!(justified.len(), 4);
+
+        let leftover = rect.bottom() - top.last().unwrap().bottom();
+        assert_eq!(leftover, 14.0);
+
+        let extra_gaps: f32 = justified
+            .windows(2)
+            .map(|pair| pair[1].top() - pair[0].bottom() - gap)
+            .sum();
+        assert!((extra_gaps - leftover).abs() < 1e-4);
+        assert_eq!(justified.last().unwrap().bottom(), rect.bottom());
+        assert_eq!(justified[0], top[0]);
+    }
+
+    #[test]
+    fn highlight_is_blended_over_base() {
+        let skeleton = Skeleton {
+            base_color: Color32::from_gray(200),
+            highlight_color: Color32::WHITE,
+            ..Default::default()
+        };
+        let rect = Rect::from_min_size(Pos2::ZERO, crate::vec2(100.0, 20.0));
+        // Halfway through the animation the band is fully inside the rect:
+        let time = 0.5 * skeleton.animation_duration;
+        let peak_color = |skeleton: Skeleton| {
+            let mut mesh = Mesh::default();
+            skeleton.add_shimmer_rect(&mut mesh, rect, Some(time));
+            let brightest = mesh.vertices.iter().max_by_key(|vertex| vertex.color.r());
+            brightest.unwrap().color
+        };
+
+        assert_eq!(peak_color(skeleton.clone()), Color32::WHITE);
+        assert_eq!(
+            peak_color(skeleton.clone().highlight_alpha(0.5)),
+            Color32::from_gray(228)
+        );
+        assert_eq!(
+            peak_color(skeleton.highlight_alpha(0.0)),
+            Color32::from_gray(200)
+        );
+    }
+
+    #[test]
+    fn progress_fills_proportionally() {
+        let skeleton = Skeleton {
+            base_color: Color32::from_gray(200),
+            highlight_color: Color32::WHITE,
+            ..Default::default()
+        }
+        .progress(0.25);
+        let rect = Rect::from_min_size(Pos2::new(10.0, 0.0), crate::vec2(200.0, 20.0));
+        let filled_width = |clip_rect: Rect| {
+            let mut mesh = Mesh::default();
+            skeleton.add_visible_shimmer_rect(&mut mesh, rect, clip_rect, Some(0.0));
+            let xs = mesh
+                .vertices
+                .iter()
+                .filter(|vertex| vertex.color == Color32::WHITE)
+                .map(|vertex| vertex.pos.x);
+            let (min_x, max_x) = xs.fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), x| {
+                (min.min(x), max.max(x))
+            });
+            max_x - min_x
+        };
+
+        assert_eq!(filled_width(Rect::EVERYTHING), 0.25 * rect.width());
+
+        // Clipping doesn't move where the bar ends:
+        let clip_rect = Rect::from_min_max(Pos2::new(40.0, 0.0), Pos2::new(300.0, 20.0));
+        assert_eq!(filled_width(clip_rect), 0.25 * rect.width() - 30.0);
+    }
+
+    #[test]
+    fn multiple_shimmer_bands() {
+        let skeleton = Skeleton {
+            base_color: Color32::from_gray(200),
+            highlight_color: Color32::WHITE,
+            ..Default::default()
+        };
+        let rect = Rect::from_min_size(Pos2::ZERO, crate::vec2(100.0, 20.0));
+        // A quarter into the animation both bands are fully inside the rect:
+        let time = 0.25 * skeleton.animation_duration;
+        let count_peaks = |skeleton: Skeleton| {
+            let mut mesh = Mesh::default();
+            skeleton.add_shimmer_rect(&mut mesh, rect, Some(time));
+            mesh.vertices
+                .iter()
+                .filter(|vertex| vertex.pos.y == rect.top() && vertex.color == Color32::WHITE)
+                .count()
+        };
+
+        assert_eq!(count_peaks(skeleton.clone()), 1);
+        assert_eq!(count_peaks(skeleton.band_count(2)), 2);
+    }
+
+    #[test]
+    fn vertical_shimmer_sweeps_down() {
+        let skeleton = Skeleton {
+            base_color: Color32::from_gray(200),
+            highlight_color: Color32::WHITE,
+            ..Default::default()
+        }
+        .orientation(Orientation::Vertical);
+        let rect = Rect::from_min_size(Pos2::new(10.0, 20.0), crate::vec2(20.0, 200.0));
+        let peak = |time: f32| {
+            let mut mesh = Mesh::default();
+            skeleton.add_shimmer_rect(&mut mesh, rect, Some(time));
+            assert!(mesh.vertices.iter().all(|vertex| rect.contains(vertex.pos)));
+            // The band spans the full width of the rect:
+            let peaks: Vec<Pos2> = mesh
+                .vertices
+                .iter()
+                .filter(|vertex| vertex.color == Color32::WHITE)
+                .map(|vertex| vertex.pos)
+                .collect();
+            assert_eq!(peaks.len(), 2, "{peaks:?}");
+            assert_eq!(peaks[0].y, peaks[1].y);
+            peaks[0].y
+        };
+
+        let duration = skeleton.animation_duration;
+        let (first, second) = (peak(0.3 * duration), peak(0.6 * duration));
+        assert!(rect.top() < first && first < second && second < rect.bottom());
+    }
+
+    #[test]
+    fn staggered_rows_start_after_their_delay() {
+        let list = SkeletonList::new(3)
+            .skeleton(Skeleton::new().start_delay(0.5))
+            .stagger(1.0);
+        let highlighted_rows = |time: f64| {
+            let ctx = crate::Context::default();
+            let run = |time: f64| {
+                let input = crate::RawInput {
+                    time: Some(time),
+                    ..Default::default()
+                };
+                ctx.run(input, |ctx| {
+                    crate::CentralPanel::default().show(ctx, |ui| {
+                        ui.add(list.clone());
+                    });
+                })
+            };
+            run(0.0); // The delays count from the first appearance.
+            let output = run(time);
+            let mesh = output
+                .shapes
+                .iter()
+                .find_map(|clipped| match &clipped.shape {
+                    epaint::Shape::Mesh(mesh) => Some(mesh.clone()),
+                    _ => None,
+                })
+                .unwrap();
+            list.row_rects(mesh.calc_bounds())
+                .enumerate()
+                .filter(|(_, row_rect)| {
+                    mesh.vertices.iter().any(|vertex| {
+                        row_rect.contains(vertex.pos) && vertex.color != list.skeleton.base_color
+                    })
+                })
+                .map(|(row, _)| row)
+                .collect::<Vec<_>>()
+        };
+
+        // Times at which every started band is inside its row:
+        assert_eq!(highlighted_rows(0.2), [] as [usize; 0]);
+        assert_eq!(highlighted_rows(1.2), [0]);
+        assert_eq!(highlighted_rows(2.2), [0, 1]);
+        assert_eq!(highlighted_rows(3.2), [0, 1, 2]);
+    }
+
+    #[test]
+    fn start_delay_counts_from_the_first_appearance() {
+        let ctx = crate::Context::default();
+        let shimmers_at = |time: f64| {
+            let output = ctx.run(
+                crate::RawInput {
+                    time: Some(time),
+                    ..Default::default()
+                },
+                |ctx| {
+                    crate::CentralPanel::default().show(ctx, |ui| {
+                        ui.add(
+                            Skeleton::new()
+                                .with_size(Vec2::new(200.0, 20.0))
+                                .start_delay(0.5),
+                        );
+                    });
+                },
+            );
+            output.shapes.iter().any(|clipped| match &clipped.shape {
+                epaint::Shape::Mesh(mesh) => {
+                    let first = mesh.vertices[0].color;
+                    mesh.vertices.iter().any(|vertex| vertex.color != first)
+                }
+                _ => false,
+            })
+        };
+
+        // Long after the app started:
+        assert!(!shimmers_at(10.0));
+        assert!(!shimmers_at(10.3), "still waiting");
+        assert!(shimmers_at(10.9));
+    }
+
+    #[test]
+    fn spinner_rotates_with_time() {
+        let start_angle = |time: f64| {
+            let ctx = crate::Context::default();
+            let input = crate::RawInput {
+                time: Some(time),
+                ..Default::default()
+            };
+            let mut center = Pos2::ZERO;
+            let output = ctx.run(input, |ctx| {
+                crate::CentralPanel::default().show(ctx, |ui| {
+                    let spinner = Skeleton::new()
+                        .shape_type(SkeletonShapeType::SPINNER)
+                        .with_size(crate::vec2(24.0, 24.0));
+                    center = ui.add(spinner).rect.center();
+                });
+            });
+            assert!(ctx.has_requested_repaint());
+            let first_point = output
+                .shapes
+                .iter()
+                .find_map(|clipped| match &clipped.shape {
+                    epaint::Shape::Path(path) => path.points.first().copied(),
+                    _ => None,
+                })
+                .unwrap();
+            (first_point - center).angle()
+        };
+
+        // A quarter of the default 1.5 s per turn:
+        assert!(start_angle(0.0).abs() < 1e-3);
+        assert!((start_angle(0.375) - std::f32::consts::FRAC_PI_2).abs() < 1e-3);
+        assert!((start_angle(0.75).abs() - std::f32::consts::PI).abs() < 1e-3);
+    }
+
+    #[test]
+    fn skeleton_speed_scales_the_animation() {
+        let peak_x = |speed: f32, time: f64| {
+            let ctx = crate::Context::default();
+            ctx.options_mut(|options| options.skeleton_speed = speed);
+            let run = |time: f64| {
+                let input = crate::RawInput {
+                    time: Some(time),
+                    ..Default::default()
+                };
+                ctx.run(input, |ctx| {
+                    crate::CentralPanel::default().show(ctx, |ui| {
+                        ui.add_sized(crate::vec2(100.0, 20.0), Skeleton::new());
+                    });
+                })
+            };
+            run(0.0);
+            let output = run(time);
+            output
+                .shapes
+                .iter()
+                .find_map(|clipped| match &clipped.shape {
+                    epaint::Shape::Mesh(mesh) => mesh
+                        .vertices
+                        .iter()
+                        .max_by_key(|vertex| vertex.color.r())
+                        .map(|vertex| vertex.pos.x),
+                    _ => None,
+                })
+                .unwrap()
+        };
+
+        let full_speed = peak_x(1.0, 0.6) - peak_x(1.0, 0.3);
+        let half_speed = peak_x(0.5, 0.6) - peak_x(0.5, 0.3);
+        assert!(full_speed > 0.0);
+        assert!((half_speed - 0.5 * full_speed).abs() < 1e-3);
+
+        assert_eq!(peak_x(0.0, 0.3), peak_x(0.0, 0.9), "Frozen");
+    }
+
+    #[test]
+    fn loadable_shows_each_state() {
+        let show = |state: &Loadable<u32>| {
+            let mut ready_value = None;
+            let shapes = {
+                let ctx = crate::Context::default();
+                let output = ctx.run(Default::default(), |ctx| {
+                    crate::CentralPanel::default().show(ctx, |ui| {
+                        ready_value = ui.loadable(state, |ui, value| {
+                            ui.label(value.to_string());
+                            *value
+                        });
+                    });
+                });
+                output.shapes
+            };
+            let meshes = shapes
+                .iter()
+                .filter(|clipped| matches!(clipped.shape, epaint::Shape::Mesh(_)))
+                .count();
+            let texts: Vec<String> = shapes
+                .iter()
+                .filter_map(|clipped| match &clipped.shape {
+                    epaint::Shape::Text(text) => Some(text.galley.text().to_owned()),
+                    _ => None,
+                })
+                .collect();
+            (ready_value, meshes, texts)
+        };
+
+        let (value, meshes, texts) = show(&Loadable::Loading);
+        assert_eq!(value, None);
+        assert_eq!(meshes, 1, "A skeleton placeholder while loading");
+        assert!(texts.is_empty());
+
+        let (value, meshes, texts) = show(&Loadable::Ready(42));
+        assert_eq!(value, Some(42));
+        assert_eq!(meshes, 0);
+        assert_eq!(texts, ["42"]);
+
+        let (value, meshes, texts) = show(&Loadable::Error("Not found".to_owned()));
+        assert_eq!(value, None);
+        assert_eq!(meshes, 0);
+        assert_eq!(texts, ["Not found"]);
+    }
+
+    #[test]
+    fn highlight_contrast_is_the_same_in_light_and_dark_mode() {
+        let delta = |visuals: &crate::Visuals| {
+            let skeleton = Skeleton::from_visuals(visuals);
+            skeleton.highlight_color.luma() - skeleton.base_color.luma()
+        };
+        let dark = delta(&crate::Visuals::dark());
+        let light = delta(&crate::Visuals::light());
+
+        // The highlight moves toward the text: lighter in dark mode, darker in light mode.
+        assert!(dark > 0.0 && light < 0.0, "dark: {dark}, light: {light}");
+        for delta in [dark, light] {
+            let error = (delta.abs() - Skeleton::HIGHLIGHT_LUMA_DELTA).abs();
+            assert!(error < 2.0 / 255.0, "luma delta {delta}");
+        }
+    }
+
+    #[test]
+    fn paint_into_paints_without_a_ui() {
+        let ctx = crate::Context::default();
+        let rect = Rect::from_min_size(Pos2::ZERO, Vec2::new(100.0, 20.0));
+        let output = ctx.run(Default::default(), |ctx| {
+            let painter = ctx.layer_painter(crate::LayerId::background());
+            Skeleton::new().paint_into(&painter, rect, 0.5);
+            Skeleton::new()
+                .shape_type(SkeletonShapeType::Circle)
+                .paint_into(&painter, rect, 0.5);
+        });
+        let shapes: Vec<epaint::Shape> = output
+            .shapes
+            .into_iter()
+            .map(|clipped| clipped.shape)
+            .collect();
+        assert_eq!(shapes.len(), 2);
+
+        let epaint::Shape::Mesh(mesh) = &shapes[0] else {
+            panic!("expected a shimmer mesh, got {:?}", shapes[0]);
+        };
+        assert!(mesh.vertices.iter().all(|vertex| rect.contains(vertex.pos)));
+        let peak = Skeleton::new().peak_color();
+        assert!(
+            mesh.vertices.iter().any(|vertex| vertex.color == peak),
+            "the highlight band is visible halfway through the animation"
+        );
+        let epaint::Shape::Mesh(circle) = &shapes[1] else {
+            panic!("expected a circular shimmer mesh, got {:?}", shapes[1]);
+        };
+        assert!(circle
+            .vertices
+            .iter()
+            .all(|vertex| vertex.pos.distance(rect.center()) <= 10.501));
+    }
+
+    #[test]
+    fn auto_shape_follows_the_aspect_ratio() {
+        for (size, expected) in [
+            (Vec2::new(300.0, 16.0), SkeletonShapeType::Rectangle),
+            (Vec2::new(16.0, 300.0), SkeletonShapeType::Rectangle),
+            (Vec2::new(120.0, 100.0), SkeletonShapeType::SQUARE),
+            (Vec2::new(200.0, 200.0), SkeletonShapeType::SQUARE),
+            (Vec2::new(32.0, 32.0), SkeletonShapeType::Circle),
+            (Vec2::new(40.0, 36.0), SkeletonShapeType::Circle),
+            (Vec2::new(40.0, 20.0), SkeletonShapeType::Rectangle),
+        ] {
+            assert_eq!(SkeletonShapeType::Auto.resolve(size), expected, "{size:?}");
+        }
+        assert_eq!(
+            SkeletonShapeType::Pill.resolve(Vec2::splat(32.0)),
+            SkeletonShapeType::Pill
+        );
+
+        let ctx = crate::Context::default();
+        let output = ctx.run(Default::default(), |ctx| {
+            let painter = ctx.layer_painter(crate::LayerId::background());
+            let avatar = Rect::from_min_size(Pos2::ZERO, Vec2::splat(32.0));
+            Skeleton::new()
+                .shape_type(SkeletonShapeType::Auto)
+                .paint_into(&painter, avatar, 0.0);
+        });
+        let epaint::Shape::Mesh(mesh) = &output.shapes[0].shape else {
+            panic!("expected a shimmer mesh");
+        };
+        assert!(mesh
+            .vertices
+            .iter()
+            .all(|vertex| vertex.pos.distance(Pos2::new(16.0, 16.0)) <= 16.501));
+    }
+
+    #[test]
+    fn seeded_row_widths_vary_reproducibly() {
+        let widths = |list: &SkeletonList| -> Vec<f32> {
+            let rect = Rect::from_min_size(Pos2::ZERO, Vec2::new(200.0, 200.0));
+            list.row_rects(rect).map(|row| row.width()).collect()
+        };
+        assert_eq!(widths(&SkeletonList::new(3)), [200.0; 3]);
+
+        let a = widths(&SkeletonList::new(5).seed(1));
+        let b = widths(&SkeletonList::new(5).seed(2));
+        assert_eq!(a, widths(&SkeletonList::new(5).seed(1)), "reproducible");
+        assert_ne!(a, b);
+        for widths in [a, b] {
+            let (last, rows) = widths.split_last().unwrap();
+            assert!(
+                rows.iter().all(|&w| (170.0..=200.0).contains(&w)),
+                "{widths:?}"
+            );
+            assert!((100.0..=150.0).contains(last), "{widths:?}");
+        }
+    }
+
+    #[test]
+    fn fades_in_on_first_appearance() {
+        let ctx = crate::Context::default();
+        let alpha_at = |time: f64| {
+            let output = ctx.run(
+                crate::RawInput {
+                    time: Some(time),
+                    ..Default::default()
+                },
+                |ctx| {
+                    crate::CentralPanel::default().show(ctx, |ui| {
+                        ui.add(
+                            Skeleton::new()
+                                .shape_type(SkeletonShapeType::Circle)
+                                .with_size(Vec2::splat(32.0))
+                                .fade_in(0.5),
+                        );
+                    });
+                },
+            );
+            output
+                .shapes
+                .iter()
+                .find_map(|clipped| match &clipped.shape {
+                    epaint::Shape::Mesh(mesh) => mesh.vertices.iter().map(|v| v.color.a()).max(),
+                    _ => None,
+                })
+                .unwrap_or(0) // Invisible shapes aren't painted at all
+        };
+
+        assert!(alpha_at(10.0) < 10, "transparent on the first frame");
+        let halfway = alpha_at(10.25);
+        assert!(50 < halfway && halfway < 200, "{halfway}");
+        assert_eq!(alpha_at(10.6), 255);
+        assert_eq!(alpha_at(20.0), 255, "stays visible");
+    }
+
+    #[test]
+    fn stops_animating_after_the_max_animation_time() {
+        let spinner = Skeleton::new()
+            .shape_type(SkeletonShapeType::SPINNER)
+            .with_size(Vec2::splat(24.0))
+            .max_animation_time(5.0);
+        let shimmer = Skeleton::new()
+            .with_size(Vec2::new(200.0, 20.0))
+            .max_animation_time(5.0);
+        let list = SkeletonList::new(3).skeleton(Skeleton::new().max_animation_time(5.0));
+        let widgets: [&dyn Fn(&mut Ui) -> Response; 3] = [
+            &|ui| ui.add(spinner.clone()),
+            &|ui| ui.add(shimmer.clone()),
+            &|ui| ui.add(list.clone()),
+        ];
+
+        for add_widget in widgets {
+            let ctx = crate::Context::default();
+            let repaints_at = |time: f64| {
+                let mut id = crate::Id::NULL;
+                let output = ctx.run(
+                    crate::RawInput {
+                        time: Some(time),
+                        ..Default::default()
+                    },
+                    |ctx| {
+                        crate::CentralPanel::default().show(ctx, |ui| {
+                            id = add_widget(ui).id;
+                        });
+                    },
+                );
+                let repaint_delay = output.viewport_output[&crate::ViewportId::ROOT].repaint_delay;
+                (repaint_delay == std::time::Duration::ZERO, id)
+            };
+
+            assert!(repaints_at(10.0).0);
+            assert!(repaints_at(14.0).0);
+            // The frame after the last animated one is still repainted, but not the ones after that:
+            repaints_at(15.5);
+            assert!(!repaints_at(16.0).0, "stands still after the timeout");
+            let (repaint, id) = repaints_at(30.0);
+            assert!(!repaint);
+
+            Skeleton::reset_animation(&ctx, id);
+            assert!(repaints_at(31.0).0, "animates again after a reset");
+        }
+    }
+
+    #[test]
+    fn matching_takes_the_look_of_the_frame() {
+        let frame = ExtFrame::new()
+            .fill(Color32::from_gray(40))
+            .rounding(Rounding::same(12));
+        let skeleton = Skeleton::matching(&frame);
+        assert_eq!(skeleton.rounding, frame.rounding);
+        assert_eq!(skeleton.base_color, frame.fill);
+        assert!(skeleton.highlight_color.luma() > skeleton.base_color.luma());
+
+        // The shimmer stays within the rounded corners:
+        let rect = Rect::from_min_size(Pos2::new(10.0, 10.0), Vec2::new(200.0, 60.0));
+        let mut mesh = Mesh::default();
+        skeleton.add_visible_shimmer_rect(&mut mesh, rect, Rect::EVERYTHING, Some(0.3));
+        let corner = Rounding::same(12).nw as f32;
+        assert!(mesh.vertices.iter().all(|v| {
+            let inner = rect.shrink(corner);
+            let nearest = v.pos.clamp(inner.min, inner.max);
+            v.pos.distance(nearest) <= corner + 0.01
+        }));
+    }
+
+    #[test]
+    fn screenshot_mode_freezes_the_animations() {
+        let ctx = crate::Context::default();
+        ctx.options_mut(|options| options.screenshot_mode = true);
+        let run = |time: f64| {
+            let output = ctx.run(
+                crate::RawInput {
+                    time: Some(time),
+                    ..Default::default()
+                },
+                |ctx| {
+                    crate::CentralPanel::default().show(ctx, |ui| {
+                        ui.add(Skeleton::new().with_size(Vec2::new(200.0, 20.0)));
+                        ui.add(
+                            Skeleton::new()
+                                .shape_type(SkeletonShapeType::SPINNER)
+                                .with_size(Vec2::splat(24.0))
+                                .fade_in(1.0),
+                        );
+                        ui.add(SkeletonList::new(3));
+                    });
+                },
+            );
+            let repaint_delay = output.viewport_output[&crate::ViewportId::ROOT].repaint_delay;
+            (output.shapes, repaint_delay)
+        };
+
+        let (first, _) = run(1.0);
+        let (second, _) = run(1.3);
+        let (third, repaint_delay) = run(2.7);
+        assert_eq!(first, second);
+        assert_eq!(first, third);
+        assert_eq!(repaint_delay, std::time::Duration::MAX);
+    }
+
+    #[test]
+    fn circle_shimmer_stays_inside_the_circle() {
+        let ctx = crate::Context::default();
+        let rect = Rect::from_min_size(Pos2::ZERO, Vec2::new(200.0, 100.0));
+        let radius = 50.0;
+        for orientation in [Orientation::Horizontal, Orientation::Vertical] {
+            let skeleton = Skeleton::new()
+                .shape_type(SkeletonShapeType::Circle)
+                .orientation(orientation);
+            let output = ctx.run(Default::default(), |ctx| {
+                let painter = ctx.layer_painter(crate::LayerId::background());
+                skeleton.paint_into(&painter, rect, 0.5);
+            });
+            assert_eq!(output.shapes.len(), 1, "no square mesh behind the circle");
+            let epaint::Shape::Mesh(mesh) = &output.shapes[0].shape else {
+                panic!("expected a shimmer mesh");
+            };
+
+            // Opaque inside the circle, fading out over a pixel at its edge:
+            let feather = 1.0 / ctx.pixels_per_point();
+            let tolerance = 1e-3;
+            for vertex in &mesh.vertices {
+                let distance = vertex.pos.distance(rect.center());
+                if vertex.color == Color32::TRANSPARENT {
+                    assert!(distance <= radius + 0.5 * feather + tolerance, "{vertex:?}");
+                } else {
+                    assert!(distance <= radius - 0.5 * feather + tolerance, "{vertex:?}");
+                }
+            }
+            assert!(
+                mesh.vertices
+                    .iter()
+                    .any(|vertex| vertex.color == skeleton.peak_color()),
+                "the highlight band sweeps through the circle"
+            );
+        }
+    }
+
+    #[test]
+    fn sweep_pause_keeps_the_band_off_screen() {
+        let skeleton = Skeleton::new().sweep_pause(1.0);
+        let rect = Rect::from_min_size(Pos2::ZERO, Vec2::new(200.0, 20.0));
+        let duration = skeleton.animation_duration;
+        let highlighted = |time: f32| {
+            skeleton
+                .shimmer_stops(rect, Some(time))
+                .iter()
+                .any(|&(_, color)| color != skeleton.base_color)
+        };
+
+        for cycle in 0..3 {
+            let cycle_start = cycle as f32 * (duration + 1.0);
+            assert!(highlighted(cycle_start + 0.5 * duration), "sweeping");
+            for pause_time in [0.05, 0.5, 0.95] {
+                assert!(
+                    !highlighted(cycle_start + duration + pause_time),
+                    "pausing {pause_time}s into cycle {cycle}"
+                );
+            }
+        }
+
+        // Without a pause, the next sweep starts right away:
+        let skeleton = Skeleton::new();
+        assert!(skeleton
+            .shimmer_stops(rect, Some(1.5 * duration))
+            .iter()
+            .any(|&(_, color)| color != skeleton.base_color));
+    }
+
+    #[test]
+    fn shimmer_band_eases_in_and_out() {
+        let skeleton = Skeleton {
+            base_color: Color32::from_gray(100),
+            highlight_color: Color32::from_gray(200),
+            ..Default::default()
+        };
+        let rect = Rect::from_min_size(Pos2::ZERO, Vec2::new(100.0, 20.0));
+        // Halfway through the animation the band is fully inside the rect:
+        let stops = skeleton.shimmer_stops(rect, Some(0.5 * skeleton.animation_duration));
+        let band: Vec<(f32, u8)> = stops[1..stops.len() - 1]
+            .iter()
+            .map(|&(x, color)| (x, color.r()))
+            .collect();
+        assert_eq!(band.len(), SHIMMER_BAND_STEPS + 1);
+
+        // Soft edges: no jumps from the base color, and the peak in the middle:
+        assert_eq!(band[0].1, 100);
+        assert_eq!(band[SHIMMER_BAND_STEPS / 2].1, 200);
+        assert_eq!(band[SHIMMER_BAND_STEPS].1, 100);
+        let band_width = band[SHIMMER_BAND_STEPS].0 - band[0].0;
+        assert!((band_width - 20.0).abs() < 1e-3);
+
+        // Eased in between: slow at the edges and the peak, fastest halfway up.
+        assert_eq!(band[1].1, 115); // 100 + 100 * sin_in_out(0.25)
+        assert_eq!(band[2].1, 150);
+        assert_eq!(band[3].1, 185);
+        for i in 0..SHIMMER_BAND_STEPS / 2 {
+            assert_eq!(band[i].1, band[SHIMMER_BAND_STEPS - i].1, "symmetric");
+        }
+    }
+
+    #[test]
+    fn zero_animation_duration_gives_no_nan() {
+        assert_eq!(
+            Skeleton::new().animation_duration(0.0).animation_duration,
+            Skeleton::MIN_ANIMATION_DURATION
+        );
+
+        // Also when the field is set directly:
+        let skeleton = Skeleton {
+            animation_duration: 0.0,
+            ..Default::default()
+        };
+        let rect = Rect::from_min_size(Pos2::ZERO, Vec2::new(100.0, 20.0));
+        for time in [0.0, 0.01, 1.0, 123.456] {
+            let stops = skeleton.shimmer_stops(rect, Some(time));
+            assert!(
+                stops.iter().all(|(x, _)| x.is_finite()),
+                "{stops:?} at {time}"
+            );
+            let points = skeleton.spinner_points(rect, 1.0, 2.0, time);
+            assert!(points.iter().all(|p| p.x.is_finite() && p.y.is_finite()));
+        }
+    }
+
+    #[test]
+    fn accent_tints_the_highlight() {
+        let blue = Color32::from_rgb(0, 90, 255);
+        let rect = Rect::from_min_size(Pos2::ZERO, Vec2::new(100.0, 20.0));
+        let mid_color = |skeleton: &Skeleton| {
+            // Halfway through the animation the middle of the band is in the middle of the rect:
+            let mut mesh = Mesh::default();
+            skeleton.add_shimmer_rect(&mut mesh, rect, Some(0.5 * skeleton.animation_duration));
+            let mid = mesh
+                .vertices
+                .iter()
+                .min_by_key(|vertex| (vertex.pos.x - rect.center().x).abs() as i32);
+            mid.unwrap().color
+        };
+
+        let gray = mid_color(&Skeleton::new());
+        assert_eq!(gray, Skeleton::default().highlight_color);
+        let tinted = mid_color(&Skeleton::new().accent(blue));
+        assert!(tinted.b() > gray.b(), "{tinted:?}");
+        assert!(tinted.r() < gray.r(), "{tinted:?}");
+        assert_eq!(tinted, gray.lerp(blue, Skeleton::ACCENT_TINT));
+
+        // The rest of the placeholder keeps the base color:
+        let mut mesh = Mesh::default();
+        Skeleton::new().accent(blue).add_shimmer_rect(
+            &mut mesh,
+            rect,
+            Some(0.5 * Skeleton::default().animation_duration),
+        );
+        assert_eq!(mesh.vertices[0].color, Skeleton::default().base_color);
+    }
+
+    #[test]
+    fn texture_sized_skeleton_fits_the_available_space() {
+        let ctx = crate::Context::default();
+        let _ = ctx.run(Default::default(), |ctx| {
+            crate::CentralPanel::default().show(ctx, |ui| {
+                let small = crate::vec2(64.0, 32.0);
+                let response = ui.add(Skeleton::new().for_texture_size(small));
+                assert_eq!(response.rect.size(), small, "not scaled up");
+
+                ui.allocate_ui(crate::vec2(100.0, 300.0), |ui| {
+                    let response = ui.add(Skeleton::new().for_texture_size([400.0, 200.0]));
+                    assert_eq!(response.rect.size(), crate::vec2(100.0, 50.0));
+                });
+                ui.allocate_ui(crate::vec2(300.0, 40.0), |ui| {
+                    let response = ui.add(Skeleton::new().for_texture_size([400.0, 200.0]));
+                    assert_eq!(response.rect.size(), crate::vec2(80.0, 40.0));
+                });
+            });
+        });
+    }
+
+    #[test]
+    fn static_placeholder_is_a_single_shape() {
+        let ctx = crate::Context::default();
+        let shapes = |skeleton: Skeleton| {
+            let output = ctx.run(Default::default(), |ctx| {
+                crate::CentralPanel::default().show(ctx, |ui| {
+                    ui.add(skeleton.clone().with_size(Vec2::new(200.0, 20.0)));
+                });
+            });
+            // Skip the background of the panel:
+            output.shapes[1..].to_vec()
+        };
+
+        ctx.options_mut(|options| options.screenshot_mode = true);
+        let frozen = shapes(Skeleton::new().rounding(4));
+        assert_eq!(frozen.len(), 1);
+        assert!(matches!(
+            &frozen[0].shape,
+            epaint::Shape::Rect(rect) if rect.fill == Skeleton::default().base_color
+        ));
+        let circle = shapes(Skeleton::new().shape_type(SkeletonShapeType::Circle));
+        assert_eq!(circle.len(), 1);
+        assert!(matches!(circle[0].shape, epaint::Shape::Circle(_)));
+
+        // A progress bar is still painted in full:
+        let progress = shapes(Skeleton::new().progress(0.5));
+        assert!(matches!(progress[0].shape, epaint::Shape::Mesh(_)));
+
+        ctx.options_mut(|options| options.screenshot_mode = false);
+        assert!(matches!(
+            shapes(Skeleton::new())[0].shape,
+            epaint::Shape::Mesh(_)
+        ));
+    }
+
+    #[test]
+    fn highlight_is_derived_from_the_base_color() {
+        let base = Color32::from_rgb(60, 90, 160);
+        let skeleton = Skeleton::new().with_base_color(base);
+        assert_eq!(skeleton.base_color, base);
+        let highlight = skeleton.highlight_color;
+        assert!(highlight.luma() > base.luma(), "{highlight:?}");
+        let saturation = |color: Color32| {
+            let [r, g, b, _] = color.to_array();
+            r.max(g).max(b) - r.min(g).min(b)
+        };
+        assert!(saturation(highlight) < saturation(base), "{highlight:?}");
+
+        // An explicit highlight is kept:
+        let red = Color32::from_rgb(200, 40, 40);
+        let skeleton = Skeleton::new().highlight_color(red).with_base_color(base);
+        assert_eq!(skeleton.highlight_color, red);
+    }
+
+    #[test]
+    fn shimmer_phase_follows_the_absolute_time() {
+        let rect = Rect::from_min_size(Pos2::ZERO, Vec2::new(200.0, 20.0));
+        let shapes_at = |ctx: &crate::Context, time: f64| {
+            let output = ctx.run(
+                crate::RawInput {
+                    time: Some(time),
+                    ..Default::default()
+                },
+                |ctx| {
+                    crate::CentralPanel::default().show(ctx, |ui| {
+                        ui.add(Skeleton::new().with_size(rect.size()));
+                    });
+                },
+            );
+            output.shapes
+        };
+        let brightest_x = |mesh: &Mesh| {
+            mesh.vertices
+                .iter()
+                .max_by_key(|vertex| vertex.color.r())
+                .unwrap()
+                .pos
+                .x
+        };
+
+        // Irregular frame times, with long gaps like after coalesced repaints:
+        let ctx = crate::Context::default();
+        for time in [0.0, 0.016, 0.05, 0.6, 0.61, 1.93, 2.2, 5.37] {
+            let shapes = shapes_at(&ctx, time);
+            let epaint::Shape::Mesh(painted) = &shapes[1].shape else {
+                panic!("expected a shimmer mesh");
+            };
+            let mut expected = Mesh::default();
+            Skeleton::new().add_shimmer_rect(&mut expected, rect, Some(time as f32));
+            let offset = painted.vertices[0].pos.x - expected.vertices[0].pos.x;
+            assert_eq!(
+                brightest_x(painted),
+                brightest_x(&expected) + offset,
+                "at {time}"
+            );
+        }
+
+        // A fresh context jumping straight from the first to the last time paints the same:
+        let fresh = crate::Context::default();
+        shapes_at(&fresh, 0.0);
+        assert_eq!(shapes_at(&fresh, 5.37), shapes_at(&ctx, 5.37));
+    }
+
+    #[test]
+    fn wireframe_paints_only_strokes() {
+        let ctx = crate::Context::default();
+        let shape_types = [
+            SkeletonShapeType::Rectangle,
+            SkeletonShapeType::Square { rounding: 4.into() },
+            SkeletonShapeType::Circle,
+            SkeletonShapeType::Pill,
+        ];
+        for shape_type in shape_types {
+            let output = ctx.run(Default::default(), |ctx| {
+                crate::CentralPanel::default().show(ctx, |ui| {
+                    ui.add(
+                        Skeleton::new()
+                            .shape_type(shape_type.clone())
+                            .wireframe(true)
+                            .with_size(Vec2::new(80.0, 40.0)),
+                    );
+                    ui.add(SkeletonList::new(3).skeleton(Skeleton::new().wireframe(true)));
+                });
+            });
+            // Skip the background of the panel:
+            let shapes = &output.shapes[1..];
+            assert_eq!(shapes.len(), 4, "{shape_type:?}");
+            for clipped in shapes {
+                let (fill, stroke) = match &clipped.shape {
+                    epaint::Shape::Rect(rect) => (rect.fill, rect.stroke),
+                    epaint::Shape::Circle(circle) => (circle.fill, circle.stroke),
+                    shape => panic!("{shape_type:?} painted {shape:?}"),
+                };
+                assert_eq!(fill, Color32::TRANSPARENT);
+                assert_eq!(
+                    stroke,
+                    Stroke::new(Skeleton::WIREFRAME_WIDTH, Skeleton::default().base_color)
+                );
+            }
+        }
+    }
+}
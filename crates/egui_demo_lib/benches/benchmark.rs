@@ -84,6 +84,29 @@ pub fn criterion_benchmark(c: &mut Criterion) {
         // Don't call `end_pass` to not have to drain the huge paint list
     }
 
+    {
+        // 100 skeleton rows, as individual widgets vs. one merged mesh:
+        let ctx = egui::Context::default();
+        let mut skeleton_bench = |name: &str, add_rows: fn(&mut egui::Ui)| {
+            c.bench_function(name, |b| {
+                b.iter(|| {
+                    let full_output = ctx.run(RawInput::default(), |ctx| {
+                        egui::CentralPanel::default().show(ctx, add_rows);
+                    });
+                    ctx.tessellate(full_output.shapes, full_output.pixels_per_point)
+                });
+            });
+        };
+        skeleton_bench("skeleton_100_widgets", |ui| {
+            for _ in 0..100 {
+                ui.add_sized(egui::vec2(200.0, 16.0), egui::Skeleton::new());
+            }
+        });
+        skeleton_bench("skeleton_list_100_rows", |ui| {
+            ui.add(egui::SkeletonList::new(100));
+        });
+    }
+
     {
         let pixels_per_point = 1.0;
         let max_texture_side = 8 * 1024;
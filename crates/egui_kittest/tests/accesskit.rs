@@ -151,6 +151,22 @@ fn window_children() {
     assert_button_exists(&output, "Hide", window_id);
 }
 
+#[test]
+fn skeleton_node_is_busy() {
+    let output = accesskit_output_single_egui_frame(|ctx| {
+        CentralPanel::default().show(ctx, |ui| ui.add(egui::Skeleton::new()));
+    });
+
+    let (_, skeleton) = output
+        .nodes
+        .iter()
+        .find(|(_, node)| node.role() == Role::ProgressIndicator)
+        .expect("Skeleton should exist in the accesskit output");
+
+    assert_eq!(skeleton.label(), Some("Loading…"));
+    assert!(skeleton.is_busy());
+}
+
 fn accesskit_output_single_egui_frame(run_ui: impl FnMut(&Context)) -> TreeUpdate {
     let ctx = Context::default();
     // Disable animations, so we do not need to wait for animations to end to see the result.
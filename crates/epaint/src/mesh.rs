@@ -1,4 +1,4 @@
-use crate::{emath, Color32, TextureId, WHITE_UV};
+use crate::{emath, Color32, Rounding, TextureId, WHITE_UV};
 use emath::{Pos2, Rect, Rot2, TSTransform, Vec2};
 
 /// The 2D vertex type.
@@ -215,6 +215,51 @@ impl Mesh {
         self.add_rect_with_uv(rect, [WHITE_UV, WHITE_UV].into(), color);
     }
 
+    /// A rectangle with rounded corners, filled with a linear gradient.
+    ///
+    /// The gradient goes from `from` to `to` along the direction given by `angle` (in radians),
+    /// where `0.0` is left-to-right and `TAU / 4.0` is top-to-bottom.
+    /// The colors reach `from` and `to` at the edges of `rect`, measured along that direction.
+    ///
+    /// The geometry follows the rounded corners, so nothing is painted outside them.
+    /// Note that the edges are not anti-aliased.
+    pub fn rounded_gradient(
+        rect: Rect,
+        rounding: impl Into<Rounding>,
+        from: Color32,
+        to: Color32,
+        angle: f32,
+    ) -> Self {
+        let mut path = vec![];
+        crate::tessellator::path::rounded_rectangle(&mut path, rect, rounding.into());
+
+        let center = rect.center();
+        let dir = Vec2::angled(angle);
+        let half_extent = 0.5 * (rect.width() * dir.x.abs() + rect.height() * dir.y.abs());
+        let color_at = |pos: Pos2| {
+            let t = if half_extent > 0.0 {
+                0.5 + 0.5 * (pos - center).dot(dir) / half_extent
+            } else {
+                0.5
+            };
+            from.lerp_to_gamma(to, t.clamp(0.0, 1.0))
+        };
+
+        // The rounded rectangle is convex, so we can use a triangle fan around the center:
+        let mut mesh = Self::default();
+        mesh.reserve_vertices(path.len() + 1);
+        mesh.reserve_triangles(path.len());
+        mesh.colored_vertex(center, color_at(center));
+        for &pos in &path {
+            mesh.colored_vertex(pos, color_at(pos));
+        }
+        let n = path.len() as u32;
+        for i in 0..n {
+            mesh.add_triangle(0, 1 + i, 1 + (i + 1) % n);
+        }
+        mesh
+    }
+
     /// This is for platforms that only support 16-bit index buffers.
     ///
     /// Splits this mesh into many smaller meshes (if needed)
@@ -331,3 +376,62 @@ impl Mesh16 {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use emath::{pos2, vec2};
+
+    #[test]
+    fn rounded_gradient_sharp_corners() {
+        let rect = Rect::from_min_size(pos2(10.0, 20.0), vec2(100.0, 50.0));
+        let mesh = Mesh::rounded_gradient(rect, 0, Color32::BLACK, Color32::WHITE, 0.0);
+        assert!(mesh.is_valid());
+        assert_eq!(mesh.vertices.len(), 5, "center + four corners");
+        assert_eq!(mesh.indices.len(), 3 * 4);
+        assert_eq!(mesh.calc_bounds(), rect);
+
+        let color_at = |pos: Pos2| {
+            mesh.vertices
+                .iter()
+                .find(|v| v.pos == pos)
+                .map(|v| v.color)
+                .unwrap()
+        };
+        assert_eq!(color_at(rect.left_top()), Color32::BLACK);
+        assert_eq!(color_at(rect.right_bottom()), Color32::WHITE);
+    }
+
+    #[test]
+    fn rounded_gradient_clips_corners() {
+        let rect = Rect::from_min_size(pos2(0.0, 0.0), vec2(100.0, 50.0));
+        let radius = 10.0;
+        let mesh = Mesh::rounded_gradient(rect, radius, Color32::BLACK, Color32::WHITE, 1.0);
+        assert!(mesh.is_valid());
+        assert!(mesh.vertices.len() > 5);
+        assert_eq!(mesh.indices.len(), 3 * (mesh.vertices.len() - 1));
+
+        let corner_centers = [
+            rect.left_top() + vec2(radius, radius),
+            rect.right_top() + vec2(-radius, radius),
+            rect.left_bottom() + vec2(radius, -radius),
+            rect.right_bottom() + vec2(-radius, -radius),
+        ];
+        for vertex in &mesh.vertices {
+            assert!(rect.expand(1e-3).contains(vertex.pos));
+            for center in corner_centers {
+                let in_corner_region = (vertex.pos.x - center.x).abs() > 0.0
+                    && (vertex.pos.y - center.y).abs() > 0.0
+                    && (vertex.pos.x < center.x) == (center.x < rect.center().x)
+                    && (vertex.pos.y < center.y) == (center.y < rect.center().y);
+                if in_corner_region {
+                    assert!(
+                        vertex.pos.distance(center) <= radius + 1e-3,
+                        "{:?} is outside the rounded corner",
+                        vertex.pos
+                    );
+                }
+            }
+        }
+    }
+}
@@ -188,6 +188,59 @@ impl LayoutJob {
             self.wrap.max_width
         }
     }
+
+    /// Check that the sections are sorted, don't overlap, start and end on `char` boundaries,
+    /// and together cover all of [`Self::text`].
+    ///
+    /// Useful in tests and debug assertions when building a [`LayoutJob`] by hand.
+    ///
+    /// # Errors
+    /// Returns the first problem found. Broken sections are reported before gaps.
+    pub fn validate(&self) -> Result<(), LayoutError> {
+        let mut covered_until = 0;
+        let mut first_gap = None;
+        for (index, section) in self.sections.iter().enumerate() {
+            let Range { start, end } = section.byte_range.clone();
+            if end < start || self.text.len() < end {
+                return Err(LayoutError::InvalidRange {
+                    section: index,
+                    byte_range: start..end,
+                    text_len: self.text.len(),
+                });
+            }
+            for byte in [start, end] {
+                if !self.text.is_char_boundary(byte) {
+                    return Err(LayoutError::NotCharBoundary {
+                        section: index,
+                        byte,
+                    });
+                }
+            }
+            if start < covered_until {
+                let previous = &self.sections[index - 1].byte_range;
+                return Err(if start < previous.start {
+                    LayoutError::Unsorted { section: index }
+                } else {
+                    LayoutError::Overlap {
+                        section: index,
+                        overlap: start..covered_until,
+                    }
+                });
+            }
+            if covered_until < start {
+                // Report broken sections before gaps, since they may be the cause:
+                first_gap.get_or_insert(covered_until..start);
+            }
+            covered_until = end;
+        }
+        if covered_until < self.text.len() {
+            first_gap.get_or_insert(covered_until..self.text.len());
+        }
+        match first_gap {
+            Some(gap) => Err(LayoutError::Gap { gap }),
+            None => Ok(()),
+        }
+    }
 }
 
 impl std::hash::Hash for LayoutJob {
@@ -215,6 +268,60 @@ impl std::hash::Hash for LayoutJob {
     }
 }
 
+/// What is wrong with the sections of a [`LayoutJob`], see [`LayoutJob::validate`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LayoutError {
+    /// The byte range of a section is reversed or goes past the end of the text.
+    InvalidRange {
+        section: usize,
+        byte_range: Range<usize>,
+        text_len: usize,
+    },
+
+    /// A section starts or ends in the middle of a multi-byte character.
+    NotCharBoundary { section: usize, byte: usize },
+
+    /// A section starts before the previous one.
+    Unsorted { section: usize },
+
+    /// A section overlaps the previous one.
+    Overlap {
+        section: usize,
+        overlap: Range<usize>,
+    },
+
+    /// Part of the text isn't covered by any section.
+    Gap { gap: Range<usize> },
+}
+
+impl std::fmt::Display for LayoutError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidRange {
+                section,
+                byte_range,
+                text_len,
+            } => write!(
+                f,
+                "Section {section} has byte range {byte_range:?}, but the text is {text_len} bytes"
+            ),
+            Self::NotCharBoundary { section, byte } => {
+                write!(f, "Section {section} splits a character at byte {byte}")
+            }
+            Self::Unsorted { section } => {
+                write!(f, "Section {section} starts before the previous section")
+            }
+            Self::Overlap { section, overlap } => write!(
+                f,
+                "Section {section} overlaps the previous section at bytes {overlap:?}"
+            ),
+            Self::Gap { gap } => write!(f, "Bytes {gap:?} are not covered by any section"),
+        }
+    }
+}
+
+impl std::error::Error for LayoutError {}
+
 // ----------------------------------------------------------------------------
 
 #[derive(Clone, Debug, PartialEq)]
@@ -1202,3 +1309,67 @@ impl Galley {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn job(text: &str, ranges: &[(usize, usize)]) -> LayoutJob {
+        LayoutJob {
+            text: text.to_owned(),
+            sections: ranges
+                .iter()
+                .map(|&(start, end)| LayoutSection {
+                    leading_space: 0.0,
+                    byte_range: start..end,
+                    format: TextFormat::default(),
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn validate_layout_job() {
+        assert_eq!(job("", &[]).validate(), Ok(()));
+        assert_eq!(job("Hello world", &[(0, 5), (5, 11)]).validate(), Ok(()));
+
+        assert_eq!(
+            job("Hello world", &[(0, 6), (5, 11)]).validate(),
+            Err(LayoutError::Overlap {
+                section: 1,
+                overlap: 5..6
+            })
+        );
+        assert_eq!(
+            job("Hello world", &[(5, 11), (0, 5)]).validate(),
+            Err(LayoutError::Unsorted { section: 1 })
+        );
+        assert_eq!(
+            job("Hello world", &[(0, 4), (5, 11)]).validate(),
+            Err(LayoutError::Gap { gap: 4..5 })
+        );
+        assert_eq!(
+            job("Hello world", &[(0, 5)]).validate(),
+            Err(LayoutError::Gap { gap: 5..11 })
+        );
+        assert_eq!(
+            job("Hello", &[(0, 6)]).validate(),
+            Err(LayoutError::InvalidRange {
+                section: 0,
+                byte_range: 0..6,
+                text_len: 5
+            })
+        );
+
+        // 'ö' is two bytes:
+        assert_eq!(
+            job("höla", &[(0, 2), (2, 5)]).validate(),
+            Err(LayoutError::NotCharBoundary {
+                section: 0,
+                byte: 2
+            })
+        );
+        assert_eq!(job("höla", &[(0, 3), (3, 5)]).validate(), Ok(()));
+    }
+}
@@ -0,0 +1,291 @@
+//! Diagnostics (errors, warnings, …) and a widget listing them.
+
+use egui::text::LayoutJob;
+use egui::{Color32, FontId, Response, TextFormat, Ui, Visuals, Widget};
+
+/// The severity of a [`CodeError`], most severe first.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ErrorType {
+    ERROR,
+    WARNING,
+    INFO,
+}
+
+impl ErrorType {
+    /// The icon shown in front of the message.
+    pub fn icon(self) -> &'static str {
+        match self {
+            Self::ERROR => "⊗",
+            Self::WARNING => "⚠",
+            Self::INFO => "ℹ",
+        }
+    }
+
+    /// The color of the icon, from the [`DiagnosticTheme`] preset for `visuals`.
+    pub fn color(self, visuals: &Visuals) -> Color32 {
+        DiagnosticTheme::from_visuals(visuals)
+            .colors(self)
+            .gutter_marker
+    }
+}
+
+/// The colors used for diagnostics of one [`ErrorType`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SeverityColors {
+    /// The wavy underline below the offending code.
+    pub underline: Color32,
+
+    /// Markers in the gutter and icons in the [`DiagnosticsList`].
+    pub gutter_marker: Color32,
+
+    /// The background of the tooltip with the message.
+    pub tooltip_background: Color32,
+}
+
+/// Maps each [`ErrorType`] to the colors it is shown with.
+///
+/// Use [`Self::from_visuals`] to pick the [`Self::dark`] or [`Self::light`] preset,
+/// or build your own and pass it to [`crate::CodeEditor::set_diagnostic_theme`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DiagnosticTheme {
+    pub error: SeverityColors,
+    pub warning: SeverityColors,
+    pub info: SeverityColors,
+}
+
+impl DiagnosticTheme {
+    /// Bright colors on dark tooltip backgrounds, for dark mode.
+    pub fn dark() -> Self {
+        Self {
+            error: SeverityColors {
+                underline: Color32::from_rgb(255, 90, 90),
+                gutter_marker: Color32::from_rgb(255, 90, 90),
+                tooltip_background: Color32::from_rgb(70, 25, 25),
+            },
+            warning: SeverityColors {
+                underline: Color32::from_rgb(255, 180, 60),
+                gutter_marker: Color32::from_rgb(255, 180, 60),
+                tooltip_background: Color32::from_rgb(70, 50, 15),
+            },
+            info: SeverityColors {
+                underline: Color32::from_rgb(90, 170, 255),
+                gutter_marker: Color32::from_rgb(90, 170, 255),
+                tooltip_background: Color32::from_rgb(20, 45, 75),
+            },
+        }
+    }
+
+    /// Deep colors on pale tooltip backgrounds, for light mode.
+    pub fn light() -> Self {
+        Self {
+            error: SeverityColors {
+                underline: Color32::from_rgb(200, 0, 0),
+                gutter_marker: Color32::from_rgb(200, 0, 0),
+                tooltip_background: Color32::from_rgb(255, 225, 225),
+            },
+            warning: SeverityColors {
+                underline: Color32::from_rgb(190, 110, 0),
+                gutter_marker: Color32::from_rgb(190, 110, 0),
+                tooltip_background: Color32::from_rgb(255, 240, 205),
+            },
+            info: SeverityColors {
+                underline: Color32::from_rgb(0, 100, 200),
+                gutter_marker: Color32::from_rgb(0, 100, 200),
+                tooltip_background: Color32::from_rgb(220, 235, 255),
+            },
+        }
+    }
+
+    /// The preset matching [`Visuals::dark_mode`].
+    pub fn from_visuals(visuals: &Visuals) -> Self {
+        if visuals.dark_mode {
+            Self::dark()
+        } else {
+            Self::light()
+        }
+    }
+
+    /// The colors for diagnostics of the given severity.
+    pub fn colors(&self, error_type: ErrorType) -> SeverityColors {
+        match error_type {
+            ErrorType::ERROR => self.error,
+            ErrorType::WARNING => self.warning,
+            ErrorType::INFO => self.info,
+        }
+    }
+}
+
+impl Default for DiagnosticTheme {
+    fn default() -> Self {
+        Self::dark()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct CodeError {
+    message: String,
+    error_type: ErrorType,
+    range: Option<std::ops::Range<usize>>,
+}
+
+impl CodeError {
+    pub fn new(error_type: ErrorType, message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            error_type,
+            range: None,
+        }
+    }
+
+    /// The byte range of the buffer the diagnostic is about.
+    ///
+    /// The range gets a wavy underline, and hovering it shows the message.
+    #[inline]
+    pub fn with_range(mut self, range: std::ops::Range<usize>) -> Self {
+        self.range = Some(range);
+        self
+    }
+
+    pub fn range(&self) -> Option<std::ops::Range<usize>> {
+        self.range.clone()
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn error_type(&self) -> ErrorType {
+        self.error_type
+    }
+}
+
+/// Lists [`CodeError`]s with an icon per [`ErrorType`], one selectable row per error.
+///
+/// Usable on its own, e.g. in a bottom panel next to the editor.
+/// Clicking a row selects it, and marks the returned [`Response`] as changed.
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// # let errors = vec![code_editor::CodeError::new(code_editor::ErrorType::ERROR, "oops")];
+/// let mut selected = None;
+/// if ui.add(code_editor::DiagnosticsList::new(&errors, &mut selected)).changed() {
+///     // jump to `errors[selected.unwrap()]`
+/// }
+/// # });
+/// ```
+pub struct DiagnosticsList<'a> {
+    errors: &'a [CodeError],
+    selected: &'a mut Option<usize>,
+}
+
+impl<'a> DiagnosticsList<'a> {
+    pub fn new(errors: &'a [CodeError], selected: &'a mut Option<usize>) -> Self {
+        Self { errors, selected }
+    }
+}
+
+impl Widget for DiagnosticsList<'_> {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let Self { errors, selected } = self;
+        let font_id = FontId::monospace(12.0);
+
+        let egui::InnerResponse {
+            inner: clicked,
+            mut response,
+        } = ui.vertical(|ui| {
+            let mut clicked = None;
+            for (index, error) in errors.iter().enumerate() {
+                let mut job = LayoutJob::default();
+                job.append(
+                    error.error_type.icon(),
+                    0.0,
+                    TextFormat::simple(font_id.clone(), error.error_type.color(ui.visuals())),
+                );
+                job.append(
+                    &error.message,
+                    6.0,
+                    TextFormat::simple(font_id.clone(), ui.visuals().text_color()),
+                );
+                if ui.selectable_label(*selected == Some(index), job).clicked() {
+                    clicked = Some(index);
+                }
+            }
+            clicked
+        });
+
+        if clicked.is_some() && clicked != *selected {
+            *selected = clicked;
+            response.mark_changed();
+        }
+        response
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn one_row_per_error_with_icon_color() {
+        let errors = [
+            CodeError::new(ErrorType::ERROR, "mismatched types"),
+            CodeError::new(ErrorType::WARNING, "unused variable"),
+            CodeError::new(ErrorType::INFO, "consider borrowing"),
+        ];
+        let mut selected = None;
+
+        let ctx = egui::Context::default();
+        let output = ctx.run(Default::default(), |ctx| {
+            egui::CentralPanel::default().show(ctx, |ui| {
+                ui.add(DiagnosticsList::new(&errors, &mut selected));
+            });
+        });
+
+        let rows: Vec<_> = output
+            .shapes
+            .iter()
+            .filter_map(|clipped| match &clipped.shape {
+                egui::Shape::Text(text) => Some(text.galley.clone()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(rows.len(), errors.len());
+
+        let visuals = ctx.style().visuals.clone();
+        for (row, error) in rows.iter().zip(&errors) {
+            assert_eq!(
+                row.text(),
+                format!("{}{}", error.error_type().icon(), error.message())
+            );
+            let icon_color = row.job.sections[0].format.color;
+            assert_eq!(icon_color, error.error_type().color(&visuals));
+        }
+        assert_eq!(selected, None);
+    }
+
+    #[test]
+    fn dark_and_light_themes_are_readable() {
+        fn contrast(a: Color32, b: Color32) -> f32 {
+            let (a, b) = (
+                egui::Rgba::from(a).intensity() + 0.05,
+                egui::Rgba::from(b).intensity() + 0.05,
+            );
+            a.max(b) / a.min(b)
+        }
+
+        for visuals in [Visuals::dark(), Visuals::light()] {
+            let theme = DiagnosticTheme::from_visuals(&visuals);
+            let other = DiagnosticTheme::from_visuals(&Visuals {
+                dark_mode: !visuals.dark_mode,
+                ..visuals.clone()
+            });
+            for error_type in [ErrorType::ERROR, ErrorType::WARNING, ErrorType::INFO] {
+                let colors = theme.colors(error_type);
+                assert_ne!(colors, other.colors(error_type));
+                assert!(contrast(colors.underline, visuals.panel_fill) >= 3.0);
+                assert!(contrast(colors.gutter_marker, visuals.panel_fill) >= 3.0);
+                assert!(contrast(colors.tooltip_background, visuals.text_color()) >= 3.0);
+            }
+        }
+    }
+}
@@ -0,0 +1,166 @@
+//! Line diffs between two versions of a buffer.
+
+use std::collections::BTreeMap;
+
+/// How a line of the new text differs from the old text.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LineChange {
+    /// The line is new.
+    Added,
+
+    /// The line replaces one or more old lines.
+    Changed,
+
+    /// Old lines were removed just before this line.
+    ///
+    /// For lines removed at the end, this is the line after the last line.
+    Removed,
+}
+
+/// The most cells of the table of the longest common subsequence [`line_changes`] builds,
+/// one per pair of lines of the differing parts of the texts. 4M cells of `u32` are 16 MB.
+const MAX_LCS_CELLS: usize = 4 << 20;
+
+/// The zero-based lines of `new` that differ from `old`, from the longest common subsequence
+/// of their lines.
+///
+/// In a run of differing lines, the new lines replacing old ones are [`LineChange::Changed`]
+/// and any extra new lines are [`LineChange::Added`]. Unchanged lines are left out.
+///
+/// This is quadratic in the number of lines between the common start and end of the texts,
+/// which is fine for the usual edits. Above [`MAX_LCS_CELLS`], e.g. for two unrelated files,
+/// the differing lines are marked as replaced as a whole, without matching them up.
+pub fn line_changes(old: &str, new: &str) -> BTreeMap<usize, LineChange> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    // Only diff the part between the common start and end:
+    let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old = &old[prefix..old.len() - suffix];
+    let new = &new[prefix..new.len() - suffix];
+    if (old.len() + 1).saturating_mul(new.len() + 1) > MAX_LCS_CELLS {
+        return replaced_lines(prefix, old.len(), new.len());
+    }
+
+    // `lcs[i][j]` is the length of the longest common subsequence of `old[i..]` and `new[j..]`:
+    let mut lcs = vec![vec![0_u32; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut changes = BTreeMap::new();
+    let mut removed = 0;
+    let mut added = Vec::new();
+    let mut flush = |removed: &mut usize, added: &mut Vec<usize>, next_line: usize| {
+        if added.is_empty() && *removed > 0 {
+            changes.insert(prefix + next_line, LineChange::Removed);
+        }
+        for (k, line) in added.drain(..).enumerate() {
+            let change = if k < *removed {
+                LineChange::Changed
+            } else {
+                LineChange::Added
+            };
+            changes.insert(prefix + line, change);
+        }
+        *removed = 0;
+    };
+
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            flush(&mut removed, &mut added, j);
+            i += 1;
+            j += 1;
+        } else if j < new.len() && (i == old.len() || lcs[i][j + 1] >= lcs[i + 1][j]) {
+            added.push(j);
+            j += 1;
+        } else {
+            removed += 1;
+            i += 1;
+        }
+    }
+    flush(&mut removed, &mut added, j);
+    changes
+}
+
+/// The changes for `old_len` lines replaced by `new_len` lines, starting at line `prefix`.
+fn replaced_lines(prefix: usize, old_len: usize, new_len: usize) -> BTreeMap<usize, LineChange> {
+    if new_len == 0 {
+        return (old_len > 0)
+            .then_some((prefix, LineChange::Removed))
+            .into_iter()
+            .collect();
+    }
+    (0..new_len)
+        .map(|line| {
+            let change = if line < old_len {
+                LineChange::Changed
+            } else {
+                LineChange::Added
+            };
+            (prefix + line, change)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn changed_added_and_removed_lines() {
+        let old = "fn main() {\n    let a = 1;\n    let b = 2;\n    println!(\"{a}\");\n}\n";
+        let new =
+            "fn main() {\n    let a = 10;\n    println!(\"{a}\");\n    println!(\"done\");\n}\n";
+        assert_eq!(
+            line_changes(old, new).into_iter().collect::<Vec<_>>(),
+            [(1, LineChange::Changed), (3, LineChange::Added)]
+        );
+
+        // Only removed lines are marked on the line after them:
+        let new = "fn main() {\n    println!(\"{a}\");\n}\n";
+        assert_eq!(
+            line_changes(old, new).into_iter().collect::<Vec<_>>(),
+            [(1, LineChange::Removed)]
+        );
+
+        // Lines removed at the end are marked on the line after the last one:
+        let new = "fn main() {\n    let a = 1;\n";
+        assert_eq!(
+            line_changes(old, new).into_iter().collect::<Vec<_>>(),
+            [(2, LineChange::Removed)]
+        );
+
+        assert!(line_changes(old, old).is_empty());
+    }
+
+    #[test]
+    fn unrelated_big_texts_are_replaced_as_a_whole() {
+        let lines = |prefix: &str, count: usize| -> Vec<String> {
+            (0..count).map(|i| format!("{prefix} {i}")).collect()
+        };
+        let old = format!("same\n{}\nsame\n", lines("old", 3000).join("\n"));
+        let new = format!("same\n{}\nsame\n", lines("new", 4000).join("\n"));
+
+        let changes = line_changes(&old, &new);
+        assert_eq!(changes.len(), 4000);
+        assert_eq!(changes.get(&0), None);
+        assert_eq!(changes.get(&1), Some(&LineChange::Changed));
+        assert_eq!(changes.get(&3000), Some(&LineChange::Changed));
+        assert_eq!(changes.get(&3001), Some(&LineChange::Added));
+        assert_eq!(changes.get(&4001), None);
+    }
+}
@@ -0,0 +1,101 @@
+//! Detecting how a buffer is indented.
+
+/// How lines are indented: with tabs, or with a number of spaces per level.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Indentation {
+    /// Indent with spaces instead of tabs.
+    pub insert_spaces: bool,
+
+    /// The number of columns of one indentation level, and of a tab.
+    pub tab_width: usize,
+}
+
+impl Default for Indentation {
+    fn default() -> Self {
+        Self {
+            insert_spaces: true,
+            tab_width: 4,
+        }
+    }
+}
+
+impl Indentation {
+    /// The indentation used by most indented lines in `text`.
+    ///
+    /// For space indentation, the width is the most common step between the indentation
+    /// of consecutive lines. Tab indented text keeps the `tab_width` of the default.
+    /// Returns `None` if no line is indented.
+    pub fn detect(text: &str) -> Option<Self> {
+        let mut tab_lines = 0;
+        let mut space_lines = 0;
+        let mut step_counts = [0_usize; 9];
+        let mut previous_width = 0;
+        for line in text.lines() {
+            let code = line.trim_start_matches([' ', '\t']);
+            if code.is_empty() {
+                continue; // Blank lines say nothing about the indentation
+            }
+            let indent = &line[..line.len() - code.len()];
+            if indent.starts_with('\t') {
+                tab_lines += 1;
+                continue;
+            }
+            let width = indent.len() - indent.trim_start_matches(' ').len();
+            if width > 0 {
+                space_lines += 1;
+            }
+            if let Some(count) = step_counts.get_mut(width.abs_diff(previous_width)) {
+                *count += 1;
+            }
+            previous_width = width;
+        }
+
+        if tab_lines == 0 && space_lines == 0 {
+            None
+        } else if tab_lines > space_lines {
+            Some(Self {
+                insert_spaces: false,
+                ..Default::default()
+            })
+        } else {
+            // On a tie, the smaller step wins, since 2 space steps also show up in 4 space files:
+            let tab_width = (2..step_counts.len())
+                .rev()
+                .max_by_key(|&step| step_counts[step])
+                .filter(|&step| step_counts[step] > 0)
+                .unwrap_or(Self::default().tab_width);
+            Some(Self {
+                insert_spaces: true,
+                tab_width,
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detect_spaces_and_tabs() {
+        assert_eq!(Indentation::detect("no indentation\n\n"), None);
+
+        let two_spaces = "fn main() {\n  if x {\n    y();\n\n    z();\n  }\n}\n";
+        assert_eq!(
+            Indentation::detect(two_spaces),
+            Some(Indentation {
+                insert_spaces: true,
+                tab_width: 2,
+            })
+        );
+
+        let tabs = "fn main() {\n\tif x {\n\t\ty();\n\t}\n}\n";
+        assert_eq!(
+            Indentation::detect(tabs),
+            Some(Indentation {
+                insert_spaces: false,
+                tab_width: 4,
+            })
+        );
+    }
+}
@@ -0,0 +1,3386 @@
+//! A code editor built on egui, with syntax highlighting powered by [`syntect`].
+
+mod diagnostics;
+mod diff;
+mod indentation;
+mod line_ending;
+mod search;
+mod tabs;
+mod undo;
+
+pub use diagnostics::{CodeError, DiagnosticTheme, DiagnosticsList, ErrorType, SeverityColors};
+pub use diff::{line_changes, LineChange};
+pub use indentation::Indentation;
+pub use line_ending::LineEnding;
+pub use search::{find, SearchMatch, SearchMode};
+pub use tabs::{EditorTab, EditorTabs};
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Arc;
+
+use egui::text::{LayoutJob, LayoutSection};
+use egui::{Align, Color32, FontId, Response, Sense, Stroke, TextFormat, Ui};
+use syntect::highlighting::FontStyle;
+use syntect::{
+    easy::ScopeRangeIterator,
+    highlighting::{HighlightIterator, HighlightState, Highlighter, Style, ThemeSet},
+    parsing::{ParseState, Scope, ScopeStack, ScopeStackOp, SyntaxReference, SyntaxSet},
+    util::LinesWithEndings,
+};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SpanStyle {
+    pub color: Color32,
+    pub background_color: Option<Color32>,
+    pub wave_underline: bool,
+    pub italics: bool,
+}
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Span {
+    pub style: SpanStyle,
+    pub range: std::ops::Range<usize>,
+}
+
+impl SpanStyle {
+    /// Unhighlighted text.
+    pub fn plain() -> Self {
+        Self {
+            color: Color32::GRAY,
+            background_color: None,
+            wave_underline: false,
+            italics: false,
+        }
+    }
+}
+
+impl Span {
+    fn from_syntect(style: Style, range: std::ops::Range<usize>) -> Self {
+        let fg = style.foreground;
+        Self {
+            range,
+            style: SpanStyle {
+                color: Color32::from_rgb(fg.r, fg.g, fg.b),
+                background_color: None,
+                wave_underline: false,
+                italics: style.font_style.contains(FontStyle::ITALIC),
+            },
+        }
+    }
+}
+
+/// Buffers larger than this are not syntax highlighted by default.
+pub const DEFAULT_MAX_HIGHLIGHT_BYTES: usize = 1024 * 1024;
+
+/// The default of [`CodeEditor::max_undo_steps`].
+pub const DEFAULT_MAX_UNDO_STEPS: usize = 1000;
+
+/// The default of [`CodeEditor::max_undo_bytes`].
+pub const DEFAULT_MAX_UNDO_BYTES: usize = 16 * 1024 * 1024;
+
+/// Height of a line of code, shared by the text and the gutter.
+const LINE_HEIGHT: f32 = 20.0;
+
+/// Width of the gutter to the left of the code.
+const GUTTER_WIDTH: f32 = 48.0;
+
+/// Width of the minimap strip to the right of the code.
+const MINIMAP_WIDTH: f32 = 14.0;
+
+/// How long the smooth scroll of [`CodeEditor::scroll_to_line`] takes, in seconds.
+const SMOOTH_SCROLL_DURATION: f32 = 0.2;
+
+/// The default color of the search match markers in the minimap.
+pub const DEFAULT_SEARCH_MARKER_COLOR: Color32 = Color32::from_rgb(230, 130, 40);
+
+/// Color of the gutter marker for lines that mix tabs and spaces in their indentation.
+const MIXED_INDENT_COLOR: Color32 = Color32::from_rgb(230, 160, 40);
+
+/// Text color of a TODO keyword in a comment, and of its gutter marker.
+const TODO_COLOR: Color32 = Color32::from_rgb(255, 210, 80);
+
+/// Background color of a TODO keyword in a comment.
+const TODO_BACKGROUND_COLOR: Color32 = Color32::from_rgb(90, 70, 20);
+
+/// Color of lines added since [`CodeEditor::set_diff`].
+const ADDED_COLOR: Color32 = Color32::from_rgb(80, 180, 90);
+
+/// Color of lines changed since [`CodeEditor::set_diff`].
+const CHANGED_COLOR: Color32 = Color32::from_rgb(80, 140, 220);
+
+/// Color of the marker where lines were removed since [`CodeEditor::set_diff`].
+const REMOVED_COLOR: Color32 = Color32::from_rgb(220, 80, 80);
+
+/// The keywords emphasized in comments by default.
+pub const DEFAULT_TODO_KEYWORDS: [&str; 4] = ["TODO", "FIXME", "HACK", "XXX"];
+
+/// How the buffer of a [`CodeEditor`] is currently rendered.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HighlightMode {
+    /// Colored by the [`SyntaxHighlighter`].
+    Syntax,
+
+    /// Plain monospace text, because the buffer is larger than the highlighting limit.
+    Plain,
+}
+
+/// What happened in a [`CodeEditor::ui`] call.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CodeEditorOutput {
+    /// The byte range of the buffer that differs from the buffer at the previous
+    /// [`CodeEditor::ui`] call, if any. `None` if the buffer is the same.
+    ///
+    /// This covers all edits in between, including the ones through methods like
+    /// [`CodeEditor::insert`], as one range from the first to the last changed byte.
+    pub changed_range: Option<std::ops::Range<usize>>,
+}
+
+impl CodeEditorOutput {
+    /// Did the buffer change since the previous frame, like [`egui::Response::changed`]
+    /// of a [`egui::TextEdit`]? Use this to trigger a recompilation or a language server request.
+    pub fn changed(&self) -> bool {
+        self.changed_range.is_some()
+    }
+}
+
+pub struct CodeEditor {
+    buffer: String,
+    syntax_highlighter: SyntaxHighlighter,
+    font_size: f32,
+    error_list: Vec<CodeError>,
+    max_highlight_bytes: usize,
+    line_ending: LineEnding,
+    tab_width: usize,
+    insert_spaces: bool,
+    detect_indentation_on_load: bool,
+    breakpoints: BTreeSet<usize>,
+    breakpoints_enabled: bool,
+    on_breakpoint_toggled: Option<Box<dyn FnMut(usize, bool)>>,
+    flag_mixed_indent: bool,
+    sticky_scroll: bool,
+    folds: Vec<std::ops::Range<usize>>,
+    scroll_to_row: Option<usize>,
+    scroll_bar_visibility: egui::scroll_area::ScrollBarVisibility,
+    smooth_scroll: bool,
+    scroll_animation: Option<ScrollAnimation>,
+
+    /// The vertical range of the code shown by the last [`Self::ui`], relative to the first row.
+    viewport: egui::Rangef,
+    drop_error: Option<String>,
+    diagnostic_theme: Option<DiagnosticTheme>,
+
+    /// Whether the last [`Self::ui`] had dark visuals, to pick the [`DiagnosticTheme`] preset.
+    dark_mode: bool,
+
+    search_query: String,
+    search_mode: SearchMode,
+    search_match_case: bool,
+    search_marker_color: Color32,
+    search_results: Option<SearchResults>,
+
+    /// Shown dimmed while the buffer is empty.
+    hint_text: String,
+
+    /// The selected byte range of the buffer, from double and triple clicks.
+    selection: Option<std::ops::Range<usize>>,
+
+    diff: Option<Diff>,
+
+    /// The buffer before the first edit since the last [`Self::ui`], for its [`CodeEditorOutput`].
+    buffer_before_edits: Option<String>,
+
+    /// The code as laid out by the last [`Self::ui`], and where it was painted.
+    last_layout: Option<(Arc<egui::Galley>, egui::Pos2)>,
+
+    undo_stack: undo::UndoStack,
+}
+
+/// A smooth scroll started by [`CodeEditor::scroll_to_line`].
+struct ScrollAnimation {
+    /// The vertical scroll offsets at the start and the end.
+    from: f32,
+    to: f32,
+    start_time: f64,
+}
+
+/// The text set with [`CodeEditor::set_diff`], and how the buffer differs from it.
+struct Diff {
+    old: String,
+
+    /// The hash of the buffer the `changes` were computed for.
+    buffer_hash: u64,
+    changes: BTreeMap<usize, LineChange>,
+}
+
+/// The results of the query of the [`CodeEditor::search_ui`].
+struct SearchResults {
+    /// The hash of the query, its options and the buffer the results were computed for.
+    key: u64,
+
+    /// The number of matches, or why the query is invalid.
+    match_count: Result<usize, String>,
+
+    /// The zero-based lines with a match, without duplicates.
+    lines: Vec<usize>,
+}
+
+pub struct SyntaxHighlighter {
+    ps: SyntaxSet,
+    ts: ThemeSet,
+    language: String,
+    todo_keywords: Vec<String>,
+    line_cache: Vec<LineCache>,
+}
+
+/// The name of the syntect theme used for highlighting.
+const THEME: &str = "base16-ocean.dark";
+
+/// The syntax with the given name (e.g. `"Python"`) or file extension (e.g. `"py"`).
+fn find_syntax<'a>(ps: &'a SyntaxSet, language: &str) -> Option<&'a SyntaxReference> {
+    ps.find_syntax_by_name(language)
+        .or_else(|| ps.find_syntax_by_extension(language))
+}
+
+/// The token starting a line comment in the syntax with the given name or file extension.
+fn line_comment_token(language: &str) -> &'static str {
+    match language.to_lowercase().as_str() {
+        "python"
+        | "py"
+        | "ruby"
+        | "rb"
+        | "perl"
+        | "pl"
+        | "r"
+        | "makefile"
+        | "yaml"
+        | "yml"
+        | "toml"
+        | "bourne again shell (bash)"
+        | "sh"
+        | "bash" => "#",
+        "sql" | "lua" | "haskell" | "hs" => "--",
+        "latex" | "tex" | "matlab" => "%",
+        "lisp" | "clojure" | "clj" => ";",
+        _ => "//",
+    }
+}
+
+/// The highlighting of one line, from the last call to [`SyntaxHighlighter::highlight_text`].
+struct LineCache {
+    /// Hash of the line's text, including its line ending.
+    hash: u64,
+
+    /// Byte ranges are relative to the start of the line.
+    sections: Vec<Span>,
+
+    /// Whether a comment on this line contains one of the TODO keywords.
+    has_todo: bool,
+
+    /// Parser state at the end of the line, i.e. the start state of the next line.
+    parse_state: ParseState,
+    highlight_state: HighlightState,
+}
+
+impl Default for SyntaxHighlighter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SyntaxHighlighter {
+    pub fn new() -> Self {
+        let syntax_set = SyntaxSet::load_defaults_newlines();
+        let theme_set = ThemeSet::load_defaults();
+        Self {
+            ps: syntax_set,
+            ts: theme_set,
+            language: "Rs".to_owned(),
+            todo_keywords: DEFAULT_TODO_KEYWORDS.map(str::to_owned).to_vec(),
+            line_cache: Vec::new(),
+        }
+    }
+
+    /// The name or file extension of the syntax used for highlighting.
+    pub fn language(&self) -> &str {
+        &self.language
+    }
+
+    /// Highlight as the given syntax name (e.g. `"Python"`) or file extension (e.g. `"py"`).
+    pub fn set_language(&mut self, language: impl Into<String>) {
+        let language = language.into();
+        if language != self.language {
+            self.language = language;
+            self.line_cache.clear();
+        }
+    }
+
+    /// The keywords emphasized inside comments, [`DEFAULT_TODO_KEYWORDS`] by default.
+    pub fn todo_keywords(&self) -> &[String] {
+        &self.todo_keywords
+    }
+
+    pub fn set_todo_keywords(&mut self, keywords: Vec<String>) {
+        if keywords != self.todo_keywords {
+            self.todo_keywords = keywords;
+            self.line_cache.clear();
+        }
+    }
+
+    /// The zero-based lines with a TODO keyword in a comment, as of the last call to
+    /// [`Self::highlight_text`].
+    pub fn todo_lines(&self) -> Vec<usize> {
+        self.line_cache
+            .iter()
+            .enumerate()
+            .filter(|(_, cached)| cached.has_todo)
+            .map(|(line, _)| line)
+            .collect()
+    }
+
+    /// The name of the syntax for files with the given extension, if there is one.
+    pub fn language_for_extension(&self, extension: &str) -> Option<&str> {
+        self.ps
+            .find_syntax_by_extension(extension)
+            .map(|syntax| syntax.name.as_str())
+    }
+
+    /// The names of the loaded color themes, sorted, e.g. for a theme picker.
+    pub fn available_themes(&self) -> Vec<String> {
+        self.ts.themes.keys().cloned().collect()
+    }
+
+    /// The names of the loaded syntaxes, sorted, e.g. for a language picker.
+    ///
+    /// Any of them can be passed to [`Self::set_language`].
+    pub fn available_languages(&self) -> Vec<String> {
+        let mut languages: Vec<String> = self
+            .ps
+            .syntaxes()
+            .iter()
+            .filter(|syntax| !syntax.hidden)
+            .map(|syntax| syntax.name.clone())
+            .collect();
+        languages.sort();
+        languages.dedup();
+        languages
+    }
+
+    /// Highlight an isolated snippet of `code`, e.g. inline code in a tooltip or label.
+    ///
+    /// `language` is a syntax name or file extension, like in [`Self::set_language`].
+    /// The snippet is parsed from scratch, without touching the cache of [`Self::highlight_text`].
+    /// For an unknown language the whole snippet is a single plain span.
+    pub fn highlight_snippet(&self, code: &str, language: &str) -> Vec<Span> {
+        let plain = || {
+            vec![Span {
+                style: SpanStyle::plain(),
+                range: 0..code.len(),
+            }]
+        };
+        let Some(syntax) = find_syntax(&self.ps, language) else {
+            return plain();
+        };
+        let highlighter = Highlighter::new(&self.ts.themes[THEME]);
+        let mut parse_state = ParseState::new(syntax);
+        let mut highlight_state = HighlightState::new(&highlighter, ScopeStack::new());
+
+        let mut spans = Vec::new();
+        for line in LinesWithEndings::from(code) {
+            let Ok(ops) = parse_state.parse_line(line, &self.ps) else {
+                return plain();
+            };
+            let line_start = as_byte_range(code, line).start;
+            spans.extend(
+                HighlightIterator::new(&mut highlight_state, &ops, line, &highlighter).map(
+                    |(style, range)| {
+                        let range = as_byte_range(line, range);
+                        Span::from_syntect(style, line_start + range.start..line_start + range.end)
+                    },
+                ),
+            );
+        }
+        spans
+    }
+
+    /// Highlight `text`, reusing the cached result for each line whose text
+    /// and start state are unchanged since the last call.
+    pub fn highlight_text(&mut self, text: &str) -> Option<Vec<Span>> {
+        let syntax = find_syntax(&self.ps, &self.language)?;
+        let highlighter = Highlighter::new(&self.ts.themes[THEME]);
+        let mut parse_state = ParseState::new(syntax);
+        let mut highlight_state = HighlightState::new(&highlighter, ScopeStack::new());
+
+        // Whether the start state of the current line matches the one it was cached with:
+        let mut start_state_unchanged = true;
+        let mut spans = Vec::new();
+        let mut line_count = 0;
+
+        for (i, line) in LinesWithEndings::from(text).enumerate() {
+            line_count = i + 1;
+            let hash = egui::util::hash(line);
+            let cached = self.line_cache.get(i);
+
+            if start_state_unchanged && cached.is_some_and(|cached| cached.hash == hash) {
+                let cached = &self.line_cache[i];
+                parse_state.clone_from(&cached.parse_state);
+                highlight_state.clone_from(&cached.highlight_state);
+            } else {
+                let scopes = highlight_state.path.clone();
+                let ops = parse_state.parse_line(line, &self.ps).ok()?;
+                let todo_ranges = find_todo_keywords(line, &ops, scopes, &self.todo_keywords);
+                let sections: Vec<Span> =
+                    HighlightIterator::new(&mut highlight_state, &ops, line, &highlighter)
+                        .map(|(style, range)| Span::from_syntect(style, as_byte_range(line, range)))
+                        .collect();
+                let sections = emphasize_todo_keywords(sections, &todo_ranges);
+
+                start_state_unchanged = cached.is_some_and(|cached| {
+                    cached.parse_state == parse_state && cached.highlight_state == highlight_state
+                });
+                let entry = LineCache {
+                    hash,
+                    sections,
+                    has_todo: !todo_ranges.is_empty(),
+                    parse_state: parse_state.clone(),
+                    highlight_state: highlight_state.clone(),
+                };
+                if i < self.line_cache.len() {
+                    self.line_cache[i] = entry;
+                } else {
+                    self.line_cache.push(entry);
+                }
+            }
+
+            let line_start = as_byte_range(text, line).start;
+            spans.extend(self.line_cache[i].sections.iter().map(|span| Span {
+                range: line_start + span.range.start..line_start + span.range.end,
+                style: span.style.clone(),
+            }));
+        }
+        self.line_cache.truncate(line_count);
+
+        Some(spans)
+    }
+
+    /// The syntax scopes at byte `offset` of `text`, from the outermost to the innermost,
+    /// separated by spaces, e.g. `"source.rust meta.function.rust storage.type.function.rust"`.
+    ///
+    /// Lines that are unchanged since the last call to [`Self::highlight_text`] aren't parsed again.
+    /// `None` for an unknown language.
+    pub fn scope_at(&self, text: &str, offset: usize) -> Option<String> {
+        let syntax = find_syntax(&self.ps, &self.language)?;
+        let lines: Vec<&str> = LinesWithEndings::from(text).collect();
+        let cursor_line = lines
+            .iter()
+            .position(|line| offset < as_byte_range(text, line).end)
+            .unwrap_or(lines.len().saturating_sub(1));
+
+        // Start after the cached lines that are still the same:
+        let cached_lines = lines[..cursor_line]
+            .iter()
+            .zip(&self.line_cache)
+            .take_while(|(line, cached)| cached.hash == egui::util::hash(line))
+            .count();
+        let (mut parse_state, mut scopes) = match cached_lines.checked_sub(1) {
+            Some(i) => (
+                self.line_cache[i].parse_state.clone(),
+                self.line_cache[i].highlight_state.path.clone(),
+            ),
+            None => (ParseState::new(syntax), ScopeStack::new()),
+        };
+        for line in &lines[cached_lines..cursor_line] {
+            for (_, op) in parse_state.parse_line(line, &self.ps).ok()? {
+                scopes.apply(&op).ok()?;
+            }
+        }
+
+        if let Some(line) = lines.get(cursor_line) {
+            let column = offset - as_byte_range(text, line).start;
+            for (position, op) in parse_state.parse_line(line, &self.ps).ok()? {
+                if position > column {
+                    break;
+                }
+                scopes.apply(&op).ok()?;
+            }
+        }
+
+        let names: Vec<String> = scopes
+            .as_slice()
+            .iter()
+            .map(|scope| scope.build_string())
+            .collect();
+        Some(names.join(" "))
+    }
+}
+
+impl CodeEditor {
+    pub fn new() -> Self {
+        Self {
+            buffer: String::new(),
+            syntax_highlighter: SyntaxHighlighter::new(),
+            font_size: 12.0,
+            error_list: vec![],
+            max_highlight_bytes: DEFAULT_MAX_HIGHLIGHT_BYTES,
+            line_ending: LineEnding::default(),
+            tab_width: Indentation::default().tab_width,
+            insert_spaces: Indentation::default().insert_spaces,
+            detect_indentation_on_load: true,
+            breakpoints: BTreeSet::new(),
+            breakpoints_enabled: true,
+            on_breakpoint_toggled: None,
+            flag_mixed_indent: true,
+            sticky_scroll: true,
+            folds: Vec::new(),
+            scroll_to_row: None,
+            scroll_bar_visibility: Default::default(),
+            smooth_scroll: true,
+            scroll_animation: None,
+            viewport: egui::Rangef::new(0.0, 0.0),
+            drop_error: None,
+            diagnostic_theme: None,
+            dark_mode: true,
+            search_query: String::new(),
+            search_mode: SearchMode::Plain,
+            search_match_case: false,
+            search_marker_color: DEFAULT_SEARCH_MARKER_COLOR,
+            search_results: None,
+            hint_text: "// Start typing…".to_owned(),
+            selection: None,
+            diff: None,
+            buffer_before_edits: None,
+            last_layout: None,
+            undo_stack: Default::default(),
+        }
+    }
+
+    /// Buffers larger than this many bytes are shown as plain monospace text,
+    /// so that pasting a huge file doesn't freeze the UI.
+    pub fn max_highlight_bytes(&self) -> usize {
+        self.max_highlight_bytes
+    }
+
+    pub fn set_max_highlight_bytes(&mut self, max_highlight_bytes: usize) {
+        self.max_highlight_bytes = max_highlight_bytes;
+    }
+
+    /// Whether the current buffer is syntax highlighted or shown as plain text.
+    pub fn highlight_mode(&self) -> HighlightMode {
+        if self.buffer.len() > self.max_highlight_bytes {
+            HighlightMode::Plain
+        } else {
+            HighlightMode::Syntax
+        }
+    }
+
+    /// Replace the buffer, clearing the undo history.
+    pub fn load(&mut self, string: impl Into<String>) {
+        self.before_edit();
+        self.buffer = string.into();
+        self.line_ending = LineEnding::detect(&self.buffer);
+        if self.detect_indentation_on_load {
+            self.detect_indentation();
+        }
+        self.undo_stack.clear();
+    }
+
+    /// The number of columns of one indentation level, and of a tab. Default: 4.
+    pub fn tab_width(&self) -> usize {
+        self.tab_width
+    }
+
+    pub fn set_tab_width(&mut self, tab_width: usize) {
+        self.tab_width = tab_width.max(1);
+    }
+
+    /// Whether to indent with spaces instead of tabs. Default: `true`.
+    pub fn insert_spaces(&self) -> bool {
+        self.insert_spaces
+    }
+
+    pub fn set_insert_spaces(&mut self, insert_spaces: bool) {
+        self.insert_spaces = insert_spaces;
+    }
+
+    /// Call [`Self::detect_indentation`] in [`Self::load`]. On by default.
+    pub fn set_detect_indentation_on_load(&mut self, detect_indentation_on_load: bool) {
+        self.detect_indentation_on_load = detect_indentation_on_load;
+    }
+
+    /// Infer tabs vs. spaces and the indentation width from the buffer,
+    /// and set [`Self::insert_spaces`] and [`Self::tab_width`] to match.
+    ///
+    /// Returns the detected settings, or `None` (changing nothing) if no line is indented.
+    pub fn detect_indentation(&mut self) -> Option<Indentation> {
+        let indentation = Indentation::detect(&self.buffer)?;
+        self.insert_spaces = indentation.insert_spaces;
+        if indentation.insert_spaces {
+            // A tab file doesn't tell us how wide its tabs are meant to be.
+            self.tab_width = indentation.tab_width;
+        }
+        Some(Indentation {
+            insert_spaces: self.insert_spaces,
+            tab_width: self.tab_width,
+        })
+    }
+
+    /// The line ending detected when the buffer was loaded.
+    ///
+    /// Edits keep using it, so a CRLF file stays CRLF.
+    pub fn line_ending(&self) -> LineEnding {
+        self.line_ending
+    }
+
+    /// Convert the whole buffer to the given line ending, and keep using it for edits.
+    pub fn set_line_ending(&mut self, line_ending: LineEnding) {
+        self.line_ending = line_ending;
+        self.normalize_line_endings();
+    }
+
+    /// Convert any stray line breaks in the buffer to [`Self::line_ending`].
+    ///
+    /// This is a single undo step.
+    pub fn normalize_line_endings(&mut self) {
+        let normalized = self.line_ending.apply(&self.buffer);
+        if normalized != self.buffer {
+            self.replace_ranges([(0..self.buffer.len(), normalized)]);
+        }
+    }
+
+    /// Insert `text` at the given byte offset.
+    ///
+    /// Line breaks in `text` are converted to [`Self::line_ending`].
+    /// Inserting single characters one after the other, like typing,
+    /// is coalesced into a single undo step.
+    pub fn insert(&mut self, byte_offset: usize, text: &str) {
+        self.replace_ranges([(byte_offset..byte_offset, text.to_owned())]);
+    }
+
+    /// Replace several non-overlapping byte ranges of the buffer at once,
+    /// e.g. for an edit with multiple cursors. This is a single undo step.
+    ///
+    /// The ranges refer to the buffer before the edit.
+    /// Line breaks in the new text are converted to [`Self::line_ending`].
+    pub fn replace_ranges(
+        &mut self,
+        edits: impl IntoIterator<Item = (std::ops::Range<usize>, String)>,
+    ) {
+        let mut edits: Vec<_> = edits.into_iter().collect();
+        // Back to front, so the ranges of the remaining edits stay valid:
+        edits.sort_by_key(|(range, _)| std::cmp::Reverse(range.start));
+
+        let changes: Vec<undo::Change> = edits
+            .into_iter()
+            .map(|(range, text)| undo::Change {
+                offset: range.start,
+                removed: self.buffer[range].to_owned(),
+                inserted: self.line_ending.apply(&text),
+            })
+            .filter(|change| change.removed != change.inserted)
+            .collect();
+        if !changes.is_empty() {
+            self.before_edit();
+        }
+        for change in &changes {
+            self.buffer.replace_range(
+                change.offset..change.offset + change.removed.len(),
+                &change.inserted,
+            );
+        }
+        self.undo_stack.push(changes);
+    }
+
+    /// Revert the last edit. Returns `false` if there is nothing to undo.
+    ///
+    /// In [`Self::ui`], this is also done with Ctrl+Z (⌘Z on Mac).
+    pub fn undo(&mut self) -> bool {
+        self.before_edit();
+        self.undo_stack.undo(&mut self.buffer)
+    }
+
+    /// Re-apply the last undone edit. Returns `false` if there is nothing to redo.
+    ///
+    /// In [`Self::ui`], this is also done with Ctrl+Shift+Z (⇧⌘Z on Mac).
+    pub fn redo(&mut self) -> bool {
+        self.before_edit();
+        self.undo_stack.redo(&mut self.buffer)
+    }
+
+    /// Remember the buffer before it changes, for the [`CodeEditorOutput`] of the next [`Self::ui`].
+    fn before_edit(&mut self) {
+        if self.buffer_before_edits.is_none() {
+            self.buffer_before_edits = Some(self.buffer.clone());
+        }
+    }
+
+    pub fn can_undo(&self) -> bool {
+        self.undo_stack.can_undo()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        self.undo_stack.can_redo()
+    }
+
+    /// How many edits can be undone at most. The oldest ones are forgotten first.
+    pub fn max_undo_steps(&self) -> usize {
+        self.undo_stack.max_steps
+    }
+
+    pub fn set_max_undo_steps(&mut self, max_undo_steps: usize) {
+        self.undo_stack.max_steps = max_undo_steps;
+        self.undo_stack.enforce_limits();
+    }
+
+    /// How many bytes of removed and inserted text the undo history keeps at most,
+    /// so long editing sessions don't use ever more memory. The oldest edits are forgotten first.
+    pub fn max_undo_bytes(&self) -> usize {
+        self.undo_stack.max_bytes
+    }
+
+    pub fn set_max_undo_bytes(&mut self, max_undo_bytes: usize) {
+        self.undo_stack.max_bytes = max_undo_bytes;
+        self.undo_stack.enforce_limits();
+    }
+
+    /// Comment out every line of the selection, or uncomment them if they all are comments.
+    ///
+    /// The comment token of the [`Self::language`] is inserted at the smallest indentation
+    /// of the lines, so the comments line up, and removed again together with the space after it.
+    /// If only some of the lines are comments, all lines are commented.
+    /// Blank lines are left alone. Afterwards the whole lines are selected.
+    ///
+    /// Returns `false` if nothing is selected. This is a single undo step.
+    /// In [`Self::ui`], this is also done with Ctrl+/ (⌘/ on Mac).
+    pub fn toggle_comment(&mut self) -> bool {
+        let Some(selection) = self.selection.clone() else {
+            return false;
+        };
+        let token = line_comment_token(self.language());
+
+        // A selection ending at the start of a line, like after a triple click, doesn't include it:
+        let last =
+            if selection.end > selection.start && self.buffer[..selection.end].ends_with('\n') {
+                selection.end - 1
+            } else {
+                selection.end
+            };
+        let first_line = self.line_range_at(selection.start);
+        let last_line = self.line_range_at(last);
+        let lines: Vec<(usize, &str)> = self.buffer[first_line.start..last_line.end]
+            .split_inclusive('\n')
+            .scan(first_line.start, |start, line| {
+                let line_start = *start;
+                *start += line.len();
+                Some((line_start, line.trim_end_matches(['\r', '\n'])))
+            })
+            .filter(|(_, line)| !line.trim().is_empty())
+            .collect();
+        let indent = |line: &str| line.len() - line.trim_start().len();
+
+        let uncomment = lines
+            .iter()
+            .all(|(_, line)| line.trim_start().starts_with(token));
+        let edits: Vec<(std::ops::Range<usize>, String)> = if uncomment {
+            lines
+                .iter()
+                .map(|&(start, line)| {
+                    let token_start = start + indent(line);
+                    let mut token_end = token_start + token.len();
+                    if self.buffer[token_end..].starts_with(' ') {
+                        token_end += 1;
+                    }
+                    (token_start..token_end, String::new())
+                })
+                .collect()
+        } else {
+            let column = lines
+                .iter()
+                .map(|(_, line)| indent(line))
+                .min()
+                .unwrap_or(0);
+            lines
+                .iter()
+                .map(|&(start, _)| (start + column..start + column, format!("{token} ")))
+                .collect()
+        };
+
+        let growth: isize = edits
+            .iter()
+            .map(|(range, text)| text.len() as isize - range.len() as isize)
+            .sum();
+        self.replace_ranges(edits);
+        self.selection = Some(first_line.start..last_line.end.saturating_add_signed(growth));
+        true
+    }
+
+    /// Zero-based line numbers that have a breakpoint.
+    pub fn breakpoints(&self) -> &BTreeSet<usize> {
+        &self.breakpoints
+    }
+
+    /// Whether clicking the gutter toggles breakpoints. On by default.
+    pub fn set_breakpoints_enabled(&mut self, enabled: bool) {
+        self.breakpoints_enabled = enabled;
+    }
+
+    /// Called with the zero-based line and whether it now has a breakpoint,
+    /// whenever a breakpoint is toggled.
+    pub fn on_breakpoint_toggled(&mut self, callback: impl FnMut(usize, bool) + 'static) {
+        self.on_breakpoint_toggled = Some(Box::new(callback));
+    }
+
+    /// Add or remove the breakpoint on the given zero-based line.
+    pub fn toggle_breakpoint(&mut self, line: usize) {
+        let enabled = if self.breakpoints.remove(&line) {
+            false
+        } else {
+            self.breakpoints.insert(line);
+            true
+        };
+        if let Some(callback) = &mut self.on_breakpoint_toggled {
+            callback(line, enabled);
+        }
+    }
+
+    /// Mark lines that mix tabs and spaces in their indentation in the gutter. On by default.
+    pub fn set_flag_mixed_indent(&mut self, flag_mixed_indent: bool) {
+        self.flag_mixed_indent = flag_mixed_indent;
+    }
+
+    /// Zero-based line numbers whose leading whitespace contains both tabs and spaces.
+    pub fn mixed_indent_lines(&self) -> Vec<usize> {
+        self.buffer
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| {
+                let indent = &line[..line.len() - line.trim_start_matches([' ', '\t']).len()];
+                indent.contains(' ') && indent.contains('\t')
+            })
+            .map(|(line, _)| line)
+            .collect()
+    }
+
+    /// Emphasize these keywords inside comments, [`DEFAULT_TODO_KEYWORDS`] by default.
+    pub fn set_todo_keywords(&mut self, keywords: Vec<String>) {
+        self.syntax_highlighter.set_todo_keywords(keywords);
+    }
+
+    /// Zero-based line numbers with a TODO keyword in a comment,
+    /// as of the last call to [`Self::create_layout_job`].
+    pub fn todo_lines(&self) -> Vec<usize> {
+        if self.highlight_mode() == HighlightMode::Plain {
+            return Vec::new();
+        }
+        self.syntax_highlighter.todo_lines()
+    }
+
+    fn line_count(&self) -> usize {
+        self.buffer.lines().count().max(1)
+    }
+
+    /// Pin the headers of the scopes enclosing the top visible line
+    /// to the top of the editor while scrolling. On by default.
+    pub fn set_sticky_scroll(&mut self, sticky_scroll: bool) {
+        self.sticky_scroll = sticky_scroll;
+    }
+
+    /// Whether the scroll bar is always shown, or only when the code doesn't fit.
+    pub fn set_scroll_bar_visibility(
+        &mut self,
+        scroll_bar_visibility: egui::scroll_area::ScrollBarVisibility,
+    ) {
+        self.scroll_bar_visibility = scroll_bar_visibility;
+    }
+
+    /// Animate the scroll of [`Self::scroll_to_line`] instead of jumping. On by default.
+    pub fn set_smooth_scroll(&mut self, smooth_scroll: bool) {
+        self.smooth_scroll = smooth_scroll;
+    }
+
+    /// The zero-based header lines of the scopes enclosing `top_line`, outermost first.
+    ///
+    /// Scopes are found by indentation: a header is the closest line above
+    /// with less indentation than the scope body. Blank lines and lines starting
+    /// with a closing bracket (the end of an earlier scope) are never headers.
+    pub fn sticky_header_lines(&self, top_line: usize) -> Vec<usize> {
+        let indent_width = |line: &str| -> Option<usize> {
+            let code = line.trim_start();
+            if code.is_empty() {
+                return None;
+            }
+            let indent = &line[..line.len() - code.len()];
+            Some(
+                indent
+                    .chars()
+                    .map(|c| if c == '\t' { self.tab_width } else { 1 })
+                    .sum(),
+            )
+        };
+
+        let lines: Vec<&str> = self.buffer.lines().collect();
+        let Some(mut indent) = lines
+            .get(top_line..)
+            .and_then(|lines| lines.iter().find_map(|line| indent_width(line)))
+        else {
+            return vec![];
+        };
+
+        let mut headers = vec![];
+        for line in (0..top_line.min(lines.len())).rev() {
+            if indent == 0 {
+                break;
+            }
+            let is_scope_end = lines[line].trim_start().starts_with(['}', ')', ']']);
+            match indent_width(lines[line]) {
+                Some(width) if width < indent && !is_scope_end => {
+                    headers.push(line);
+                    indent = width;
+                }
+                _ => {}
+            }
+        }
+        headers.reverse();
+        headers
+    }
+
+    /// Hide the given zero-based lines, e.g. the body of a function.
+    ///
+    /// The gutter keeps showing the true line numbers, with a `…` after the last visible line.
+    pub fn fold(&mut self, lines: std::ops::Range<usize>) {
+        if lines.is_empty() {
+            return;
+        }
+        self.folds.push(lines);
+        self.folds.sort_by_key(|fold| fold.start);
+        let mut merged: Vec<std::ops::Range<usize>> = Vec::with_capacity(self.folds.len());
+        for fold in self.folds.drain(..) {
+            match merged.last_mut() {
+                Some(last) if fold.start <= last.end => last.end = last.end.max(fold.end),
+                _ => merged.push(fold),
+            }
+        }
+        self.folds = merged;
+    }
+
+    /// Show the lines of the fold containing the given zero-based line again.
+    pub fn unfold(&mut self, line: usize) {
+        self.folds.retain(|fold| !fold.contains(&line));
+    }
+
+    /// The hidden ranges of zero-based lines, sorted and non-overlapping.
+    pub fn folds(&self) -> &[std::ops::Range<usize>] {
+        &self.folds
+    }
+
+    /// The zero-based line shown on each row of the editor, skipping folded lines.
+    pub fn visible_lines(&self) -> Vec<usize> {
+        (0..self.line_count())
+            .filter(|line| !self.folds.iter().any(|fold| fold.contains(line)))
+            .collect()
+    }
+
+    /// The row of the editor showing the given zero-based line.
+    ///
+    /// For a folded line, this is the row of the last visible line before the fold.
+    pub fn line_to_row(&self, line: usize) -> usize {
+        self.visible_lines()
+            .partition_point(|&visible| visible <= line)
+            .saturating_sub(1)
+    }
+
+    /// The zero-based lines in view in the last [`Self::ui`], from its scroll offset and height,
+    /// e.g. to only compute diagnostics for what is on screen.
+    ///
+    /// Partly visible lines are included, and so are folded lines between visible ones.
+    /// Empty before the editor was shown.
+    pub fn visible_line_range(&self) -> std::ops::Range<usize> {
+        self.line_range_in_view(self.viewport)
+    }
+
+    /// The zero-based lines on the rows overlapping `viewport`, measured from the top of the first row.
+    fn line_range_in_view(&self, viewport: egui::Rangef) -> std::ops::Range<usize> {
+        let rows = self.visible_lines();
+        let first_row = (viewport.min / LINE_HEIGHT).floor().max(0.0) as usize;
+        let end_row = ((viewport.max / LINE_HEIGHT).ceil().max(0.0) as usize).min(rows.len());
+        if first_row >= end_row {
+            return 0..0;
+        }
+        rows[first_row]..rows[end_row - 1] + 1
+    }
+
+    /// Scroll the editor so the given zero-based line is at the top, unfolding it if needed.
+    pub fn scroll_to_line(&mut self, line: usize) {
+        self.unfold(line);
+        self.scroll_to_row = Some(self.line_to_row(line));
+    }
+
+    /// The byte ranges of the buffer hidden by [`Self::folds`].
+    fn folded_byte_ranges(&self) -> Vec<std::ops::Range<usize>> {
+        let line_starts: Vec<usize> = std::iter::once(0)
+            .chain(self.buffer.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+        let line_start = |line: usize| line_starts.get(line).copied().unwrap_or(self.buffer.len());
+        self.folds
+            .iter()
+            .map(|fold| line_start(fold.start)..line_start(fold.end))
+            .filter(|range| !range.is_empty())
+            .collect()
+    }
+
+    /// The syntax used for highlighting, see [`SyntaxHighlighter::set_language`].
+    pub fn language(&self) -> &str {
+        self.syntax_highlighter.language()
+    }
+
+    pub fn set_language(&mut self, language: impl Into<String>) {
+        self.syntax_highlighter.set_language(language);
+    }
+
+    /// Load a file dropped onto the window, picking the language from its file extension.
+    ///
+    /// # Errors
+    /// Binary and unreadable files are refused with an error message,
+    /// leaving the buffer as it was.
+    pub fn load_dropped_file(&mut self, file: &egui::DroppedFile) -> Result<(), String> {
+        let bytes = match (&file.bytes, &file.path) {
+            (Some(bytes), _) => bytes.to_vec(),
+            (None, Some(path)) => std::fs::read(path)
+                .map_err(|err| format!("Failed to read {}: {err}", path.display()))?,
+            (None, None) => return Err(format!("No contents for dropped file {:?}", file.name)),
+        };
+        let name = file
+            .path
+            .as_deref()
+            .unwrap_or_else(|| std::path::Path::new(&file.name));
+        let text = match String::from_utf8(bytes) {
+            Ok(text) if !text.contains('\0') => text,
+            _ => return Err(format!("{} looks like a binary file", name.display())),
+        };
+
+        if let Some(language) = name
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .and_then(|extension| self.syntax_highlighter.language_for_extension(extension))
+        {
+            let language = language.to_owned();
+            self.set_language(language);
+        }
+        self.load(text);
+        Ok(())
+    }
+
+    /// All matches of `query` in the buffer, see [`find`].
+    ///
+    /// # Errors
+    /// If `query` is not a valid regular expression in [`SearchMode::Regex`].
+    pub fn find(
+        &self,
+        query: &str,
+        mode: SearchMode,
+        match_case: bool,
+    ) -> Result<Vec<SearchMatch>, String> {
+        find(&self.buffer, query, mode, match_case)
+    }
+
+    /// The zero-based lines with a match of the query in the [`Self::search_ui`], without duplicates.
+    ///
+    /// Empty if there is no query, or it is an invalid regex.
+    pub fn search_match_lines(&self) -> Vec<usize> {
+        let key = self.search_key();
+        match &self.search_results {
+            Some(results) if results.key == key => results.lines.clone(),
+            _ => self.search(key).lines,
+        }
+    }
+
+    /// The hash of the query of the [`Self::search_ui`], its options and the buffer.
+    fn search_key(&self) -> u64 {
+        egui::util::hash((
+            &self.search_query,
+            self.search_mode,
+            self.search_match_case,
+            &self.buffer,
+        ))
+    }
+
+    /// Search the buffer again if the query, its options or the buffer changed
+    /// since the last search.
+    fn update_search(&mut self) -> &SearchResults {
+        let key = self.search_key();
+        if self
+            .search_results
+            .as_ref()
+            .map_or(true, |results| results.key != key)
+        {
+            self.search_results = Some(self.search(key));
+        }
+        self.search_results.as_ref().expect("just searched")
+    }
+
+    /// Find the query of the [`Self::search_ui`] in the buffer.
+    fn search(&self, key: u64) -> SearchResults {
+        let matches = match self.find(&self.search_query, self.search_mode, self.search_match_case)
+        {
+            Ok(matches) => matches,
+            Err(err) => {
+                return SearchResults {
+                    key,
+                    match_count: Err(err),
+                    lines: Vec::new(),
+                };
+            }
+        };
+
+        // The matches are in order, so only count the newlines since the previous one:
+        let mut lines = Vec::new();
+        let (mut line, mut counted_until) = (0, 0);
+        for m in &matches {
+            let start = m.range.start.max(counted_until);
+            line += self.buffer.as_bytes()[counted_until..start]
+                .iter()
+                .filter(|&&byte| byte == b'\n')
+                .count();
+            counted_until = start;
+            if lines.last() != Some(&line) {
+                lines.push(line);
+            }
+        }
+        SearchResults {
+            key,
+            match_count: Ok(matches.len()),
+            lines,
+        }
+    }
+
+    /// The color of the search match markers in the [`Self::minimap_ui`].
+    pub fn search_marker_color(&self) -> Color32 {
+        self.search_marker_color
+    }
+
+    pub fn set_search_marker_color(&mut self, color: Color32) {
+        self.search_marker_color = color;
+    }
+
+    /// The dim placeholder shown in place of an empty buffer, like [`egui::TextEdit::hint_text`].
+    /// Default: `"// Start typing…"`. An empty hint shows nothing.
+    pub fn hint_text(&self) -> &str {
+        &self.hint_text
+    }
+
+    pub fn set_hint_text(&mut self, hint_text: impl Into<String>) {
+        self.hint_text = hint_text.into();
+    }
+
+    /// A narrow strip showing where in the whole buffer the search matches are.
+    ///
+    /// Each line with a match gets a marker at the same relative height as the line in the buffer.
+    /// Clicking the strip scrolls to the match closest to the click.
+    pub fn minimap_ui(&mut self, ui: &mut Ui) -> Response {
+        let (rect, response) = ui.allocate_exact_size(
+            egui::vec2(MINIMAP_WIDTH, ui.available_height()),
+            Sense::click(),
+        );
+        let line_count = self.buffer.lines().count().max(1);
+        let line_height = rect.height() / line_count as f32;
+        let lines = self.update_search().lines.clone();
+
+        if response.clicked() {
+            if let Some(pos) = response.interact_pointer_pos() {
+                let clicked_line = ((pos.y - rect.top()) / line_height) as usize;
+                let target = lines
+                    .iter()
+                    .copied()
+                    .min_by_key(|line| line.abs_diff(clicked_line))
+                    .unwrap_or(clicked_line);
+                self.scroll_to_line(target.min(line_count - 1));
+            }
+        }
+
+        if ui.is_rect_visible(rect) {
+            let painter = ui.painter_at(rect);
+            painter.rect_filled(rect, 0.0, ui.visuals().faint_bg_color);
+            for line in lines {
+                let top = rect.top() + line as f32 * line_height;
+                let marker = egui::Rect::from_min_size(
+                    egui::pos2(rect.left() + 2.0, top),
+                    egui::vec2(MINIMAP_WIDTH - 4.0, line_height.max(2.0)),
+                );
+                painter.rect_filled(marker, 0.0, self.search_marker_color);
+            }
+        }
+
+        response
+    }
+
+    /// A search bar with toggles for regex and case sensitive search,
+    /// showing the number of matches or why the query is invalid.
+    pub fn search_ui(&mut self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ui.add(
+                egui::TextEdit::singleline(&mut self.search_query)
+                    .hint_text("Find")
+                    .desired_width(200.0),
+            );
+            let mut is_regex = self.search_mode == SearchMode::Regex;
+            if ui
+                .selectable_label(is_regex, ".*")
+                .on_hover_text("Regular expression")
+                .clicked()
+            {
+                is_regex = !is_regex;
+                self.search_mode = if is_regex {
+                    SearchMode::Regex
+                } else {
+                    SearchMode::Plain
+                };
+            }
+            if ui
+                .selectable_label(self.search_match_case, "Aa")
+                .on_hover_text("Match case")
+                .clicked()
+            {
+                self.search_match_case = !self.search_match_case;
+            }
+
+            if self.search_query.is_empty() {
+                return;
+            }
+            match &self.update_search().match_count {
+                Ok(match_count) => {
+                    ui.label(format!("{match_count} matches"));
+                }
+                Err(err) => {
+                    ui.colored_label(ui.visuals().error_fg_color, err);
+                }
+            }
+        });
+    }
+
+    /// Show how the buffer differs from `old`, e.g. the last saved version of the file:
+    /// added and changed lines get a colored background and gutter marker,
+    /// and removed lines a marker where they used to be.
+    ///
+    /// The diff follows the edits to the buffer, see [`Self::line_changes`].
+    pub fn set_diff(&mut self, old: &str) {
+        self.diff = Some(Diff {
+            old: old.to_owned(),
+            buffer_hash: egui::util::hash(&self.buffer),
+            changes: line_changes(old, &self.buffer),
+        });
+    }
+
+    pub fn clear_diff(&mut self) {
+        self.diff = None;
+    }
+
+    /// The zero-based lines that differ from the text set with [`Self::set_diff`].
+    ///
+    /// Updated by [`Self::ui`] after the buffer was edited.
+    pub fn line_changes(&self) -> &BTreeMap<usize, LineChange> {
+        static NO_CHANGES: BTreeMap<usize, LineChange> = BTreeMap::new();
+        self.diff.as_ref().map_or(&NO_CHANGES, |diff| &diff.changes)
+    }
+
+    /// Diff the buffer again if it was edited since the last diff.
+    fn update_diff(&mut self) {
+        if let Some(diff) = &mut self.diff {
+            let buffer_hash = egui::util::hash(&self.buffer);
+            if diff.buffer_hash != buffer_hash {
+                diff.changes = line_changes(&diff.old, &self.buffer);
+                diff.buffer_hash = buffer_hash;
+            }
+        }
+    }
+
+    /// The full-width line backgrounds of [`Self::line_changes`], behind the code in `galley`.
+    fn diff_shapes(
+        &self,
+        galley: &egui::Galley,
+        origin: egui::Pos2,
+        right: f32,
+    ) -> Vec<egui::Shape> {
+        let changes = self.line_changes();
+        if changes.is_empty() {
+            return Vec::new();
+        }
+        let lines = self.visible_lines();
+        let mut shapes = Vec::new();
+        let mut row_index = 0;
+        let mut last_row = None;
+        for row in &galley.rows {
+            let Some(&line) = lines.get(row_index) else {
+                break;
+            };
+            let y_range = row.rect.translate(origin.to_vec2()).y_range();
+            let rect = egui::Rect::from_x_y_ranges(origin.x..=right, y_range);
+            last_row = Some((line, rect));
+            match changes.get(&line) {
+                Some(LineChange::Added) => {
+                    shapes.push(egui::Shape::rect_filled(
+                        rect,
+                        0.0,
+                        ADDED_COLOR.gamma_multiply(0.2),
+                    ));
+                }
+                Some(LineChange::Changed) => {
+                    shapes.push(egui::Shape::rect_filled(
+                        rect,
+                        0.0,
+                        CHANGED_COLOR.gamma_multiply(0.2),
+                    ));
+                }
+                Some(LineChange::Removed) => {
+                    shapes.push(egui::Shape::hline(
+                        rect.x_range(),
+                        rect.top(),
+                        Stroke::new(1.0, REMOVED_COLOR),
+                    ));
+                }
+                None => {}
+            }
+            if row.ends_with_newline {
+                row_index += 1;
+            }
+        }
+
+        // Lines removed at the end are marked at the bottom of the last row:
+        if let Some((last_line, rect)) = last_row.filter(|&(line, _)| lines.last() == Some(&line)) {
+            if changes
+                .range(last_line + 1..)
+                .any(|(_, &change)| change == LineChange::Removed)
+            {
+                shapes.push(egui::Shape::hline(
+                    rect.x_range(),
+                    rect.bottom(),
+                    Stroke::new(1.0, REMOVED_COLOR),
+                ));
+            }
+        }
+        shapes
+    }
+
+    /// Show the gutter and the highlighted code next to each other, in a scroll area.
+    ///
+    /// Files dropped onto the window are loaded with [`Self::load_dropped_file`].
+    pub fn ui(&mut self, ui: &mut Ui) -> CodeEditorOutput {
+        self.dark_mode = ui.visuals().dark_mode;
+        self.update_diff();
+
+        // Leave the shortcuts to text fields (like the search bar) that have focus:
+        if ui.memory(|mem| mem.focused().is_none()) {
+            let redo = egui::KeyboardShortcut::new(
+                egui::Modifiers::COMMAND | egui::Modifiers::SHIFT,
+                egui::Key::Z,
+            );
+            let undo = egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, egui::Key::Z);
+            let comment = egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, egui::Key::Slash);
+            let bracket = egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, egui::Key::M);
+            // Redo first, since its shortcut includes the one for undo:
+            if ui.input_mut(|i| i.consume_shortcut(&redo)) {
+                self.redo();
+            } else if ui.input_mut(|i| i.consume_shortcut(&undo)) {
+                self.undo();
+            } else if ui.input_mut(|i| i.consume_shortcut(&comment)) {
+                self.toggle_comment();
+            } else if ui.input_mut(|i| i.consume_shortcut(&bracket)) {
+                self.jump_to_matching_bracket();
+            }
+        }
+        let dropped_files = ui.input(|i| i.raw.dropped_files.clone());
+        if let Some(file) = dropped_files.first() {
+            self.drop_error = self.load_dropped_file(file).err();
+        }
+        if let Some(drop_error) = &self.drop_error {
+            ui.colored_label(ui.visuals().error_fg_color, drop_error);
+        }
+
+        let mut scroll_area = egui::ScrollArea::vertical()
+            .auto_shrink(false)
+            .scroll_bar_visibility(self.scroll_bar_visibility);
+        let time = ui.input(|i| i.time);
+        if let Some(row) = self.scroll_to_row.take() {
+            let offset = row as f32 * LINE_HEIGHT;
+            if self.smooth_scroll {
+                self.scroll_animation = Some(ScrollAnimation {
+                    from: self.viewport.min,
+                    to: offset,
+                    start_time: time,
+                });
+            } else {
+                scroll_area = scroll_area.vertical_scroll_offset(offset);
+            }
+        }
+        if ui.input(|i| i.smooth_scroll_delta.y != 0.0) {
+            self.scroll_animation = None; // The user takes over
+        }
+        if let Some(animation) = &self.scroll_animation {
+            let t = ((time - animation.start_time) as f32 / SMOOTH_SCROLL_DURATION).clamp(0.0, 1.0);
+            let offset = egui::lerp(
+                animation.from..=animation.to,
+                egui::emath::easing::cubic_out(t),
+            );
+            scroll_area = scroll_area.vertical_scroll_offset(offset);
+            if t < 1.0 {
+                ui.ctx().request_repaint();
+            } else {
+                self.scroll_animation = None;
+            }
+        }
+        let available = ui.available_rect_before_wrap();
+        let (code_rect, minimap_rect) =
+            available.split_left_right_at_x(available.right() - MINIMAP_WIDTH);
+        self.minimap_ui(&mut ui.new_child(egui::UiBuilder::new().max_rect(minimap_rect)));
+        let output = ui
+            .scope_builder(egui::UiBuilder::new().max_rect(code_rect), |ui| {
+                scroll_area.show(ui, |ui| {
+                    ui.horizontal_top(|ui| {
+                        ui.spacing_mut().item_spacing.x = 0.0;
+                        // Highlight first, so the gutter knows about the TODO comments:
+                        let galley = self.galley(ui);
+                        self.gutter_ui(ui);
+                        let diff_background = ui.painter().add(egui::Shape::Noop);
+                        let selection_background = ui.painter().add(egui::Shape::Noop);
+                        let response = ui.add(
+                            egui::Label::new(galley.clone())
+                                .selectable(false)
+                                .sense(Sense::click()),
+                        );
+                        self.handle_selection_clicks(&response, &galley);
+                        if self.buffer.is_empty() && !self.hint_text.is_empty() {
+                            ui.painter().text(
+                                response.rect.min,
+                                egui::Align2::LEFT_TOP,
+                                &self.hint_text,
+                                FontId::monospace(self.font_size),
+                                ui.visuals().weak_text_color(),
+                            );
+                        }
+                        ui.painter().set(
+                            diff_background,
+                            self.diff_shapes(&galley, response.rect.min, ui.max_rect().right()),
+                        );
+                        ui.painter().set(
+                            selection_background,
+                            self.selection_shapes(ui, &galley, response.rect.min),
+                        );
+                        self.diagnostics_tooltip_ui(ui, &response, &galley);
+                        self.last_layout = Some((galley, response.rect.min));
+                    });
+                })
+            })
+            .inner;
+
+        self.viewport = egui::Rangef::new(
+            output.state.offset.y,
+            output.state.offset.y + output.inner_rect.height(),
+        );
+
+        if self.sticky_scroll {
+            let top_row = (output.state.offset.y / LINE_HEIGHT) as usize;
+            if let Some(&top_line) = self.visible_lines().get(top_row) {
+                self.paint_sticky_headers(ui, output.inner_rect, top_line);
+            }
+        }
+
+        CodeEditorOutput {
+            changed_range: self
+                .buffer_before_edits
+                .take()
+                .and_then(|before| changed_byte_range(&before, &self.buffer)),
+        }
+    }
+
+    fn paint_sticky_headers(&self, ui: &Ui, viewport: egui::Rect, top_line: usize) {
+        let headers = self.sticky_header_lines(top_line);
+        if headers.is_empty() {
+            return;
+        }
+
+        let painter = ui.painter_at(viewport);
+        let font_id = FontId::monospace(self.font_size);
+        let lines: Vec<&str> = self.buffer.lines().collect();
+        for (row, &line) in headers.iter().enumerate() {
+            let rect = egui::Rect::from_min_size(
+                viewport.left_top() + egui::vec2(0.0, row as f32 * LINE_HEIGHT),
+                egui::vec2(viewport.width(), LINE_HEIGHT),
+            );
+            painter.rect_filled(rect, 0.0, ui.visuals().extreme_bg_color);
+            painter.text(
+                egui::pos2(rect.left() + GUTTER_WIDTH - 4.0, rect.center().y),
+                egui::Align2::RIGHT_CENTER,
+                (line + 1).to_string(),
+                font_id.clone(),
+                Color32::GRAY,
+            );
+            painter.text(
+                egui::pos2(rect.left() + GUTTER_WIDTH, rect.center().y),
+                egui::Align2::LEFT_CENTER,
+                lines[line].trim_end(),
+                font_id.clone(),
+                ui.visuals().text_color(),
+            );
+        }
+
+        let bottom = viewport.top() + headers.len() as f32 * LINE_HEIGHT;
+        painter.hline(
+            viewport.x_range(),
+            bottom,
+            ui.visuals().widgets.noninteractive.bg_stroke,
+        );
+    }
+
+    /// Show line numbers, breakpoint dots and mixed indentation markers.
+    ///
+    /// Clicking a line in the gutter toggles its breakpoint,
+    /// unless disabled with [`Self::set_breakpoints_enabled`].
+    pub fn gutter_ui(&mut self, ui: &mut Ui) -> Response {
+        let rows = self.visible_lines();
+        let sense = if self.breakpoints_enabled {
+            Sense::click()
+        } else {
+            Sense::hover()
+        };
+        let (rect, response) = ui.allocate_exact_size(
+            egui::vec2(GUTTER_WIDTH, rows.len() as f32 * LINE_HEIGHT),
+            sense,
+        );
+
+        if response.clicked() {
+            if let Some(pos) = response.interact_pointer_pos() {
+                let row = ((pos.y - rect.top()) / LINE_HEIGHT) as usize;
+                if let Some(&line) = rows.get(row) {
+                    self.toggle_breakpoint(line);
+                }
+            }
+        }
+
+        if ui.is_rect_visible(rect) {
+            let painter = ui.painter_at(rect);
+            let font_id = FontId::monospace(self.font_size);
+            let dot_x = rect.left() + LINE_HEIGHT / 2.0;
+            if self.flag_mixed_indent {
+                for line in self.mixed_indent_lines() {
+                    let Ok(row) = rows.binary_search(&line) else {
+                        continue;
+                    };
+                    let top = rect.top() + row as f32 * LINE_HEIGHT;
+                    let marker = egui::Rect::from_min_max(
+                        egui::pos2(rect.right() - 2.0, top + 2.0),
+                        egui::pos2(rect.right(), top + LINE_HEIGHT - 2.0),
+                    );
+                    painter.rect_filled(marker, 0.0, MIXED_INDENT_COLOR);
+                }
+            }
+            for line in self.todo_lines() {
+                let Ok(row) = rows.binary_search(&line) else {
+                    continue;
+                };
+                let top = rect.top() + row as f32 * LINE_HEIGHT;
+                let marker = egui::Rect::from_min_max(
+                    egui::pos2(rect.left(), top + 2.0),
+                    egui::pos2(rect.left() + 2.0, top + LINE_HEIGHT - 2.0),
+                );
+                painter.rect_filled(marker, 0.0, TODO_COLOR);
+            }
+            for (&line, &change) in self.line_changes() {
+                let top = match rows.iter().position(|&row_line| row_line >= line) {
+                    Some(row) => rect.top() + row as f32 * LINE_HEIGHT,
+                    // Lines removed at the end are marked at the bottom of the last row:
+                    None if change == LineChange::Removed => {
+                        rect.top() + rows.len() as f32 * LINE_HEIGHT - 2.0
+                    }
+                    None => continue,
+                };
+                let (color, height) = match change {
+                    LineChange::Added => (ADDED_COLOR, LINE_HEIGHT),
+                    LineChange::Changed => (CHANGED_COLOR, LINE_HEIGHT),
+                    LineChange::Removed => (REMOVED_COLOR, 2.0),
+                };
+                painter.rect_filled(
+                    egui::Rect::from_min_size(
+                        egui::pos2(rect.left() + 3.0, top),
+                        egui::vec2(3.0, height),
+                    ),
+                    0.0,
+                    color,
+                );
+            }
+            for (row, &line) in rows.iter().enumerate() {
+                let center_y = rect.top() + (row as f32 + 0.5) * LINE_HEIGHT;
+                if self.breakpoints.contains(&line) {
+                    painter.circle_filled(
+                        egui::pos2(dot_x, center_y),
+                        LINE_HEIGHT / 4.0,
+                        Color32::from_rgb(220, 50, 50),
+                    );
+                }
+                painter.text(
+                    egui::pos2(rect.right() - 4.0, center_y),
+                    egui::Align2::RIGHT_CENTER,
+                    (line + 1).to_string(),
+                    font_id.clone(),
+                    Color32::GRAY,
+                );
+                if self.folds.iter().any(|fold| fold.start == line + 1) {
+                    painter.text(
+                        egui::pos2(dot_x + LINE_HEIGHT / 2.0, center_y),
+                        egui::Align2::LEFT_CENTER,
+                        "…",
+                        font_id.clone(),
+                        Color32::GRAY,
+                    );
+                }
+            }
+        }
+
+        response
+    }
+
+    /// The colors of the diagnostics, overriding the preset for the current visuals.
+    ///
+    /// Pass `None` to go back to [`DiagnosticTheme::dark`] or [`DiagnosticTheme::light`],
+    /// depending on the visuals of the [`Ui`].
+    pub fn set_diagnostic_theme(&mut self, diagnostic_theme: Option<DiagnosticTheme>) {
+        self.diagnostic_theme = diagnostic_theme;
+    }
+
+    /// The colors the diagnostics are shown with.
+    pub fn diagnostic_theme(&self) -> DiagnosticTheme {
+        self.diagnostic_theme.unwrap_or_else(|| {
+            if self.dark_mode {
+                DiagnosticTheme::dark()
+            } else {
+                DiagnosticTheme::light()
+            }
+        })
+    }
+
+    /// The diagnostics for the buffer, e.g. from a compiler.
+    pub fn errors(&self) -> &[CodeError] {
+        &self.error_list
+    }
+
+    pub fn set_errors(&mut self, errors: Vec<CodeError>) {
+        self.error_list = errors;
+    }
+
+    /// The diagnostics whose [`CodeError::range`] covers the given byte offset, most severe first.
+    pub fn diagnostics_at(&self, offset: usize) -> Vec<&CodeError> {
+        let mut diagnostics: Vec<&CodeError> = self
+            .error_list
+            .iter()
+            .filter(|error| error.range().is_some_and(|range| range.contains(&offset)))
+            .collect();
+        diagnostics.sort_by_key(|error| error.error_type());
+        diagnostics
+    }
+
+    /// Map a byte offset into the text with the folded lines removed back to the buffer.
+    fn unfolded_offset(&self, mut offset: usize) -> usize {
+        for range in self.folded_byte_ranges() {
+            if range.start <= offset {
+                offset += range.len();
+            }
+        }
+        offset
+    }
+
+    /// Show the messages of all diagnostics under the pointer, stacked in one tooltip below the squiggle.
+    fn diagnostics_tooltip_ui(&self, ui: &Ui, response: &Response, galley: &egui::Galley) {
+        let Some(pointer) = response.hover_pos() else {
+            return;
+        };
+        let origin = response.rect.min;
+        let Some(index) = glyph_at(galley, pointer - origin) else {
+            return;
+        };
+        let Some(offset) = galley.text().char_indices().nth(index).map(|(i, _)| i) else {
+            return;
+        };
+        let diagnostics = self.diagnostics_at(self.unfolded_offset(offset));
+        let Some(most_severe) = diagnostics.first() else {
+            return;
+        };
+
+        let theme = self.diagnostic_theme();
+        let text_color = ui.visuals().strong_text_color();
+        let mut job = LayoutJob::default();
+        for (i, error) in diagnostics.iter().enumerate() {
+            let leading_newline = if i == 0 { "" } else { "\n" };
+            job.append(
+                &format!("{leading_newline}{} ", error.error_type().icon()),
+                0.0,
+                TextFormat::simple(
+                    FontId::proportional(self.font_size),
+                    theme.colors(error.error_type()).gutter_marker,
+                ),
+            );
+            job.append(
+                error.message(),
+                0.0,
+                TextFormat::simple(FontId::proportional(self.font_size), text_color),
+            );
+        }
+        let tooltip_galley = ui.fonts(|fonts| fonts.layout_job(job));
+
+        let glyph = galley
+            .pos_from_ccursor(egui::text::CCursor::new(index))
+            .union(galley.pos_from_ccursor(egui::text::CCursor::new(index + 1)))
+            .translate(origin.to_vec2());
+        let anchor =
+            egui::Rect::from_x_y_ranges(glyph.x_range(), glyph.top()..=glyph.top() + LINE_HEIGHT);
+        let padding = egui::vec2(6.0, 4.0);
+        let rect = tooltip_rect(
+            anchor,
+            tooltip_galley.size() + 2.0 * padding,
+            ui.ctx().screen_rect(),
+        );
+
+        let painter = ui.ctx().layer_painter(egui::LayerId::new(
+            egui::Order::Tooltip,
+            response.id.with("diagnostics_tooltip"),
+        ));
+        painter.rect(
+            rect,
+            ui.visuals().menu_rounding,
+            theme.colors(most_severe.error_type()).tooltip_background,
+            ui.visuals().window_stroke,
+        );
+        painter.galley(rect.min + padding, tooltip_galley, text_color);
+    }
+
+    /// The text being edited.
+    pub fn buffer(&self) -> &str {
+        &self.buffer
+    }
+
+    /// The selected byte range of the buffer, if any.
+    ///
+    /// Double-clicking selects a word, see [`Self::word_range_at`],
+    /// and triple-clicking a line, see [`Self::line_range_at`].
+    pub fn selection(&self) -> Option<std::ops::Range<usize>> {
+        self.selection.clone()
+    }
+
+    pub fn set_selection(&mut self, selection: Option<std::ops::Range<usize>>) {
+        self.selection = selection;
+    }
+
+    /// The byte range of the word, run of punctuation or run of whitespace at the given offset.
+    ///
+    /// Words follow the Unicode word boundaries (UAX #29), so `foo_bar` and `größe` are one word,
+    /// but are also split at punctuation inside them, so `foo.bar` is two words, like in most code editors.
+    pub fn word_range_at(&self, offset: usize) -> std::ops::Range<usize> {
+        use unicode_segmentation::UnicodeSegmentation as _;
+
+        let offset = offset.min(self.buffer.len());
+        let Some((start, segment)) = self
+            .buffer
+            .split_word_bound_indices()
+            .find(|(start, segment)| offset < start + segment.len())
+            .or_else(|| self.buffer.split_word_bound_indices().last())
+        else {
+            return offset..offset;
+        };
+
+        // Narrow down to the characters of the same kind around the offset:
+        let kind = |c: char| (c.is_alphanumeric() || c == '_', c.is_whitespace());
+        let at = (offset - start).min(segment.len().saturating_sub(1));
+        let Some(target) = segment[at..].chars().next().map(kind) else {
+            return start..start;
+        };
+        let begin = segment[..at]
+            .char_indices()
+            .rev()
+            .take_while(|&(_, c)| kind(c) == target)
+            .last()
+            .map_or(at, |(i, _)| i);
+        let end = segment[at..]
+            .char_indices()
+            .find(|&(_, c)| kind(c) != target)
+            .map_or(segment.len(), |(i, _)| at + i);
+        start + begin..start + end
+    }
+
+    /// The byte range of the line at the given offset, including its line break.
+    pub fn line_range_at(&self, offset: usize) -> std::ops::Range<usize> {
+        let offset = offset.min(self.buffer.len());
+        let start = self.buffer[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let end = self.buffer[offset..]
+            .find('\n')
+            .map_or(self.buffer.len(), |i| offset + i + 1);
+        start..end
+    }
+
+    /// The byte offset of the bracket matching the one at `offset`,
+    /// or else the one just before `offset`, like a cursor touching either of them.
+    ///
+    /// `()`, `[]` and `{}` are matched by nesting depth. Returns `None` if there is no bracket
+    /// next to `offset`, or it is unbalanced.
+    pub fn matching_bracket(&self, offset: usize) -> Option<usize> {
+        const PAIRS: [(u8, u8); 3] = [(b'(', b')'), (b'[', b']'), (b'{', b'}')];
+        let bytes = self.buffer.as_bytes();
+        let is_bracket = |i: &usize| {
+            bytes
+                .get(*i)
+                .is_some_and(|b| PAIRS.iter().any(|&(open, close)| *b == open || *b == close))
+        };
+        let at = Some(offset)
+            .filter(is_bracket)
+            .or_else(|| offset.checked_sub(1).filter(is_bracket))?;
+
+        let bracket = bytes[at];
+        let (open, close) = *PAIRS
+            .iter()
+            .find(|&&(open, close)| bracket == open || bracket == close)?;
+        let mut depth = 0_usize;
+        let mut step = |i: usize| {
+            if bytes[i] == open || bytes[i] == close {
+                if bytes[i] == bracket {
+                    depth += 1;
+                } else {
+                    depth -= 1;
+                }
+            }
+            depth == 0
+        };
+        if bracket == open {
+            (at..bytes.len()).find(|&i| step(i))
+        } else {
+            (0..=at).rev().find(|&i| step(i))
+        }
+    }
+
+    /// Move the cursor (the end of [`Self::selection`]) to the bracket matching the one next to it,
+    /// see [`Self::matching_bracket`], unfolding its line if needed.
+    ///
+    /// Returns `false`, and leaves the cursor where it is, if there is no matching bracket.
+    /// In [`Self::ui`], this is also done with Ctrl+M (⌘M on Mac).
+    pub fn jump_to_matching_bracket(&mut self) -> bool {
+        let Some(cursor) = self.selection.as_ref().map(|selection| selection.end) else {
+            return false;
+        };
+        let Some(partner) = self.matching_bracket(cursor) else {
+            return false;
+        };
+        self.unfold(self.offset_to_line_col(partner).0);
+        self.selection = Some(partner..partner);
+        true
+    }
+
+    /// The syntax scopes at the cursor, e.g. `"source.rust meta.function.rust"`,
+    /// for showing in a status bar while writing a theme. See [`SyntaxHighlighter::scope_at`].
+    ///
+    /// The cursor is the end of the selection. `None` without a selection,
+    /// or if the buffer isn't syntax highlighted (see [`Self::highlight_mode`]).
+    pub fn scope_at_cursor(&self) -> Option<String> {
+        let cursor = self.selection.as_ref()?.end;
+        if self.highlight_mode() == HighlightMode::Plain {
+            return None;
+        }
+        self.syntax_highlighter.scope_at(&self.buffer, cursor)
+    }
+
+    /// Map a byte offset into the buffer to the text with the folded lines removed.
+    ///
+    /// Offsets inside a fold map to where the fold starts.
+    fn folded_offset(&self, offset: usize) -> usize {
+        let mut removed = 0;
+        for range in self.folded_byte_ranges() {
+            if range.start >= offset {
+                break;
+            }
+            removed += range.len().min(offset - range.start);
+        }
+        offset - removed
+    }
+
+    /// Select a word on double-click and a line on triple-click in the code `response`.
+    fn handle_selection_clicks(&mut self, response: &Response, galley: &egui::Galley) {
+        let triple = response.triple_clicked();
+        if !triple && !response.double_clicked() {
+            return;
+        }
+        let Some(pointer) = response.interact_pointer_pos() else {
+            return;
+        };
+        let Some(index) = glyph_at(galley, pointer - response.rect.min) else {
+            return;
+        };
+        let Some(offset) = galley.text().char_indices().nth(index).map(|(i, _)| i) else {
+            return;
+        };
+        let offset = self.unfolded_offset(offset);
+        self.selection = Some(if triple {
+            self.line_range_at(offset)
+        } else {
+            self.word_range_at(offset)
+        });
+    }
+
+    /// The selection background for the code in `galley` at `origin`.
+    fn selection_shapes(
+        &self,
+        ui: &Ui,
+        galley: &egui::Galley,
+        origin: egui::Pos2,
+    ) -> Vec<egui::Shape> {
+        let Some(selection) = &self.selection else {
+            return Vec::new();
+        };
+        // Selected line breaks are shown as a little extra space:
+        self.galley_range_rects(galley, selection.clone(), 0.5 * self.font_size)
+            .into_iter()
+            .map(|rect| {
+                egui::Shape::rect_filled(
+                    rect.translate(origin.to_vec2()),
+                    0.0,
+                    ui.visuals().selection.bg_fill,
+                )
+            })
+            .collect()
+    }
+
+    /// The on-screen rectangles covering the given byte range of the buffer, one per row,
+    /// as painted by the last [`Self::ui`]. Empty before the first [`Self::ui`].
+    ///
+    /// Use this to put squiggles, highlights or popovers over a piece of the code.
+    /// Rows outside the scrolled viewport are included too, and folded code is left out.
+    pub fn range_rects(&self, range: std::ops::Range<usize>) -> Vec<egui::Rect> {
+        let Some((galley, origin)) = &self.last_layout else {
+            return Vec::new();
+        };
+        self.galley_range_rects(galley, range, 0.0)
+            .into_iter()
+            .map(|rect| rect.translate(origin.to_vec2()))
+            .collect()
+    }
+
+    /// The rectangles covering the given byte range of the buffer in `galley`, one per row,
+    /// relative to the galley. A line break in the range adds `newline_width` to its row.
+    fn galley_range_rects(
+        &self,
+        galley: &egui::Galley,
+        range: std::ops::Range<usize>,
+        newline_width: f32,
+    ) -> Vec<egui::Rect> {
+        let text = galley.text();
+        let char_index = |offset: usize| text[..offset.min(text.len())].chars().count();
+        let start = char_index(self.folded_offset(range.start));
+        let end = char_index(self.folded_offset(range.end));
+
+        let mut rects = Vec::new();
+        let mut row_start = 0;
+        for row in &galley.rows {
+            let row_end = row_start + row.glyphs.len() + usize::from(row.ends_with_newline);
+            let (from, to) = (start.max(row_start), end.min(row_end));
+            if from < to {
+                let left = row.x_offset(from - row_start);
+                let right = if to > row_start + row.glyphs.len() {
+                    row.rect.right() + newline_width
+                } else {
+                    row.x_offset(to - row_start)
+                };
+                rects.push(egui::Rect::from_x_y_ranges(
+                    left..=right,
+                    row.rect.y_range(),
+                ));
+            }
+            row_start = row_end;
+        }
+        rects
+    }
+
+    /// Convert a byte offset into the buffer to a zero-based `(line, column)`,
+    /// where the column counts `char`s, not bytes.
+    ///
+    /// Offsets past the end are clamped to the end of the buffer,
+    /// and offsets inside a multi-byte character to the start of that character.
+    pub fn offset_to_line_col(&self, offset: usize) -> (usize, usize) {
+        let mut offset = offset.min(self.buffer.len());
+        while !self.buffer.is_char_boundary(offset) {
+            offset -= 1;
+        }
+        let before = &self.buffer[..offset];
+        let line = before.matches('\n').count();
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        (line, before[line_start..].chars().count())
+    }
+
+    /// Convert a zero-based `(line, column)`, with the column counting `char`s,
+    /// to a byte offset into the buffer.
+    ///
+    /// Lines past the end are clamped to the last line,
+    /// and columns past the end of the line to the end of the line (before its line break).
+    pub fn line_col_to_offset(&self, (line, column): (usize, usize)) -> usize {
+        let mut line_start = 0;
+        for _ in 0..line {
+            match self.buffer[line_start..].find('\n') {
+                Some(i) => line_start += i + 1,
+                None => break,
+            }
+        }
+        let rest = &self.buffer[line_start..];
+        let line_text = rest.split('\n').next().unwrap_or_default();
+        let line_text = line_text.strip_suffix('\r').unwrap_or(line_text);
+        line_start
+            + line_text
+                .char_indices()
+                .nth(column)
+                .map_or(line_text.len(), |(i, _)| i)
+    }
+
+    /// The buffer as a [`LayoutJob`], highlighted and without the folded lines.
+    pub fn create_layout_job(&mut self) -> LayoutJob {
+        let job = self.create_full_layout_job();
+        let job = underline_diagnostics(job, &self.error_list, &self.diagnostic_theme());
+        remove_byte_ranges(job, &self.folded_byte_ranges())
+    }
+
+    /// The buffer laid out like in [`Self::ui`]: highlighted and without the folded lines.
+    ///
+    /// Use this to embed the highlighted code in other layouts, e.g. in a tooltip or a table.
+    /// Unchanged lines keep their cached highlighting, and egui caches the layout,
+    /// so this is cheap to call every frame.
+    pub fn galley(&mut self, ui: &Ui) -> Arc<egui::Galley> {
+        let job = self.create_layout_job();
+        ui.fonts(|fonts| fonts.layout_job(job))
+    }
+
+    /// The whole buffer as a single monospace section, without any highlighting.
+    pub fn plain_layout_job(&self) -> LayoutJob {
+        LayoutJob::single_section(
+            self.buffer.clone(),
+            TextFormat::simple(FontId::monospace(self.font_size), Color32::GRAY),
+        )
+    }
+
+    /// Show the gutter next to the buffer as plain monospace text, skipping the highlighter.
+    ///
+    /// A fallback for when highlighting is unavailable or too slow.
+    /// Unlike [`Self::ui`], this doesn't add a scroll area.
+    pub fn render_plain(&mut self, ui: &mut Ui) -> Response {
+        let job = remove_byte_ranges(self.plain_layout_job(), &self.folded_byte_ranges());
+        ui.horizontal_top(|ui| {
+            ui.spacing_mut().item_spacing.x = 0.0;
+            self.gutter_ui(ui);
+            ui.label(job)
+        })
+        .inner
+    }
+
+    fn create_full_layout_job(&mut self) -> LayoutJob {
+        if self.highlight_mode() == HighlightMode::Plain {
+            return self.plain_layout_job();
+        }
+
+        let underline_color = self.diagnostic_theme().error.underline;
+        let Self {
+            buffer,
+            syntax_highlighter,
+            font_size,
+            ..
+        } = self;
+        let mut job = LayoutJob::default();
+        let spans = syntax_highlighter.highlight_text(buffer.as_str());
+        job.text = buffer.clone();
+
+        for span in spans.unwrap_or(Vec::new()) {
+            // Add the highlighted span
+            let format = TextFormat {
+                font_id: FontId::monospace(*font_size),
+                color: span.style.color,
+                background: span.style.background_color.unwrap_or(Color32::TRANSPARENT),
+                wave_underline: if span.style.wave_underline {
+                    Stroke::new(1., underline_color)
+                } else {
+                    Stroke::NONE
+                },
+                italics: span.style.italics,
+                underline: if span.style.italics {
+                    Stroke::new(1., span.style.color)
+                } else {
+                    Stroke::NONE
+                },
+                line_height: Some(LINE_HEIGHT),
+                valign: Align::Center,
+                ..Default::default()
+            };
+
+            job.sections.push(LayoutSection {
+                leading_space: 0.0,
+                byte_range: span.range,
+                format,
+            });
+        }
+        job
+    }
+}
+
+impl Default for CodeEditor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The char index of the glyph at `pos` (relative to the galley), if the position is on a glyph.
+fn glyph_at(galley: &egui::Galley, pos: egui::Vec2) -> Option<usize> {
+    let cursor = galley.cursor_from_pos(pos);
+    if !galley.rows[cursor.rcursor.row].rect.contains(pos.to_pos2()) {
+        return None; // Past the end of the line
+    }
+    // The cursor is in front of the nearest glyph boundary; we want the glyph under the pointer.
+    let mut index = cursor.ccursor.index;
+    if index > 0 && galley.pos_from_ccursor(cursor.ccursor).min.x > pos.x {
+        index -= 1;
+    }
+    Some(index)
+}
+
+/// Where to show a tooltip of the given size for the `anchor` (e.g. a squiggle).
+///
+/// The tooltip goes below the anchor, or above it if there is no room below,
+/// and is clamped to stay within the `screen`.
+fn tooltip_rect(anchor: egui::Rect, size: egui::Vec2, screen: egui::Rect) -> egui::Rect {
+    let mut pos = anchor.left_bottom();
+    if pos.y + size.y > screen.bottom() && anchor.top() - size.y >= screen.top() {
+        pos.y = anchor.top() - size.y;
+    }
+    pos.x = pos.x.min(screen.right() - size.x).max(screen.left());
+    pos.y = pos.y.min(screen.bottom() - size.y).max(screen.top());
+    egui::Rect::from_min_size(pos, size)
+}
+
+/// Give the parts of `job` covered by a diagnostic a wavy underline in the color of its severity.
+///
+/// Where diagnostics overlap, the most severe one picks the color.
+fn underline_diagnostics(
+    mut job: LayoutJob,
+    errors: &[CodeError],
+    theme: &DiagnosticTheme,
+) -> LayoutJob {
+    let ranges: Vec<(std::ops::Range<usize>, ErrorType)> = errors
+        .iter()
+        .filter_map(|error| Some((error.range()?, error.error_type())))
+        .filter(|(range, _)| !range.is_empty())
+        .collect();
+    if ranges.is_empty() {
+        return job;
+    }
+
+    let mut boundaries: Vec<usize> = ranges
+        .iter()
+        .flat_map(|(range, _)| [range.start, range.end])
+        .collect();
+    boundaries.sort_unstable();
+    boundaries.dedup();
+
+    let mut sections = Vec::with_capacity(job.sections.len());
+    for section in &job.sections {
+        let mut start = section.byte_range.start;
+        let cuts = boundaries
+            .iter()
+            .copied()
+            .filter(|&cut| section.byte_range.start < cut && cut < section.byte_range.end)
+            .chain([section.byte_range.end]);
+        for end in cuts {
+            let mut piece = LayoutSection {
+                byte_range: start..end,
+                ..section.clone()
+            };
+            if let Some(error_type) = ranges
+                .iter()
+                .filter(|(range, _)| range.contains(&start))
+                .map(|(_, error_type)| *error_type)
+                .min()
+            {
+                piece.format.wave_underline = Stroke::new(1., theme.colors(error_type).underline);
+            }
+            sections.push(piece);
+            start = end;
+        }
+    }
+    job.sections = sections;
+    job
+}
+
+/// The range of `new` between the start and end that it has in common with `old`,
+/// or `None` if they are the same.
+fn changed_byte_range(old: &str, new: &str) -> Option<std::ops::Range<usize>> {
+    if old == new {
+        return None;
+    }
+    let mut prefix = old
+        .bytes()
+        .zip(new.bytes())
+        .take_while(|(a, b)| a == b)
+        .count();
+    while !new.is_char_boundary(prefix) {
+        prefix -= 1;
+    }
+    let max_suffix = old.len().min(new.len()) - prefix;
+    let suffix = old
+        .bytes()
+        .rev()
+        .zip(new.bytes().rev())
+        .take(max_suffix)
+        .take_while(|(a, b)| a == b)
+        .count();
+    let mut end = new.len() - suffix;
+    while !new.is_char_boundary(end) {
+        end += 1;
+    }
+    Some(prefix..end)
+}
+
+/// Remove the given sorted, non-overlapping byte ranges from the text of `job`,
+/// adjusting the sections to match.
+fn remove_byte_ranges(mut job: LayoutJob, removed: &[std::ops::Range<usize>]) -> LayoutJob {
+    if removed.is_empty() {
+        return job;
+    }
+
+    let mut kept = Vec::with_capacity(removed.len() + 1);
+    let mut start = 0;
+    for range in removed {
+        kept.push(start..range.start);
+        start = range.end;
+    }
+    kept.push(start..job.text.len());
+
+    let mut text = String::with_capacity(job.text.len());
+    let mut sections = Vec::with_capacity(job.sections.len());
+    for range in &kept {
+        let offset = text.len();
+        text.push_str(&job.text[range.clone()]);
+        for section in &job.sections {
+            let start = section.byte_range.start.max(range.start);
+            let end = section.byte_range.end.min(range.end);
+            if start < end {
+                sections.push(LayoutSection {
+                    byte_range: offset + start - range.start..offset + end - range.start,
+                    ..section.clone()
+                });
+            }
+        }
+    }
+    job.text = text;
+    job.sections = sections;
+    job
+}
+
+fn as_byte_range(whole: &str, range: &str) -> std::ops::Range<usize> {
+    let whole_start = whole.as_ptr() as usize;
+    let range_start = range.as_ptr() as usize;
+    assert!(whole_start <= range_start);
+    assert!(range_start + range.len() <= whole_start + whole.len());
+    let offset = range_start - whole_start;
+    offset..(offset + range.len())
+}
+
+/// The byte ranges of `line` holding one of `keywords` as a whole word inside a comment.
+///
+/// `scopes` is the scope stack at the start of the line, `ops` the parse of the line.
+fn find_todo_keywords(
+    line: &str,
+    ops: &[(usize, ScopeStackOp)],
+    mut scopes: ScopeStack,
+    keywords: &[String],
+) -> Vec<std::ops::Range<usize>> {
+    let comment = Scope::new("comment").expect("valid scope");
+    let is_word_byte = |b: u8| b.is_ascii_alphanumeric() || b == b'_';
+
+    let mut ranges = Vec::new();
+    for (range, op) in ScopeRangeIterator::new(ops, line) {
+        if scopes.apply(op).is_err() {
+            break;
+        }
+        let in_comment = scopes
+            .as_slice()
+            .iter()
+            .any(|scope| comment.is_prefix_of(*scope));
+        if range.is_empty() || !in_comment {
+            continue;
+        }
+
+        let text = &line[range.clone()];
+        for keyword in keywords.iter().filter(|keyword| !keyword.is_empty()) {
+            for (start, _) in text.match_indices(keyword.as_str()) {
+                let end = start + keyword.len();
+                let bytes = text.as_bytes();
+                let word_start = start == 0 || !is_word_byte(bytes[start - 1]);
+                let word_end = end == text.len() || !is_word_byte(bytes[end]);
+                if word_start && word_end {
+                    ranges.push(range.start + start..range.start + end);
+                }
+            }
+        }
+    }
+    ranges.sort_by_key(|range| range.start);
+    ranges
+}
+
+/// Split `sections` so that each of the sorted, non-overlapping `keywords` ranges
+/// is its own span, styled as a TODO keyword.
+fn emphasize_todo_keywords(sections: Vec<Span>, keywords: &[std::ops::Range<usize>]) -> Vec<Span> {
+    if keywords.is_empty() {
+        return sections;
+    }
+
+    let mut emphasized = Vec::with_capacity(sections.len() + 2 * keywords.len());
+    for span in sections {
+        let mut start = span.range.start;
+        for keyword in keywords {
+            if keyword.end <= start || span.range.end <= keyword.start {
+                continue;
+            }
+            if start < keyword.start {
+                emphasized.push(Span {
+                    style: span.style.clone(),
+                    range: start..keyword.start,
+                });
+            }
+            let end = keyword.end.min(span.range.end);
+            emphasized.push(Span {
+                style: SpanStyle {
+                    color: TODO_COLOR,
+                    background_color: Some(TODO_BACKGROUND_COLOR),
+                    ..span.style.clone()
+                },
+                range: start.max(keyword.start)..end,
+            });
+            start = end;
+        }
+        if start < span.range.end {
+            emphasized.push(Span {
+                style: span.style,
+                range: start..span.range.end,
+            });
+        }
+    }
+    emphasized
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Run one frame of `ctx` with `input`, showing `add_contents` in a central panel.
+    fn run_ui(
+        ctx: &egui::Context,
+        input: egui::RawInput,
+        mut add_contents: impl FnMut(&mut Ui),
+    ) -> egui::FullOutput {
+        ctx.run(input, |ctx| {
+            egui::CentralPanel::default().show(ctx, |ui| add_contents(ui));
+        })
+    }
+
+    /// Run one frame of `ctx` showing `editor`, with the given input `events`.
+    fn run_editor(
+        ctx: &egui::Context,
+        editor: &mut CodeEditor,
+        events: Vec<egui::Event>,
+    ) -> egui::FullOutput {
+        let input = egui::RawInput {
+            events,
+            ..Default::default()
+        };
+        run_ui(ctx, input, |ui| {
+            editor.ui(ui);
+        })
+    }
+
+    /// The text shapes painted in `output`.
+    fn texts(output: &egui::FullOutput) -> Vec<&egui::epaint::TextShape> {
+        output
+            .shapes
+            .iter()
+            .filter_map(|clipped| match &clipped.shape {
+                egui::Shape::Text(text) => Some(text),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn huge_buffer_is_not_highlighted() {
+        let mut editor = CodeEditor::new();
+        editor.set_max_highlight_bytes(16);
+        editor.load("fn main() {\n    println!(\"Hello world!\");\n}\n");
+        assert_eq!(editor.highlight_mode(), HighlightMode::Plain);
+
+        let job = editor.create_layout_job();
+        assert_eq!(job.sections.len(), 1);
+        assert_eq!(job.sections[0].byte_range, 0..editor.buffer.len());
+
+        editor.set_max_highlight_bytes(DEFAULT_MAX_HIGHLIGHT_BYTES);
+        assert_eq!(editor.highlight_mode(), HighlightMode::Syntax);
+        let job = editor.create_layout_job();
+        assert!(job.sections.len() > 1);
+        assert_eq!(job.validate(), Ok(()));
+    }
+
+    #[test]
+    fn crlf_is_preserved_through_edits() {
+        let mut editor = CodeEditor::new();
+        editor.load("fn main() {\r\n}\r\n");
+        assert_eq!(editor.line_ending(), LineEnding::CrLf);
+
+        let end_of_first_line = editor.buffer().find("\r\n").unwrap() + 2;
+        editor.insert(end_of_first_line, "    let x = 1;\n");
+        assert_eq!(editor.buffer(), "fn main() {\r\n    let x = 1;\r\n}\r\n");
+        assert_eq!(editor.line_ending(), LineEnding::CrLf);
+    }
+
+    #[test]
+    fn typing_is_undone_as_one_step() {
+        let mut editor = CodeEditor::new();
+        editor.load("fn main() {}\n");
+        let start = "fn main() {".len();
+        for (i, c) in "let".chars().enumerate() {
+            editor.insert(start + i, &c.to_string());
+        }
+        editor.insert(start + 3, "\n");
+        editor.insert(start + 4, "x");
+        editor.insert(start + 5, " = 42;");
+        assert_eq!(editor.buffer(), "fn main() {let\nx = 42;}\n");
+
+        assert!(editor.undo());
+        assert_eq!(editor.buffer(), "fn main() {let\nx}\n");
+        assert!(editor.undo());
+        assert_eq!(editor.buffer(), "fn main() {let\n}\n");
+        assert!(editor.undo());
+        assert_eq!(editor.buffer(), "fn main() {let}\n");
+        assert!(editor.undo(), "Typing 'let' is a single step");
+        assert_eq!(editor.buffer(), "fn main() {}\n");
+        assert!(!editor.undo());
+
+        assert!(editor.redo());
+        assert_eq!(editor.buffer(), "fn main() {let}\n");
+
+        // A new edit forgets what was undone:
+        editor.insert(0, "// ");
+        assert!(!editor.can_redo());
+    }
+
+    #[test]
+    fn multi_cursor_edit_is_undone_atomically() {
+        let mut editor = CodeEditor::new();
+        editor.load("let a = 1;\nlet b = 2;\nlet c = 3;\n");
+        let original = editor.buffer().to_owned();
+
+        // Rename `a`, `b`, and `c` with a cursor on each line:
+        let cursors: Vec<_> = ["a", "b", "c"]
+            .iter()
+            .map(|name| {
+                let start = original.find(&format!("let {name}")).unwrap() + 4;
+                (start..start + 1, format!("{name}_renamed"))
+            })
+            .collect();
+        editor.replace_ranges(cursors);
+        assert_eq!(
+            editor.buffer(),
+            "let a_renamed = 1;\nlet b_renamed = 2;\nlet c_renamed = 3;\n"
+        );
+
+        assert!(editor.undo());
+        assert_eq!(editor.buffer(), original);
+        assert!(!editor.can_undo());
+
+        assert!(editor.redo());
+        assert_eq!(
+            editor.buffer(),
+            "let a_renamed = 1;\nlet b_renamed = 2;\nlet c_renamed = 3;\n"
+        );
+    }
+
+    #[test]
+    fn gutter_click_toggles_breakpoint() {
+        use std::{cell::RefCell, rc::Rc};
+
+        let toggled = Rc::new(RefCell::new(Vec::new()));
+        let mut editor = CodeEditor::new();
+        editor.load("fn main() {\n    let x = 1;\n}\n");
+        editor.on_breakpoint_toggled({
+            let toggled = toggled.clone();
+            move |line, enabled| toggled.borrow_mut().push((line, enabled))
+        });
+
+        let ctx = egui::Context::default();
+        let mut run = |events: Vec<egui::Event>| {
+            let mut gutter_rect = egui::Rect::NOTHING;
+            let input = egui::RawInput {
+                events,
+                ..Default::default()
+            };
+            run_ui(&ctx, input, |ui| gutter_rect = editor.gutter_ui(ui).rect);
+            gutter_rect
+        };
+
+        let gutter_rect = run(vec![]);
+        let second_line = gutter_rect.left_top() + egui::vec2(10.0, 1.5 * LINE_HEIGHT);
+        let button = |pressed| egui::Event::PointerButton {
+            pos: second_line,
+            button: egui::PointerButton::Primary,
+            pressed,
+            modifiers: Default::default(),
+        };
+        run(vec![egui::Event::PointerMoved(second_line), button(true)]);
+        run(vec![button(false)]);
+
+        assert_eq!(
+            editor.breakpoints().iter().copied().collect::<Vec<_>>(),
+            [1]
+        );
+        assert_eq!(*toggled.borrow(), [(1, true)]);
+    }
+
+    #[test]
+    fn unchanged_lines_reuse_cached_highlighting() {
+        let mut highlighter = SyntaxHighlighter::new();
+        let before = "fn a() {}\nlet x = 1;\nfn b() {}\n";
+        let after = "fn a() {}\nlet y = \"two\";\nfn b() {}\n";
+
+        highlighter.highlight_text(before).unwrap();
+        let first = highlighter.line_cache[0].sections.clone();
+        let middle = highlighter.line_cache[1].sections.clone();
+        let last = highlighter.line_cache[2].sections.clone();
+
+        let spans = highlighter.highlight_text(after).unwrap();
+        assert_eq!(highlighter.line_cache.len(), 3);
+        assert_eq!(highlighter.line_cache[0].sections, first);
+        assert_ne!(highlighter.line_cache[1].sections, middle);
+        assert_eq!(highlighter.line_cache[2].sections, last);
+
+        // The cached result is the same as highlighting from scratch:
+        assert_eq!(
+            SyntaxHighlighter::new().highlight_text(after).unwrap(),
+            spans
+        );
+    }
+
+    #[test]
+    fn multi_line_comments_and_strings_stay_colored() {
+        let color_at = |spans: &[Span], offset: usize| {
+            let span = spans.iter().find(|span| span.range.contains(&offset));
+            span.unwrap().style.color
+        };
+        let assert_lines_colored = |text: &str, spans: &[Span], lines: &str, color: Color32| {
+            let start = text.find(lines).unwrap();
+            for (offset, c) in lines.char_indices() {
+                if !c.is_whitespace() {
+                    assert_eq!(color_at(spans, start + offset), color, "{c:?} in {lines:?}");
+                }
+            }
+        };
+
+        let mut highlighter = SyntaxHighlighter::new();
+        let comment = "/* first\n   fn not_code() {}\n   last */";
+        let text = format!("// line comment\n{comment}\nfn code() {{}}\n");
+        let spans = highlighter.highlight_text(&text).unwrap();
+        let comment_color = color_at(&spans, 0);
+        assert_lines_colored(&text, &spans, comment, comment_color);
+        assert_ne!(
+            color_at(&spans, text.find("fn code").unwrap()),
+            comment_color
+        );
+
+        // Opening a comment on an earlier line recolors the cached lines after it:
+        let text = "/*\nfn code() {}\n*/\n";
+        let spans = highlighter.highlight_text(text).unwrap();
+        assert_lines_colored(text, &spans, text.trim_end(), comment_color);
+
+        let mut highlighter = SyntaxHighlighter::new();
+        highlighter.set_language("py");
+        let string = "\"\"\"first\ndef not_code(): pass\nlast\"\"\"";
+        let text = format!("x = 1\n{string}\ny = 2\n");
+        let spans = highlighter.highlight_text(&text).unwrap();
+        let string_color = color_at(&spans, text.find(string).unwrap());
+        assert_lines_colored(&text, &spans, string, string_color);
+        assert_ne!(color_at(&spans, text.find('y').unwrap()), string_color);
+    }
+
+    #[test]
+    fn mixed_indentation_is_flagged_once() {
+        let mut editor = CodeEditor::new();
+        editor.load("fn main() {\n\tlet x = 1;\n    let y = 2;\n\t  let z = x + y;\n}\n");
+        assert_eq!(editor.mixed_indent_lines(), [3]);
+
+        let count_markers = |editor: &mut CodeEditor| {
+            let output = run_ui(&egui::Context::default(), Default::default(), |ui| {
+                editor.gutter_ui(ui);
+            });
+            output
+                .shapes
+                .iter()
+                .filter(|clipped| {
+                    matches!(&clipped.shape, egui::Shape::Rect(rect) if rect.fill == MIXED_INDENT_COLOR)
+                })
+                .count()
+        };
+        assert_eq!(count_markers(&mut editor), 1);
+
+        editor.set_flag_mixed_indent(false);
+        assert_eq!(count_markers(&mut editor), 0);
+    }
+
+    #[test]
+    fn todo_comments_are_emphasized() {
+        let mut editor = CodeEditor::new();
+        editor.load("fn main() {\n    // TODO: handle errors\n    let todo = \"FIXME\";\n}\n");
+
+        let job = editor.create_layout_job();
+        let emphasized: Vec<&str> = job
+            .sections
+            .iter()
+            .filter(|section| section.format.background == TODO_BACKGROUND_COLOR)
+            .map(|section| &job.text[section.byte_range.clone()])
+            .collect();
+        assert_eq!(
+            emphasized,
+            ["TODO"],
+            "Keywords outside comments are left alone"
+        );
+        assert_eq!(editor.todo_lines(), [1]);
+
+        let output = run_ui(&egui::Context::default(), Default::default(), |ui| {
+            editor.gutter_ui(ui);
+        });
+        let markers = output
+            .shapes
+            .iter()
+            .filter(|clipped| matches!(&clipped.shape, egui::Shape::Rect(rect) if rect.fill == TODO_COLOR))
+            .count();
+        assert_eq!(markers, 1);
+
+        editor.set_todo_keywords(vec!["handle".to_owned()]);
+        editor.create_layout_job();
+        assert_eq!(editor.todo_lines(), [1]);
+        editor.set_todo_keywords(Vec::new());
+        editor.create_layout_job();
+        assert!(editor.todo_lines().is_empty());
+    }
+
+    #[test]
+    fn snippet_is_highlighted_without_an_editor() {
+        let highlighter = SyntaxHighlighter::new();
+        let code = "let x = 42;";
+        let spans = highlighter.highlight_snippet(code, "rs");
+        assert!(spans.len() > 1);
+        assert_eq!(spans.first().unwrap().range.start, 0);
+        assert_eq!(spans.last().unwrap().range.end, code.len());
+        let color_of = |word: &str| {
+            let start = code.find(word).unwrap();
+            let span = spans.iter().find(|span| span.range.contains(&start));
+            span.unwrap().style.color
+        };
+        assert_ne!(color_of("let"), color_of("42"));
+
+        let plain = highlighter.highlight_snippet(code, "no such language");
+        assert_eq!(
+            plain,
+            [Span {
+                style: SpanStyle::plain(),
+                range: 0..code.len(),
+            }]
+        );
+    }
+
+    #[test]
+    fn offset_line_col_round_trip() {
+        let mut editor = CodeEditor::new();
+        editor.load("let a = 1;\nlet ä = \"日本\";\r\nend");
+
+        assert_eq!(editor.offset_to_line_col(0), (0, 0));
+        assert_eq!(editor.offset_to_line_col(11), (1, 0));
+
+        // Columns count chars, not bytes:
+        let second_line = &editor.buffer()[11..];
+        let quote = 11 + second_line.find('"').unwrap();
+        assert_eq!(editor.offset_to_line_col(quote), (1, 8));
+        let nihon_end = 11 + second_line.rfind('"').unwrap();
+        assert_eq!(editor.offset_to_line_col(nihon_end), (1, 11));
+        assert_eq!(editor.line_col_to_offset((1, 8)), quote);
+        assert_eq!(editor.line_col_to_offset((1, 11)), nihon_end);
+
+        // Inside a multi-byte char:
+        assert_eq!(editor.offset_to_line_col(quote + 2), (1, 9));
+
+        // Clamping:
+        assert_eq!(
+            editor.line_col_to_offset((1, 100)),
+            editor.buffer().find('\r').unwrap()
+        );
+        assert_eq!(
+            editor.line_col_to_offset((100, 1)),
+            editor.buffer().len() - 2
+        );
+        assert_eq!(editor.offset_to_line_col(1000), (2, 3));
+
+        for offset in 0..=editor.buffer().len() {
+            if editor.buffer().is_char_boundary(offset) {
+                let line_col = editor.offset_to_line_col(offset);
+                if editor.buffer().as_bytes().get(offset) != Some(&b'\n') {
+                    assert_eq!(editor.line_col_to_offset(line_col), offset);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn sticky_headers_of_nested_scopes() {
+        let mut editor = CodeEditor::new();
+        editor.load(
+            "\
+mod a {
+    fn first() {
+        one();
+    }
+
+    impl B {
+        fn second(&self) {
+            if true {
+                two();
+
+                three();
+            }
+        }
+    }
+}
+",
+        );
+
+        assert_eq!(editor.sticky_header_lines(0), Vec::<usize>::new());
+        assert_eq!(editor.sticky_header_lines(2), [0, 1]);
+        // The blank line belongs to the scope of the next line:
+        assert_eq!(editor.sticky_header_lines(4), [0]);
+        assert_eq!(editor.sticky_header_lines(5), [0]);
+        // Not the end of `first`:
+        assert_eq!(editor.sticky_header_lines(6), [0, 5]);
+        assert_eq!(editor.sticky_header_lines(10), [0, 5, 6, 7]);
+        assert_eq!(editor.sticky_header_lines(12), [0, 5]);
+        assert_eq!(editor.sticky_header_lines(100), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn gutter_shows_true_line_numbers_around_folds() {
+        let mut editor = CodeEditor::new();
+        let text = (1..=12)
+            .map(|i| format!("line {i}\n"))
+            .collect::<Vec<_>>()
+            .concat();
+        editor.load(text);
+
+        // Lines 5–8 (one-based):
+        editor.fold(4..8);
+        assert_eq!(editor.visible_lines(), [0, 1, 2, 3, 8, 9, 10, 11]);
+        assert_eq!(editor.line_to_row(8), 4);
+        assert_eq!(
+            editor.line_to_row(6),
+            3,
+            "A folded line maps to the row before the fold"
+        );
+
+        let output = run_ui(&egui::Context::default(), Default::default(), |ui| {
+            editor.gutter_ui(ui);
+        });
+        let gutter_texts: Vec<&str> = texts(&output)
+            .iter()
+            .map(|text| text.galley.text())
+            .collect();
+        assert_eq!(
+            gutter_texts,
+            ["1", "2", "3", "4", "…", "9", "10", "11", "12"]
+        );
+
+        let job = editor.create_layout_job();
+        assert_eq!(job.validate(), Ok(()));
+        assert_eq!(job.text.lines().nth(4), Some("line 9"));
+
+        editor.scroll_to_line(6);
+        assert!(
+            editor.folds().is_empty(),
+            "Going to a folded line unfolds it"
+        );
+        assert_eq!(editor.scroll_to_row, Some(6));
+    }
+
+    #[test]
+    fn dropped_file_is_loaded() {
+        let mut editor = CodeEditor::new();
+        editor.load("fn main() {}\n");
+
+        let ctx = egui::Context::default();
+        let drop = |editor: &mut CodeEditor, name: &str, bytes: &[u8]| {
+            let input = egui::RawInput {
+                dropped_files: vec![egui::DroppedFile {
+                    name: name.to_owned(),
+                    bytes: Some(bytes.into()),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            };
+            run_ui(&ctx, input, |ui| {
+                editor.ui(ui);
+            });
+        };
+
+        drop(
+            &mut editor,
+            "hello.py",
+            b"def hello():\n    print('hello')\n",
+        );
+        assert_eq!(editor.buffer(), "def hello():\n    print('hello')\n");
+        assert_eq!(editor.language(), "Python");
+        assert_eq!(editor.drop_error, None);
+
+        drop(&mut editor, "image.png", b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR");
+        assert_eq!(editor.buffer(), "def hello():\n    print('hello')\n");
+        assert_eq!(editor.language(), "Python");
+        assert!(editor.drop_error.is_some());
+    }
+
+    #[test]
+    fn hovering_overlapping_diagnostics_lists_all_messages() {
+        let mut editor = CodeEditor::new();
+        editor.load("let x = undefined_value;\n");
+        editor.set_errors(vec![
+            CodeError::new(ErrorType::WARNING, "unused variable").with_range(4..5),
+            CodeError::new(ErrorType::ERROR, "cannot find value").with_range(8..23),
+            CodeError::new(ErrorType::INFO, "consider a constant").with_range(0..23),
+        ]);
+        assert_eq!(editor.diagnostics_at(10).len(), 2);
+        assert_eq!(editor.diagnostics_at(10)[0].error_type(), ErrorType::ERROR);
+
+        let ctx = egui::Context::default();
+        let output = run_editor(&ctx, &mut editor, vec![]);
+        let code = texts(&output)
+            .into_iter()
+            .find(|text| text.galley.text().starts_with("let x"))
+            .unwrap()
+            .clone();
+        let glyph = code.galley.pos_from_ccursor(egui::text::CCursor::new(10));
+        let output = run_editor(
+            &ctx,
+            &mut editor,
+            vec![egui::Event::PointerMoved(
+                code.pos + glyph.center().to_vec2() + egui::vec2(1.0, 0.0),
+            )],
+        );
+
+        let tooltip = texts(&output)
+            .into_iter()
+            .find(|text| text.galley.text().contains("cannot find value"))
+            .expect("hovering an error shows a tooltip");
+        assert!(tooltip.galley.text().contains("consider a constant"));
+        assert!(!tooltip.galley.text().contains("unused variable"));
+        assert!(
+            tooltip.pos.y > code.pos.y + glyph.bottom(),
+            "below the squiggle"
+        );
+    }
+
+    #[test]
+    fn tooltip_is_clamped_to_the_screen() {
+        let screen = egui::Rect::from_min_size(egui::Pos2::ZERO, egui::vec2(200.0, 100.0));
+        let size = egui::vec2(80.0, 30.0);
+        let anchor =
+            |x: f32, y: f32| egui::Rect::from_min_size(egui::pos2(x, y), egui::vec2(8.0, 16.0));
+
+        let below = tooltip_rect(anchor(10.0, 10.0), size, screen);
+        assert_eq!(below.min, egui::pos2(10.0, 26.0));
+
+        let flipped_above = tooltip_rect(anchor(10.0, 70.0), size, screen);
+        assert_eq!(flipped_above.min, egui::pos2(10.0, 40.0));
+
+        let shifted_left = tooltip_rect(anchor(180.0, 10.0), size, screen);
+        assert_eq!(shifted_left.min, egui::pos2(120.0, 26.0));
+    }
+
+    #[test]
+    fn minimap_has_a_marker_per_matching_line() {
+        let mut editor = CodeEditor::new();
+        let text: String = (0..100)
+            .map(|line| {
+                if [10, 50, 90].contains(&line) {
+                    format!("let needle_{line} = 0;\n")
+                } else {
+                    "let hay = 0;\n".to_owned()
+                }
+            })
+            .collect();
+        editor.load(&text);
+        editor.search_query = "needle".to_owned();
+        assert_eq!(editor.search_match_lines(), [10, 50, 90]);
+
+        // Several matches on a line, and a match spanning lines:
+        let mut other = CodeEditor::new();
+        other.load("a a\nb\na\nb a\n");
+        other.search_query = "a".to_owned();
+        assert_eq!(other.search_match_lines(), [0, 2, 3]);
+        other.search_query = r"a\nb".to_owned();
+        other.search_mode = SearchMode::Regex;
+        assert_eq!(other.search_match_lines(), [0, 2]);
+
+        let ctx = egui::Context::default();
+        let mut run = |events: Vec<egui::Event>| {
+            let mut minimap_rect = egui::Rect::NOTHING;
+            let input = egui::RawInput {
+                events,
+                ..Default::default()
+            };
+            let output = run_ui(&ctx, input, |ui| minimap_rect = editor.minimap_ui(ui).rect);
+            let markers: Vec<egui::Rect> = output
+                .shapes
+                .iter()
+                .filter_map(|clipped| match &clipped.shape {
+                    egui::Shape::Rect(rect) if rect.fill == DEFAULT_SEARCH_MARKER_COLOR => {
+                        Some(rect.rect)
+                    }
+                    _ => None,
+                })
+                .collect();
+            (minimap_rect, markers)
+        };
+
+        let (minimap_rect, markers) = run(vec![]);
+        assert_eq!(markers.len(), 3);
+        for (marker, line) in markers.iter().zip([10.0, 50.0, 90.0]) {
+            let relative_y = (marker.top() - minimap_rect.top()) / minimap_rect.height();
+            assert!((relative_y - line / 100.0).abs() < 1e-3, "{relative_y}");
+        }
+
+        // Clicking next to the second marker jumps to its line:
+        let pos = egui::pos2(minimap_rect.center().x, markers[1].top() + 5.0);
+        let button = |pressed| egui::Event::PointerButton {
+            pos,
+            button: egui::PointerButton::Primary,
+            pressed,
+            modifiers: Default::default(),
+        };
+        run(vec![egui::Event::PointerMoved(pos), button(true)]);
+        run(vec![button(false)]);
+        assert_eq!(editor.scroll_to_row, Some(50));
+    }
+
+    #[test]
+    fn indentation_is_detected_on_load() {
+        let mut editor = CodeEditor::new();
+        editor.load("fn main() {\n  if x {\n    y();\n  }\n}\n");
+        assert!(editor.insert_spaces());
+        assert_eq!(editor.tab_width(), 2);
+
+        editor.load("fn main() {\n\tif x {\n\t\ty();\n\t}\n}\n");
+        assert!(!editor.insert_spaces());
+        assert_eq!(editor.tab_width(), 2, "tabs keep their width");
+        assert_eq!(
+            editor.detect_indentation(),
+            Some(Indentation {
+                insert_spaces: false,
+                tab_width: 2,
+            })
+        );
+
+        editor.set_detect_indentation_on_load(false);
+        editor.load("a\n        b\n");
+        assert!(!editor.insert_spaces());
+    }
+
+    #[test]
+    fn plain_rendering_has_a_single_section() {
+        let mut editor = CodeEditor::new();
+        editor.load("fn main() {\n    println!(\"hi\");\n}\n");
+
+        let job = editor.plain_layout_job();
+        assert_eq!(job.text, editor.buffer());
+        assert_eq!(job.sections.len(), 1);
+        assert_eq!(job.sections[0].byte_range, 0..editor.buffer().len());
+        assert_eq!(
+            job.sections[0].format,
+            TextFormat::simple(FontId::monospace(editor.font_size), Color32::GRAY)
+        );
+
+        let output = run_ui(&egui::Context::default(), Default::default(), |ui| {
+            editor.render_plain(ui);
+        });
+        let painted: Vec<&str> = texts(&output)
+            .iter()
+            .map(|text| text.galley.text())
+            .collect();
+        assert!(painted.contains(&editor.buffer()));
+        assert!(painted.contains(&"3"), "line numbers: {painted:?}");
+    }
+
+    #[test]
+    fn word_and_line_ranges() {
+        let mut editor = CodeEditor::new();
+        editor.load("let foo_bar = foo.bar;\nlet größe = 1;\n");
+        let text = editor.buffer().to_owned();
+        let word_at = |needle: &str, delta: usize| {
+            let range = editor.word_range_at(text.find(needle).unwrap() + delta);
+            text[range].to_owned()
+        };
+        assert_eq!(word_at("foo_bar", 5), "foo_bar");
+        assert_eq!(word_at("foo.bar", 1), "foo");
+        assert_eq!(word_at("foo.bar", 5), "bar");
+        assert_eq!(word_at("foo.bar", 3), ".");
+        assert_eq!(word_at("größe", 2), "größe");
+
+        assert_eq!(&text[editor.line_range_at(3)], "let foo_bar = foo.bar;\n");
+        assert_eq!(
+            &text[editor.line_range_at(text.len() - 2)],
+            "let größe = 1;\n"
+        );
+    }
+
+    #[test]
+    fn toggle_comment_of_a_multi_line_selection() {
+        let mut editor = CodeEditor::new();
+        editor.set_language("rs");
+        editor.load("fn main() {\n    if x {\n        y();\n    }\n}\n");
+        let start = editor.line_col_to_offset((1, 0));
+        let end = editor.line_col_to_offset((4, 0));
+        editor.set_selection(Some(start..end));
+
+        assert!(editor.toggle_comment());
+        assert_eq!(
+            editor.buffer(),
+            "fn main() {\n    // if x {\n    //     y();\n    // }\n}\n"
+        );
+        let selection = editor.selection().unwrap();
+        assert_eq!(
+            &editor.buffer()[selection.clone()],
+            "    // if x {\n    //     y();\n    // }\n"
+        );
+
+        assert!(editor.toggle_comment());
+        assert_eq!(
+            editor.buffer(),
+            "fn main() {\n    if x {\n        y();\n    }\n}\n"
+        );
+
+        // Partially commented lines are all commented:
+        editor.load("    // a\n    b\n    // c\n");
+        editor.set_selection(Some(0..editor.buffer().len()));
+        assert!(editor.toggle_comment());
+        assert_eq!(editor.buffer(), "    // // a\n    // b\n    // // c\n");
+        assert!(editor.undo());
+        assert_eq!(editor.buffer(), "    // a\n    b\n    // c\n");
+
+        editor.set_selection(None);
+        assert!(!editor.toggle_comment());
+    }
+
+    #[test]
+    fn jump_to_matching_bracket() {
+        let mut editor = CodeEditor::new();
+        editor.load("fn f(a: [u8; 2]) {\n    g(a[0]);\n}\n");
+        let open_brace = editor.buffer().find('{').unwrap();
+        let close_brace = editor.buffer().rfind('}').unwrap();
+
+        // From the opening brace to the closing one, past the nested brackets, and back:
+        editor.set_selection(Some(open_brace..open_brace));
+        assert!(editor.jump_to_matching_bracket());
+        assert_eq!(editor.selection(), Some(close_brace..close_brace));
+        assert!(editor.jump_to_matching_bracket());
+        assert_eq!(editor.selection(), Some(open_brace..open_brace));
+
+        // A cursor just after a bracket matches it too:
+        let close_paren = editor.buffer().find(')').unwrap();
+        assert_eq!(
+            editor.matching_bracket(close_paren + 1),
+            editor.buffer().find('(')
+        );
+        let open_bracket = editor.buffer().find('[').unwrap();
+        assert_eq!(
+            editor.matching_bracket(open_bracket),
+            editor.buffer().find(']')
+        );
+
+        // Without a bracket next to the cursor, nothing happens:
+        editor.set_selection(Some(1..1));
+        assert!(!editor.jump_to_matching_bracket());
+        assert_eq!(editor.selection(), Some(1..1));
+    }
+
+    #[test]
+    fn visible_line_range_of_the_viewport() {
+        let mut editor = CodeEditor::new();
+        editor.load("line\n".repeat(100));
+        assert_eq!(editor.visible_line_range(), 0..0, "not shown yet");
+
+        // Scrolled down 10.5 rows, with room for 5 rows:
+        let top = 10.5 * LINE_HEIGHT;
+        let viewport = egui::Rangef::new(top, top + 5.0 * LINE_HEIGHT);
+        assert_eq!(editor.line_range_in_view(viewport), 10..16);
+
+        // Folded lines take no rows:
+        editor.fold(12..20);
+        assert_eq!(editor.line_range_in_view(viewport), 10..24);
+
+        let past_the_end = egui::Rangef::new(200.0 * LINE_HEIGHT, 210.0 * LINE_HEIGHT);
+        assert_eq!(editor.line_range_in_view(past_the_end), 0..0);
+    }
+
+    #[test]
+    fn scope_at_cursor_of_a_keyword() {
+        let mut editor = CodeEditor::new();
+        editor.load("/* a\n   comment */\nfn main() {\n    let x = 1;\n}\n");
+        assert_eq!(editor.scope_at_cursor(), None, "no cursor");
+
+        let let_offset = editor.buffer.find("let").unwrap();
+        editor.selection = Some(let_offset..let_offset + 1);
+        let scope = editor.scope_at_cursor().unwrap();
+        assert_eq!(
+            scope,
+            "source.rust meta.function.rust meta.block.rust storage.type.rust"
+        );
+
+        // The same with the highlighting cached:
+        editor.create_layout_job();
+        assert_eq!(editor.scope_at_cursor().unwrap(), scope);
+
+        let comment_offset = editor.buffer.find("comment").unwrap();
+        editor.selection = Some(comment_offset..comment_offset);
+        let scope = editor.scope_at_cursor().unwrap();
+        assert!(scope.ends_with(" comment.block.rust"), "{scope}");
+    }
+
+    #[test]
+    fn galley_has_a_row_per_line() {
+        let mut editor = CodeEditor::new();
+        editor.load("fn main() {\n    let x = 1;\n\n    let y = 2;\n}");
+        let ctx = egui::Context::default();
+        let mut galley = None;
+        run_ui(&ctx, Default::default(), |ui| {
+            galley = Some(editor.galley(ui));
+        });
+        let galley = galley.unwrap();
+        assert_eq!(galley.text(), editor.buffer);
+        assert_eq!(galley.rows.len(), editor.buffer.lines().count());
+        assert!(galley.job.sections.len() > 1, "highlighted");
+    }
+
+    #[test]
+    fn hint_text_is_shown_only_while_empty() {
+        let mut editor = CodeEditor::new();
+        let ctx = egui::Context::default();
+        let shown_texts = |editor: &mut CodeEditor| -> Vec<String> {
+            let output = run_editor(&ctx, editor, vec![]);
+            texts(&output)
+                .iter()
+                .map(|text| text.galley.text().to_owned())
+                .collect()
+        };
+
+        assert!(shown_texts(&mut editor)
+            .iter()
+            .any(|text| text == "// Start typing…"));
+
+        editor.insert(0, "f");
+        assert!(!shown_texts(&mut editor)
+            .iter()
+            .any(|text| text.contains("Start typing")));
+
+        editor.load("");
+        editor.set_hint_text("-- query");
+        assert!(shown_texts(&mut editor)
+            .iter()
+            .any(|text| text == "-- query"));
+    }
+
+    #[test]
+    fn ui_reports_the_changed_range() {
+        let mut editor = CodeEditor::new();
+        editor.load("fn main() {\n    let x = 1;\n}\n");
+        let ctx = egui::Context::default();
+        let run = |editor: &mut CodeEditor, events: Vec<egui::Event>| {
+            let input = egui::RawInput {
+                events,
+                ..Default::default()
+            };
+            let mut output = None;
+            run_ui(&ctx, input, |ui| output = Some(editor.ui(ui)));
+            output.unwrap()
+        };
+        assert!(run(&mut editor, vec![]).changed(), "loaded");
+        assert!(!run(&mut editor, vec![]).changed());
+
+        // Comment out the middle line with the shortcut:
+        let line_start = editor.buffer.find("    let").unwrap();
+        editor.selection = Some(line_start..line_start);
+        let shortcut = egui::Event::Key {
+            key: egui::Key::Slash,
+            physical_key: None,
+            pressed: true,
+            repeat: false,
+            modifiers: egui::Modifiers::COMMAND,
+        };
+        let output = run(&mut editor, vec![shortcut]);
+        assert!(output.changed());
+        assert_eq!(editor.buffer, "fn main() {\n    // let x = 1;\n}\n");
+        let comment_start = line_start + 4;
+        assert_eq!(output.changed_range, Some(comment_start..comment_start + 3));
+        assert!(!run(&mut editor, vec![]).changed());
+
+        // Edits in between frames are reported too, as one range:
+        editor.insert(0, "pub ");
+        editor.insert(editor.buffer.len(), "// end");
+        let output = run(&mut editor, vec![]);
+        assert_eq!(output.changed_range, Some(0..editor.buffer.len()));
+
+        // Undone edits are no change:
+        editor.insert(0, "x");
+        editor.undo();
+        assert!(!run(&mut editor, vec![]).changed());
+    }
+
+    #[test]
+    fn double_and_triple_click_select() {
+        let mut editor = CodeEditor::new();
+        editor.load("let foo_bar = 1;\nfoo.bar();\n");
+
+        let ctx = egui::Context::default();
+        let mut run = |events: Vec<egui::Event>| {
+            let output = run_editor(&ctx, &mut editor, events);
+            let code = texts(&output)
+                .into_iter()
+                .find(|text| text.galley.text().starts_with("let foo_bar"))
+                .unwrap()
+                .clone();
+            (code.pos, code.galley, editor.selection())
+        };
+
+        let (pos, galley, _) = run(vec![]);
+        let glyph = galley.pos_from_ccursor(egui::text::CCursor::new(6));
+        let click_pos = pos + glyph.center().to_vec2() + egui::vec2(1.0, 0.0);
+        let button = |pressed| egui::Event::PointerButton {
+            pos: click_pos,
+            button: egui::PointerButton::Primary,
+            pressed,
+            modifiers: Default::default(),
+        };
+        let mut click = || {
+            run(vec![egui::Event::PointerMoved(click_pos), button(true)]);
+            run(vec![button(false)]).2
+        };
+
+        assert_eq!(click(), None);
+        assert_eq!(click(), Some(4..11), "double-click selects foo_bar");
+        assert_eq!(click(), Some(0..17), "triple-click selects the line");
+    }
+
+    #[test]
+    fn scroll_to_line_animates_the_offset() {
+        let mut editor = CodeEditor::new();
+        editor.load("line\n".repeat(100));
+        let ctx = egui::Context::default();
+        let run = |editor: &mut CodeEditor, time: f64| {
+            let input = egui::RawInput {
+                time: Some(time),
+                screen_rect: Some(egui::Rect::from_min_size(
+                    egui::Pos2::ZERO,
+                    egui::vec2(400.0, 200.0),
+                )),
+                ..Default::default()
+            };
+            run_ui(&ctx, input, |ui| {
+                editor.ui(ui);
+            });
+        };
+        run(&mut editor, 0.0);
+
+        editor.scroll_to_line(50);
+        let target = 50.0 * LINE_HEIGHT;
+        run(&mut editor, 1.0);
+        assert_eq!(editor.viewport.min, 0.0, "starts where it was");
+        assert!(ctx.has_requested_repaint());
+
+        run(&mut editor, 1.0 + 0.5 * SMOOTH_SCROLL_DURATION as f64);
+        let halfway = editor.viewport.min;
+        assert!(0.0 < halfway && halfway < target, "{halfway}");
+
+        run(&mut editor, 1.0 + SMOOTH_SCROLL_DURATION as f64);
+        assert_eq!(editor.viewport.min, target);
+
+        // Without smooth scrolling, it jumps:
+        editor.set_smooth_scroll(false);
+        editor.scroll_to_line(10);
+        run(&mut editor, 2.0);
+        assert_eq!(editor.viewport.min, 10.0 * LINE_HEIGHT);
+    }
+
+    #[test]
+    fn available_themes_and_languages() {
+        let highlighter = SyntaxHighlighter::new();
+        assert!(highlighter
+            .available_themes()
+            .contains(&"base16-ocean.dark".to_owned()));
+
+        let languages = highlighter.available_languages();
+        assert!(languages.contains(&"Rust".to_owned()));
+        assert!(languages.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn undo_history_is_capped() {
+        let mut editor = CodeEditor::new();
+        editor.set_max_undo_steps(3);
+        for i in 0..5 {
+            editor.insert(editor.buffer().len(), &format!("line {i}\n"));
+        }
+        assert_eq!(editor.undo_stack.undo_len(), 3);
+        while editor.undo() {}
+        assert_eq!(
+            editor.buffer(),
+            "line 0\nline 1\n",
+            "the oldest edits were dropped"
+        );
+
+        // Each edit keeps 7 bytes, so 20 bytes fit two of them:
+        let mut editor = CodeEditor::new();
+        editor.set_max_undo_bytes(20);
+        for i in 0..5 {
+            editor.insert(editor.buffer().len(), &format!("line {i}\n"));
+        }
+        assert_eq!(editor.undo_stack.undo_len(), 2);
+
+        // Lowering a limit drops edits right away:
+        editor.set_max_undo_steps(1);
+        assert_eq!(editor.undo_stack.undo_len(), 1);
+        assert!(editor.undo());
+        assert_eq!(editor.buffer(), "line 0\nline 1\nline 2\nline 3\n");
+        assert!(!editor.undo());
+    }
+
+    #[test]
+    fn range_rects_cover_the_code() {
+        let mut editor = CodeEditor::new();
+        editor.load("fn main() {}\nlet x = 1;\n");
+        assert!(editor.range_rects(3..7).is_empty(), "not shown yet");
+
+        let output = run_editor(&egui::Context::default(), &mut editor, vec![]);
+        let code = texts(&output)
+            .into_iter()
+            .find(|text| text.galley.text().starts_with("fn main"))
+            .unwrap()
+            .clone();
+        let (origin, galley) = (code.pos, code.galley);
+
+        // `main` on the first row:
+        let row = &galley.rows[0];
+        let rects = editor.range_rects(3..7);
+        assert_eq!(
+            rects,
+            [egui::Rect::from_x_y_ranges(
+                origin.x + row.x_offset(3)..=origin.x + row.x_offset(7),
+                origin.y + row.rect.top()..=origin.y + row.rect.bottom(),
+            )]
+        );
+
+        // From `}` to `let` spans two rows:
+        let rects = editor.range_rects(11..16);
+        assert_eq!(rects.len(), 2);
+        assert_eq!(rects[0].left(), origin.x + row.x_offset(11));
+        assert_eq!(rects[1].left(), origin.x);
+        assert_eq!(rects[1].top(), origin.y + galley.rows[1].rect.top());
+    }
+}
@@ -0,0 +1,59 @@
+//! Detecting and preserving the line endings of a buffer.
+
+/// The character sequence that ends a line.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LineEnding {
+    /// `\n`, as used on Linux and macOS.
+    #[default]
+    Lf,
+
+    /// `\r\n`, as used on Windows.
+    CrLf,
+}
+
+impl LineEnding {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Lf => "\n",
+            Self::CrLf => "\r\n",
+        }
+    }
+
+    /// The most common line ending in `text`.
+    ///
+    /// Text without any line breaks is considered to use [`Self::Lf`].
+    pub fn detect(text: &str) -> Self {
+        let crlf = text.matches("\r\n").count();
+        let lf = text.matches('\n').count() - crlf;
+        if crlf > lf {
+            Self::CrLf
+        } else {
+            Self::Lf
+        }
+    }
+
+    /// Convert all line breaks in `text` to this line ending.
+    pub fn apply(self, text: &str) -> String {
+        let normalized = text.replace("\r\n", "\n");
+        match self {
+            Self::Lf => normalized,
+            Self::CrLf => normalized.replace('\n', "\r\n"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detect_and_apply() {
+        assert_eq!(LineEnding::detect("no line breaks"), LineEnding::Lf);
+        assert_eq!(LineEnding::detect("a\nb\n"), LineEnding::Lf);
+        assert_eq!(LineEnding::detect("a\r\nb\r\n"), LineEnding::CrLf);
+        assert_eq!(LineEnding::detect("a\r\nb\r\nc\n"), LineEnding::CrLf);
+
+        assert_eq!(LineEnding::CrLf.apply("a\nb\r\nc"), "a\r\nb\r\nc");
+        assert_eq!(LineEnding::Lf.apply("a\nb\r\nc"), "a\nb\nc");
+    }
+}
@@ -0,0 +1,113 @@
+//! Finding text in the buffer of a [`crate::CodeEditor`].
+
+use std::ops::Range;
+
+/// How the query of [`find`] is interpreted.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum SearchMode {
+    /// The query is matched literally.
+    #[default]
+    Plain,
+
+    /// The query is a regular expression, in the syntax of the [`regex`] crate.
+    Regex,
+}
+
+/// One match of a search query.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SearchMatch {
+    /// Byte range of the whole match.
+    pub range: Range<usize>,
+
+    /// Byte ranges of the capture groups of a [`SearchMode::Regex`] query,
+    /// `None` for groups that didn't participate in the match.
+    ///
+    /// Empty for [`SearchMode::Plain`].
+    pub captures: Vec<Option<Range<usize>>>,
+}
+
+/// All non-overlapping matches of `query` in `text`, in order.
+///
+/// An empty query matches nothing.
+///
+/// # Errors
+/// If `query` is not a valid regular expression in [`SearchMode::Regex`],
+/// with a message suitable for showing to the user.
+pub fn find(
+    text: &str,
+    query: &str,
+    mode: SearchMode,
+    match_case: bool,
+) -> Result<Vec<SearchMatch>, String> {
+    if query.is_empty() {
+        return Ok(Vec::new());
+    }
+    let pattern = match mode {
+        SearchMode::Plain => regex::escape(query),
+        SearchMode::Regex => query.to_owned(),
+    };
+    let regex = regex::RegexBuilder::new(&pattern)
+        .case_insensitive(!match_case)
+        .multi_line(true)
+        .build()
+        .map_err(|err| err.to_string())?;
+
+    Ok(regex
+        .captures_iter(text)
+        .map(|captures| {
+            let range = captures.get(0).map_or(0..0, |m| m.range());
+            let captures = match mode {
+                SearchMode::Plain => Vec::new(),
+                SearchMode::Regex => captures
+                    .iter()
+                    .skip(1)
+                    .map(|group| group.map(|m| m.range()))
+                    .collect(),
+            };
+            SearchMatch { range, captures }
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn regex_with_groups() {
+        let text = "let x = 1;\nlet yy = 22;\n";
+        let matches = find(text, r"let (\w+) = (\d+)(;)?", SearchMode::Regex, true).unwrap();
+        assert_eq!(matches.len(), 2);
+        let second = &matches[1];
+        assert_eq!(&text[second.range.clone()], "let yy = 22;");
+        let groups: Vec<_> = second
+            .captures
+            .iter()
+            .map(|group| &text[group.clone().unwrap()])
+            .collect();
+        assert_eq!(groups, ["yy", "22", ";"]);
+
+        // Plain mode matches regex syntax literally:
+        assert!(find(text, r"\w+", SearchMode::Plain, true)
+            .unwrap()
+            .is_empty());
+        let matches = find(text, "LET", SearchMode::Plain, false).unwrap();
+        assert_eq!(matches.len(), 2);
+        assert!(matches[0].captures.is_empty());
+        assert!(find(text, "LET", SearchMode::Plain, true)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn invalid_pattern_is_an_error() {
+        let err = find("fn main() {}", "main(", SearchMode::Regex, true).unwrap_err();
+        assert!(err.contains("unclosed group"), "{err}");
+        assert_eq!(
+            find("fn main() {}", "main(", SearchMode::Plain, true)
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+}
@@ -0,0 +1,197 @@
+//! A tab strip switching between several [`CodeEditor`]s.
+
+use egui::Ui;
+
+use crate::{CodeEditor, CodeEditorOutput};
+
+/// One open buffer of an [`EditorTabs`].
+pub struct EditorTab {
+    /// Shown in the tab strip, e.g. the file name.
+    pub title: String,
+
+    /// The buffer, with its own scroll position, folds, selection and undo history.
+    pub editor: CodeEditor,
+
+    /// Keeps the egui state of the editor (like its scroll offset) apart from other tabs,
+    /// even when tabs before it are closed.
+    id: u64,
+}
+
+/// Several [`CodeEditor`]s with a tab strip to open, close and switch between them.
+///
+/// Each tab keeps its own editor, so switching back to a tab shows it as it was left.
+#[derive(Default)]
+pub struct EditorTabs {
+    tabs: Vec<EditorTab>,
+    active: usize,
+    next_id: u64,
+}
+
+impl EditorTabs {
+    /// The title of tabs opened with the `+` button of the tab strip.
+    pub const UNTITLED: &'static str = "untitled";
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Open `editor` in a new tab after the last one, and switch to it.
+    ///
+    /// Returns the index of the new tab.
+    pub fn open(&mut self, title: impl Into<String>, editor: CodeEditor) -> usize {
+        self.tabs.push(EditorTab {
+            title: title.into(),
+            editor,
+            id: self.next_id,
+        });
+        self.next_id += 1;
+        self.active = self.tabs.len() - 1;
+        self.active
+    }
+
+    /// Close the tab at `index`, returning it, or `None` if there is no such tab.
+    ///
+    /// The active tab stays active. If it is the one closed, its right neighbor becomes active,
+    /// or its left one if it was the last tab.
+    pub fn close(&mut self, index: usize) -> Option<EditorTab> {
+        if index >= self.tabs.len() {
+            return None;
+        }
+        let tab = self.tabs.remove(index);
+        if index < self.active || self.active == self.tabs.len() {
+            self.active = self.active.saturating_sub(1);
+        }
+        Some(tab)
+    }
+
+    /// The number of open tabs.
+    pub fn len(&self) -> usize {
+        self.tabs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tabs.is_empty()
+    }
+
+    /// The open tabs, in the order of the tab strip.
+    pub fn tabs(&self) -> &[EditorTab] {
+        &self.tabs
+    }
+
+    /// The index of the active tab, or `None` if no tab is open.
+    pub fn active(&self) -> Option<usize> {
+        (!self.tabs.is_empty()).then_some(self.active)
+    }
+
+    /// Switch to the tab at `index`. Does nothing if there is no such tab.
+    pub fn set_active(&mut self, index: usize) {
+        if index < self.tabs.len() {
+            self.active = index;
+        }
+    }
+
+    /// The editor of the active tab.
+    pub fn active_editor(&self) -> Option<&CodeEditor> {
+        self.tabs.get(self.active).map(|tab| &tab.editor)
+    }
+
+    pub fn active_editor_mut(&mut self) -> Option<&mut CodeEditor> {
+        self.tabs.get_mut(self.active).map(|tab| &mut tab.editor)
+    }
+
+    /// Show the tab strip, and below it the editor of the active tab.
+    ///
+    /// Returns the output of the active editor, if any tab is open.
+    pub fn ui(&mut self, ui: &mut Ui) -> Option<CodeEditorOutput> {
+        self.tab_strip_ui(ui);
+        ui.separator();
+        let tab = self.tabs.get_mut(self.active)?;
+        Some(ui.push_id(tab.id, |ui| tab.editor.ui(ui)).inner)
+    }
+
+    fn tab_strip_ui(&mut self, ui: &mut Ui) {
+        let mut close = None;
+        ui.horizontal(|ui| {
+            for (index, tab) in self.tabs.iter().enumerate() {
+                ui.push_id(tab.id, |ui| {
+                    if ui
+                        .selectable_label(index == self.active, &tab.title)
+                        .clicked()
+                    {
+                        self.active = index;
+                    }
+                    if ui.small_button("×").on_hover_text("Close").clicked() {
+                        close = Some(index);
+                    }
+                });
+            }
+            if ui.small_button("+").on_hover_text("New tab").clicked() {
+                self.open(Self::UNTITLED, CodeEditor::new());
+            }
+        });
+        if let Some(index) = close {
+            self.close(index);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn switching_tabs_keeps_their_state() {
+        let mut tabs = EditorTabs::new();
+        assert_eq!(tabs.active(), None);
+        for title in ["a.rs", "b.rs"] {
+            let mut editor = CodeEditor::new();
+            editor.load(format!("// {title}\n").repeat(100));
+            editor.set_smooth_scroll(false);
+            tabs.open(title, editor);
+        }
+        assert_eq!(tabs.active(), Some(1));
+
+        let ctx = egui::Context::default();
+        let run = |tabs: &mut EditorTabs| {
+            let input = egui::RawInput {
+                screen_rect: Some(egui::Rect::from_min_size(
+                    egui::Pos2::ZERO,
+                    egui::vec2(400.0, 300.0),
+                )),
+                ..Default::default()
+            };
+            let _ = ctx.run(input, |ctx| {
+                egui::CentralPanel::default().show(ctx, |ui| tabs.ui(ui));
+            });
+        };
+
+        tabs.set_active(0);
+        let editor = tabs.active_editor_mut().unwrap();
+        editor.fold(5..8);
+        editor.scroll_to_line(50);
+        run(&mut tabs);
+        assert_eq!(tabs.active_editor().unwrap().visible_line_range().start, 50);
+
+        tabs.set_active(1);
+        run(&mut tabs);
+        let editor = tabs.active_editor().unwrap();
+        assert!(editor.buffer().starts_with("// b.rs"));
+        assert_eq!(editor.visible_line_range().start, 0);
+        assert!(editor.folds().is_empty());
+
+        tabs.set_active(0);
+        run(&mut tabs);
+        let editor = tabs.active_editor().unwrap();
+        assert!(editor.buffer().starts_with("// a.rs"));
+        assert_eq!(editor.visible_line_range().start, 50);
+        assert_eq!(editor.folds().to_vec(), vec![5..8]);
+
+        // Closing a tab before the active one keeps the same tab active:
+        tabs.set_active(1);
+        assert_eq!(tabs.close(0).unwrap().title, "a.rs");
+        assert_eq!(tabs.active(), Some(0));
+        assert_eq!(tabs.tabs()[0].title, "b.rs");
+        tabs.close(0);
+        assert_eq!(tabs.active(), None);
+    }
+}
@@ -0,0 +1,159 @@
+//! The undo history of a [`crate::CodeEditor`].
+
+/// One replacement in the buffer: `removed` was replaced by `inserted` at `offset`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct Change {
+    pub offset: usize,
+    pub removed: String,
+    pub inserted: String,
+}
+
+impl Change {
+    fn apply(&self, buffer: &mut String) {
+        buffer.replace_range(
+            self.offset..self.offset + self.removed.len(),
+            &self.inserted,
+        );
+    }
+
+    fn revert(&self, buffer: &mut String) {
+        buffer.replace_range(
+            self.offset..self.offset + self.inserted.len(),
+            &self.removed,
+        );
+    }
+
+    /// The bytes of text kept for this change.
+    fn size(&self) -> usize {
+        self.removed.len() + self.inserted.len()
+    }
+
+    /// Typing a single character, which is coalesced with the typing before it.
+    fn is_typing(&self) -> bool {
+        let mut chars = self.inserted.chars();
+        self.removed.is_empty()
+            && chars.next().is_some_and(|c| c != '\n' && c != '\r')
+            && chars.next().is_none()
+    }
+}
+
+/// Undo and redo steps, each one a list of [`Change`]s in the order they were applied.
+///
+/// Consecutive typed characters are coalesced into a single step;
+/// anything else is one step of its own.
+///
+/// The oldest undo steps are dropped to stay within [`Self::max_steps`] and [`Self::max_bytes`].
+#[derive(Clone, Debug)]
+pub(crate) struct UndoStack {
+    undo: Vec<Vec<Change>>,
+    redo: Vec<Vec<Change>>,
+
+    /// Whether the last undo step is typing that may be continued.
+    typing: bool,
+
+    pub max_steps: usize,
+
+    /// The most text, in bytes, kept by the undo and redo steps together.
+    pub max_bytes: usize,
+}
+
+impl Default for UndoStack {
+    fn default() -> Self {
+        Self {
+            undo: Vec::new(),
+            redo: Vec::new(),
+            typing: false,
+            max_steps: crate::DEFAULT_MAX_UNDO_STEPS,
+            max_bytes: crate::DEFAULT_MAX_UNDO_BYTES,
+        }
+    }
+}
+
+impl UndoStack {
+    /// Forget all steps, keeping the limits.
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+        self.typing = false;
+    }
+
+    /// The number of steps that can be undone.
+    #[cfg(test)]
+    pub fn undo_len(&self) -> usize {
+        self.undo.len()
+    }
+
+    /// Drop the oldest undo steps until the stack is within its limits.
+    pub fn enforce_limits(&mut self) {
+        let step_size = |step: &Vec<Change>| step.iter().map(Change::size).sum::<usize>();
+        let mut bytes: usize = self.undo.iter().chain(&self.redo).map(step_size).sum();
+        let mut dropped = self.undo.len().saturating_sub(self.max_steps);
+        for step in &self.undo[..dropped] {
+            bytes -= step_size(step);
+        }
+        while bytes > self.max_bytes && dropped < self.undo.len() {
+            bytes -= step_size(&self.undo[dropped]);
+            dropped += 1;
+        }
+        if dropped == self.undo.len() {
+            self.typing = false;
+        }
+        self.undo.drain(..dropped);
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Remember changes that were just applied, as one step.
+    pub fn push(&mut self, changes: Vec<Change>) {
+        if changes.is_empty() {
+            return;
+        }
+        self.redo.clear();
+
+        let is_typing = matches!(changes.as_slice(), [change] if change.is_typing());
+        if is_typing && self.typing {
+            if let Some([previous]) = self.undo.last_mut().map(Vec::as_mut_slice) {
+                if previous.offset + previous.inserted.len() == changes[0].offset {
+                    previous.inserted.push_str(&changes[0].inserted);
+                    self.enforce_limits();
+                    return;
+                }
+            }
+        }
+        self.typing = is_typing;
+        self.undo.push(changes);
+        self.enforce_limits();
+    }
+
+    /// Revert the last step. Returns `false` if there was nothing to undo.
+    pub fn undo(&mut self, buffer: &mut String) -> bool {
+        let Some(changes) = self.undo.pop() else {
+            return false;
+        };
+        for change in changes.iter().rev() {
+            change.revert(buffer);
+        }
+        self.redo.push(changes);
+        self.typing = false;
+        true
+    }
+
+    /// Re-apply the last undone step. Returns `false` if there was nothing to redo.
+    pub fn redo(&mut self, buffer: &mut String) -> bool {
+        let Some(changes) = self.redo.pop() else {
+            return false;
+        };
+        for change in &changes {
+            change.apply(buffer);
+        }
+        self.undo.push(changes);
+        self.typing = false;
+        true
+    }
+}